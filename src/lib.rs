@@ -1,8 +1,5 @@
-#![recursion_limit = "1024"]
-
 extern crate proc_macro;
 
-use heck::{ToKebabCase, ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
 use proc_macro::TokenStream;
 use proc_macro2::{Ident, Span};
 use quote::quote;
@@ -17,363 +14,7564 @@ use syn::*;
 /// * `#[ExistingTypePath = "crate::schema::sql_types::NewEnum"]` specifies
 ///   the path to a corresponding diesel type that was already created by the
 ///   diesel CLI. If omitted, the type will be generated by this macro.
-///   *Note*: Only applies to `postgres`, will error if specified for other databases
+///   The path may also be given unquoted, e.g.
+///   `#[ExistingTypePath(crate::schema::sql_types::NewEnum)]`.
+///   *Note*: Only applies to `postgres`, will error if specified for other databases.
+///   With `mysql` or `sqlite` also enabled, the diesel type for those is still generated.
+/// * `#[ExistingTypeAlias]` additionally emits a `pub type` alias of the `ExistingTypePath`,
+///   named `<enum name>Mapping` (or the `DieselType`, or the name given, e.g.
+///   `#[ExistingTypeAlias = "NewEnumPgMapping"]`), so code written against the generated
+///   type keeps compiling. The name cannot be that of the `mysql` or `sqlite` type.
+///   This also adds the `SQL_TYPE_NAME` and `LABELS` constants (see below) to the
+///   existing type, so it must be declared in the same crate.
+/// * `#[DbSchemaRoot = "crate::schema::sql_types"]` infers `ExistingTypePath` as
+///   the given module followed by the enum name, e.g. `crate::schema::sql_types::NewEnum`.
+///   An explicit `ExistingTypePath` takes precedence.
+///   *Note*: Only applies to `postgres`
+/// * `#[PgOid = 16385]` and `#[PgArrayOid = 16384]` give the oids of the postgres type
+///   and of its array type, which diesel then uses instead of looking the type up by name
+///   (once per connection). Only for deployments where the oids are stable, e.g. when the
+///   type is created in a template database. Both must be specified.
+///   *Note*: Cannot be specified alongside `ExistingTypePath`, or a non-enum postgres storage
+/// * `#[PgTypeStyle = "kebab-case"]` names the postgres type after the enum in the given
+///   casing (any of those of `DbValueStyle`), e.g. `new-enum`, instead of snake_case.
+///   *Note*: Cannot be specified alongside `PgType` or `ExistingTypePath`
 /// * `#[DieselType = "NewEnumMapping"]` specifies the name for the diesel type
-///   to create. If omitted, uses `<enum name>Mapping`.
-///   *Note*: Cannot be specified alongside `ExistingTypePath`
+///   to create. If omitted, uses `<enum name>Mapping`. With `ExistingTypePath`, this
+///   is the type used by `mysql` and `sqlite`.
+/// * `#[DieselTypeByBackend(mysql = "TasksStatusEnum")]` gives the diesel type a
+///   different name on the listed backends (`postgres`, `mysql` or `sqlite`), e.g. to
+///   match the names `diesel print-schema` uses on each database. Each name gets its
+///   own generated type; other backends use the `DieselType`.
+///   *Note*: A `postgres` name cannot be specified alongside `ExistingTypePath`
 /// * `#[DbValueStyle = "snake_case"]` specifies a renaming style from each of
 ///   the rust enum variants to each of the database variants. Either `camelCase`,
 ///   `kebab-case`, `PascalCase`, `SCREAMING_SNAKE_CASE`, `snake_case`,
-///   `verbatim`. If omitted, uses `snake_case`.
+///   `verbatim`. If omitted, uses `snake_case`. The style may also be given
+///   unquoted, e.g. `#[DbValueStyle(snake_case)]`.
+/// * `#[DbRenameAll = "camelCase"]` is an alias of `DbValueStyle`, named after serde's
+///   `rename_all`. Only one of the two may be given.
+/// * `#[DbValueStyleFn = "crate::my_style"]` specifies a function, called at runtime,
+///   which maps each rust enum variant name to its database variant. The function
+///   must have the signature `fn(&str) -> String` and is called at most once per
+///   variant. Use this for naming schemes that `DbValueStyle` cannot express.
+///   *Note*: Cannot be specified alongside `DbValueStyle`.
+/// * `#[DbValueNormalize = "str::to_lowercase"]` specifies a function, with the same
+///   signature, which canonicalizes values: values read from the database are
+///   normalized before being matched (e.g. `'Active'` and `'ACTIVE'` are read as
+///   `Active`), and the normalized db value is written, so that mixed data converges as
+///   rows are updated. Checks and SQL built at compile time (e.g. `MYSQL_COLUMN_TYPE`)
+///   use the db values as declared, so declare them in their canonical form.
+/// * `#[MaxDbLen = 32]` checks at compile time that every db value fits in N bytes,
+///   e.g. for a `VARCHAR(32)` column, which could otherwise truncate or reject it at
+///   runtime. Values computed by `DbValueStyleFn` are not checked.
+/// * `#[DbCharWidth = 4]` is for legacy fixed-width `CHAR(n)` columns: the diesel
+///   `ToSql` impls right-pad the db values with spaces to n characters, and `FromSql`
+///   trims trailing spaces before matching. Implies text storage (the postgres type is
+///   `bpchar`), and every db value must fit in n characters. Like the `Db*With` hooks
+///   below, other conversions keep using the unpadded db values. Not available with
+///   `DbValueStyleFn` or the `Db*With` hooks.
+/// * `#[DbSerializeWith = "crate::encrypt"]` and `#[DbDeserializeWith = "crate::decrypt"]`
+///   replace how the diesel `ToSql` and `FromSql` impls convert text and enum values,
+///   e.g. for encryption or remapping. The functions must have the signatures
+///   `fn(&MyEnum) -> String` and `fn(&[u8]) -> Result<MyEnum, E>` (with `E` convertible
+///   to a boxed error). Either can be given alone. Other conversions (e.g.
+///   `to_sql_literal`, `DbTextMapping` or `serde`) keep using the db values.
+/// * `#[DbArrayStorage = "json"]` additionally generates a `<enum name>ArrayMapping`
+///   diesel type, which stores a `Vec<enum>` in a single text column on `mysql`
+///   and `sqlite`. Either `json` (a JSON array of strings) or `delimited`
+///   (values joined by a delimiter, see below).
+///   Values are read directly into a `Vec<enum>`; to write them, use the
+///   generated `<enum name>Array` newtype (e.g. via `#[diesel(serialize_as = ...)]`).
+///   *Note*: Only applies to `mysql` and `sqlite`, postgres has native arrays.
+/// * `#[DbArrayDelimiter = ","]` specifies the delimiter used by
+///   `#[DbArrayStorage = "delimited"]`. If omitted, uses `,`.
+/// * `#[DbCustomBackend = "my_backend::MyBackend"]` additionally implements the
+///   traits for a third-party diesel backend, storing the enum as `Text`. The backend
+///   must support `Text` (i.e. `String: FromSql<Text, _>` and `str: ToSql<Text, _>`).
+/// * `#[DbEnumDsl]` additionally generates a `<snake case enum name>_dsl` module
+///   with one filter helper per variant, e.g. `my_enum_dsl::is_foo(col)` for
+///   `col.eq(MyEnum::Foo)`, and a `<enum name>ExpressionMethods` trait with the same
+///   helpers as methods, e.g. `col.is_foo()`. No helper is generated for the
+///   `db_null_variant`.
+/// * `#[DbEnumIndex]` adds `index()`, the position of the variant in declaration order
+///   (starting at 0), and implements `TryFrom<usize>` for the reverse, returning the
+///   index as the error if no variant has it. The indexes don't depend on the storage,
+///   e.g. for bit sets or arrays keyed by variant.
+/// * `#[DbEnumStrEq]` implements `PartialEq` between the enum and `str`, comparing
+///   against the db value, e.g. `MyEnum::Foo == "foo"`.
+/// * `#[DbEnumClap]` implements `clap::ValueEnum`, using the db values as the command
+///   line values. The enum must also implement `Clone`.
+///   *Note*: Requires the `clap` feature, and a dependency on `clap` 4
+/// * `#[DbEnumRocket]` implements rocket's `FromParam` and `FromFormField`, parsing
+///   the db values. The null variant cannot be parsed.
+///   *Note*: Requires the `rocket` feature, and a dependency on `rocket` 0.5
+/// * `#[DbEnumFake]` implements `fake::Dummy<fake::Faker>`, picking one of the
+///   variants uniformly at random, so the enum works with `Faker.fake()` and in structs
+///   deriving `Dummy`. It is a compile error if every variant is `#[cfg]`'d out.
+///   *Note*: Requires the `fake` feature, and a dependency on `fake` 2
+/// * `#[DbEnumArbitrary]` implements `arbitrary::Arbitrary`, choosing a variant from the
+///   fuzzer input, e.g. for fuzz targets constructing rows or queries with the enum.
+///   *Note*: Requires the `arbitrary` feature, and a dependency on `arbitrary` 1.3 or later
+/// * `#[DbEnumPostgresTypes]` implements `postgres_types::ToSql` and `FromSql` with the
+///   same db values, so the enum can also be used with `tokio-postgres` (or `postgres`).
+///   Both accept the postgres enum type and the text types. The null variant maps to `NULL`.
+///   *Note*: Requires the `postgres-types` feature, and a dependency on `postgres-types` 0.2
+/// * `#[DbEnumSqlx]` implements `sqlx::Type`, `Encode` and `Decode` with the same db values,
+///   so the enum can also be used with sqlx, for each backend enabled on this crate. With
+///   postgres, it is bound as the enum type, and also reads text columns. The null variant
+///   maps to `NULL`. Requires text or enum storage, and can't be used with `DbSerializeWith`.
+///   *Note*: Requires the `sqlx` feature, and a dependency on `sqlx` 0.7
+/// * `#[DbEnumJson]` additionally implements the diesel traits for `Json` (and `Jsonb`
+///   with `postgres`), reading and writing the db value as a bare JSON string, e.g. for
+///   `SELECT data->'status'`. Requires the `postgres` or `mysql` feature.
+/// * `#[DbEnumOrd]` implements `PartialOrd` and `Ord` following the declaration
+///   order of the variants, which is how postgres (and mysql) order enum values.
+///   The enum must also implement `Eq`. With `postgres`, this also adds
+///   `assert_order_matches_db(conn)` to check the order against the database type,
+///   and with `mysql`, `assert_mysql_order_matches_db(conn, table, column)` to check
+///   the `ENUM(...)` definition of a column.
+/// * `#[DbEnumSkip(as_expression, queryable)]` suppresses the listed impls, for
+///   when they collide with impls written by hand or derived elsewhere. Any of
+///   `as_expression`, `to_sql`, `from_sql` and `queryable`. Skipping
+///   `as_expression` also skips the `ToSql` impl for `Nullable`, as
+///   `#[derive(AsExpression)]` provides both.
+/// * `#[DbReadOnly]` generates only the impls for reading the enum (`FromSql` and
+///   `Queryable`), e.g. for reporting read models, and `#[DbWriteOnly]` only those
+///   for writing it (`ToSql` and `AsExpression`), e.g. for append-only audit enums.
+///   A read-only enum can't be compared against a column in a filter either, as
+///   that goes through `AsExpression`.
+/// * `#[DbEnumCodegen(inline, minimal)]` tunes the generated code: `inline` marks the
+///   helpers converting between variants and db values `#[inline]`, so they can be inlined
+///   into the diesel impls instantiated in other crates, and `minimal` leaves out the
+///   `AsExpression` impls for `&&MyEnum`, to compile less code. The impls for `&MyEnum`
+///   and `Nullable` are kept, as inserting by reference and `Option` fields need them.
+/// * `#[DbTextMapping]` additionally generates a `<enum name>TextMapping` diesel type
+///   (or the name given, e.g. `#[DbTextMapping = "LegacyStatus"]`), which stores the
+///   enum as text on every backend. This allows using the same enum with an old text
+///   column and a new enum column, e.g. during a migration.
+/// * `#[DbEnumStr]` additionally generates `<enum name>Str` (or the name given), a
+///   newtype around `String` which can be used in place of the enum for the same columns.
+///   `new` only accepts the db values of known variants, but any value can be read (or
+///   wrapped with `new_unchecked`), and `known()` returns the variant, if there is one.
+///   Not available with integer storage, `DbEnumTrustedRead` or the `Db*With` hooks.
+/// * `#[DbEnumAsText]` additionally implements the diesel traits for the built-in `Text`
+///   type, so the enum can be compared with and read from text expressions (e.g.
+///   `col::text`, or a column declared as `Text` in a hand-written schema). With
+///   `postgres`, `Vec<MyEnum>` then also round-trips through `Array<Text>` (`text[]`).
+/// * `#[DbEnumTrustedRead]` speeds up reading text values, for databases known to only
+///   hold valid values: only the shortest prefix which tells the variants apart is
+///   compared, so e.g. `"lar"` would be read as `Large`. Unknown values still fail, but
+///   with a fixed error message. Not available with `DbValueStyleFn` or `DbValueNormalize`.
+/// * `#[DbEnumI18n]` adds an `i18n_key()` method, which returns the key of the variant in
+///   localization catalogs: a prefix, `enum.<snake case enum name>` by default (or the
+///   one given, e.g. `#[DbEnumI18n = "labels.status"]`), and the db value, e.g.
+///   `enum.status.active`. Not available with `DbValueStyleFn` or `DbValueNormalize`.
+/// * `#[DbEnumCockroach]` adapts the postgres migration helpers to CockroachDB:
+///   `sync_variants` uses `ADD VALUE IF NOT EXISTS` and fails inside a transaction,
+///   `create_type_migration` runs outside of one, and `PG_COMMENT_SQL` is not generated.
+/// * `#[DbEnumNoReexport]` skips the `pub use` of the generated diesel types next to the
+///   enum, e.g. to avoid a name collision or keep them out of a library's public API. They
+///   can still be named (and re-exported deliberately) from the `db_enum_impl_<Enum>` module.
+/// * `#[DbEnumStrictAttrs]` rejects the attributes of this derive in the wrong place,
+///   which are otherwise ignored, e.g. `ExistingTypePath` on a variant or `db_rename` on
+///   the enum.
+/// * `#[DbEnumLocal]` supports enums declared in a function body (e.g. in a test): the
+///   diesel types are declared next to the enum instead of being re-exported from a
+///   module, which couldn't name the enum. Not available with `DbEnumDsl`,
+///   `DbTextMapping` or `generate_tests`, and there is no serde module.
+/// * `#[DbStorageByBackend(postgres = "enum", mysql = "text", sqlite = "integer")]`
+///   chooses how the enum is stored on each backend: `enum` (the native enum type,
+///   the default for `postgres` and `mysql`), `text` or `varchar` (the db value, the
+///   default for `sqlite`), `integer` (the declaration index of the variant, starting at 0) or
+///   `bigint` (the same number, in a 64 bit `BigInt` column). For `mysql`, the number
+///   can also be stored in an unsigned column with `unsigned_integer` (`INT UNSIGNED`)
+///   or `unsigned_smallint` (`SMALLINT UNSIGNED`).
+///   Backends which are not listed keep their default.
+///   *Note*: `ExistingTypePath` requires `postgres = "enum"`
+/// * `#[db_enum(...)]` sets any of the above in a single attribute, using
+///   snake_case keys: `existing_type_path`, `existing_type_alias`, `schema_root`,
+///   `pg_type`, `pg_type_style`, `pg_oid`, `pg_array_oid`, `diesel_type`, `value_style`,
+///   `rename_all`, `value_style_fn`, `value_normalize`, `max_db_len`, `char_width`,
+///   `serialize_with`, `deserialize_with`, `array_storage`, `array_delimiter`,
+///   `custom_backend`, `dsl`, `ord`, `index`, `str_eq`,
+///   `as_text`, `clap`, `rocket`, `fake`, `arbitrary`, `postgres_types`, `sqlx`, `json`,
+///   `trusted_read`, `cockroach`, `local`, `no_reexport`, `strict_attrs`, `skip`,
+///   `read_only`, `write_only`, `codegen`, `storage_by_backend`, `diesel_type_by_backend`,
+///   `text_mapping`, `str` and `i18n`, e.g. `#[db_enum(pg_type = "my_enum", value_style(camelCase), ord)]`.
+///   Additionally, `#[db_enum(generate_tests)]` emits `#[cfg(test)]` unit tests into
+///   the user crate, checking that every variant round-trips through its db value
+///   and that all db values are unique.
+///
+/// The derive also adds an `all()` associated function, which returns every
+/// variant in declaration order, e.g. for `.filter(col.eq_any(MyEnum::all()))`,
+/// a `matches_db_str(s)` method, which checks `s` against the db value,
+/// `to_sql_literal()` / `to_sql_literal_for::<DB>()`, which quote the db value as an
+/// SQL string literal, `db_rank(col)`, an expression of type `<enum name>Rank<C>` to
+/// `ORDER BY` in declaration order on any backend, e.g. where the enum is stored as text
+/// (which sorts lexically; not available with the 'diesel-1' feature),
+/// and `markdown_table()`, which lists the variants with their db
+/// values and doc comments, e.g. for runbooks. With `mysql`, it also adds a
+/// `MYSQL_COLUMN_TYPE` constant, e.g. `"ENUM('foo', 'bar')"` (or `"VARCHAR(3)"`, sized
+/// for the longest db value, with text storage), for use in migrations (not available
+/// with `DbValueStyleFn`).
+/// The generated diesel types themselves carry `SQL_TYPE_NAME`, the postgres type name
+/// (as for `PG_TYPE_NAME`, only on the diesel type of a postgres enum, not with text or
+/// integer storage), and `LABELS`, the db values of the variants except a
+/// `db_null_variant` (not available with `DbValueStyleFn` or `DbValueNormalize`), for
+/// code which only has the diesel type in scope.
+/// With `postgres`, `PG_TYPE_NAME` and `PG_ARRAY_TYPE_NAME` constants hold the names of
+/// the type and its array type as written in SQL, e.g. `my_enum` and `my_enum[]`,
+/// `print_schema_patch(schema, mapping_path)` returns a diesel-cli `patch_file` which
+/// replaces the type `diesel print-schema` generates for the enum with a re-export, and
+/// a `PG_COMMENT_SQL` constant holds a `COMMENT ON TYPE` statement built
+/// from the doc comments of the enum and its variants, `sync_variants(conn, dry_run)`
+/// adds the variants missing from the postgres type, `pg_sort_order(conn)` returns the
+/// `enumsortorder` of each label, `create_type_migration(version)` returns a diesel
+/// `Migration` creating the type (and with the `refinery` feature,
+/// `create_type_refinery_migration(version)` a refinery one),
+/// `pg_remove_values_sql(previous, columns, array_columns, defaults)`
+/// returns the statements recreating the type without the labels of removed variants
+/// (`pg_remove_values_migration(conn, previous)` finds the columns and defaults), and
+/// `find_columns_using_type(conn)` lists the `(schema, table, column)` of the columns
+/// of the type or its array type (all unless using `ExistingTypePath`).
+/// With `sqlite`, which has no enum type, `install_validation(conn, "table", "column")`
+/// creates triggers rejecting any value of the column which is not a variant, and
+/// `assert_check_matches_db(conn, "table", "column")` checks that an existing
+/// `CHECK (column IN (...))` constraint allows exactly the db values.
+/// Enums stored as a postgres or mysql enum also get `drift_report(db_values)`, along with
+/// `pg_drift_report(conn)` or `mysql_drift_report(conn, table, column)`, which list every
+/// difference from the database as a `<enum name>DriftReport` (`Serialize` with the
+/// `serde` feature). The other checks, such as `sync_variants` and
+/// `assert_check_matches_db`, keep returning their own results and `String` errors.
+///
+/// Where the database compares the stored values in declaration order (postgres and
+/// mysql enums, and integer storage on every enabled backend), the generated diesel types
+/// implement `SqlOrd`, so enum columns can be aggregated with `max()` and `min()` (loading
+/// an `Option<MyEnum>`). Text storage compares lexically, so it doesn't implement `SqlOrd`.
+/// Note that mysql's `MAX()` and `MIN()` compare `ENUM` values as strings.
+///
+/// With the `serde` feature, the derive also adds a `<snake case enum name>_as_db_string`
+/// module, for (de)serializing individual fields as their db value with
+/// `#[serde(with = "my_enum_as_db_string")]`. This requires a dependency on `serde`.
+///
+/// With the `diesel-1` feature, the impls are generated for diesel 1.4 instead. This
+/// rejects the attributes which rely on diesel 2 (e.g. integer storage or
+/// `db_null_variant`), and skips the helpers which run queries (e.g. `sync_variants`).
+///
+/// With the `live-check` feature (and `postgres`), if `DATABASE_URL` is set at compile
+/// time, the derive checks the db values against the labels of the postgres type in that
+/// database, and fails to compile if they differ. This runs `psql`, which must be on the
+/// `PATH`. Enums using `ExistingTypePath` or a non-enum postgres storage are not checked.
+/// Without `DATABASE_URL`, the labels are instead read from a snapshot file,
+/// `db_enums.json` next to `Cargo.toml` (or the path in `DIESEL_DERIVE_ENUM_SNAPSHOT`),
+/// if it exists. Building with `DATABASE_URL` and `DIESEL_DERIVE_ENUM_UPDATE_SNAPSHOT=1`
+/// (re)writes the snapshot from the database, in place of a separate command. Cargo
+/// rebuilds the crate when these variables or the snapshot change.
+///
+/// Project-wide defaults can be set in a `db-enum.toml` next to `Cargo.toml` (or at the
+/// path in `DIESEL_DERIVE_ENUM_CONFIG`): `value_style` (as for `DbValueStyle`),
+/// `pg_type_style` (as for `PgTypeStyle`),
+/// `schema_root` (as for `DbSchemaRoot`, for enums which don't name their postgres type
+/// otherwise) and a `[storage_by_backend]` table (as for `DbStorageByBackend`). The
+/// attributes of an enum take precedence. Only `key = "value"` lines and comments are
+/// supported, and any other TOML is an error. Cargo rebuilds the crate when the file or
+/// `DIESEL_DERIVE_ENUM_CONFIG` change, but can't watch for a file which doesn't exist yet:
+/// after adding one, run `cargo clean -p <your crate>`.
 ///
 /// ## Variant attributes
 ///
 /// * `#[db_rename = "variant"]` specifies the db name for a specific variant.
+///   Any unicode is allowed (e.g. `"café"`), but values which an enabled backend
+///   cannot store are rejected: postgres enum labels are limited to 63 bytes, and
+///   mysql enum values to 255 characters without trailing spaces.
+///   With `mysql` only, a byte string (e.g. `b"\xE9tat"`) gives a value which isn't
+///   valid UTF-8, such as a latin-1 code, which is written and read as those exact
+///   bytes. Helpers working with strings (e.g. `matches_db_str`) see the invalid bytes
+///   escaped, as `"\\xE9tat"`.
+/// * `#[db_null_variant]` marks (at most) one variant which represents SQL `NULL`.
+///   When reading a `Nullable` column, `NULL` is deserialized into this variant
+///   (so the field need not be an `Option`), and it is written back as `NULL`.
+/// * `#[db_value = 42]` sets the number stored for a variant with `integer` or `bigint`
+///   storage (see `DbStorageByBackend`), e.g. to match an existing numbering scheme.
+///   Negative numbers are written as `#[db_value(-1)]`. As with enum discriminants,
+///   variants without one follow the previous variant (starting at 0).
+///   Duplicate numbers are rejected.
+/// * `#[db_empty_fallback]` marks (at most) one variant which an empty string is read
+///   as, instead of failing. MySQL stores `''` in place of invalid `ENUM` values when not
+///   in strict mode, so there an empty db value is rejected in favour of this attribute.
+/// * `#[db_renamed_from = "old"]` records the previous db value of a variant. With
+///   `postgres`, the `ALTER TYPE ... RENAME VALUE 'old' TO 'new'` statements for these
+///   are collected in the `PG_RENAME_VALUES_SQL` constant, for use in a migration
+///   (unless using `ExistingTypePath`).
+/// * `#[db_label = "Active user"]` sets the human-readable label of a variant, returned
+///   by `label()` (which otherwise gives the variant name). `MyEnum::choices()` lists
+///   each variant with its db value and label, e.g. to render a select in a web form,
+///   except a `db_null_variant`, which is stored as `NULL`.
+///
+/// Variants may be conditionally compiled with `#[cfg(...)]`, which is carried over to
+/// the generated code for them. SQL built at compile time (e.g. `MYSQL_COLUMN_TYPE`) and
+/// the compile-time checks still include every variant.
 #[proc_macro_derive(
     DbEnum,
-    attributes(PgType, DieselType, ExistingTypePath, DbValueStyle, db_rename)
+    attributes(
+        db_enum,
+        PgType,
+        PgTypeStyle,
+        PgOid,
+        PgArrayOid,
+        DieselType,
+        ExistingTypePath,
+        ExistingTypeAlias,
+        DbSchemaRoot,
+        DbValueStyle,
+        DbRenameAll,
+        DbValueStyleFn,
+        DbValueNormalize,
+        MaxDbLen,
+        DbCharWidth,
+        DbSerializeWith,
+        DbDeserializeWith,
+        DbArrayStorage,
+        DbArrayDelimiter,
+        DbCustomBackend,
+        DbEnumDsl,
+        DbEnumOrd,
+        DbEnumIndex,
+        DbEnumStrEq,
+        DbEnumAsText,
+        DbEnumTrustedRead,
+        DbEnumCockroach,
+        DbEnumLocal,
+        DbEnumNoReexport,
+        DbEnumStrictAttrs,
+        DbEnumClap,
+        DbEnumRocket,
+        DbEnumFake,
+        DbEnumArbitrary,
+        DbEnumPostgresTypes,
+        DbEnumSqlx,
+        DbEnumJson,
+        DbEnumSkip,
+        DbReadOnly,
+        DbWriteOnly,
+        DbEnumCodegen,
+        DbStorageByBackend,
+        DieselTypeByBackend,
+        DbTextMapping,
+        DbEnumStr,
+        DbEnumI18n,
+        db_rename,
+        db_renamed_from,
+        db_value,
+        db_null_variant,
+        db_empty_fallback,
+        db_label
+    )
 )]
 pub fn derive(input: TokenStream) -> TokenStream {
     let input: DeriveInput = parse_macro_input!(input as DeriveInput);
+    derive_db_enum(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
 
-    let existing_mapping_path = val_from_attrs(&input.attrs, "ExistingTypePath");
-    if !cfg!(feature = "postgres") && existing_mapping_path.is_some() {
-        panic!("ExistingTypePath attribute only applies when the 'postgres' feature is enabled");
-    }
-
-    // we could allow a default value here but... I'm not very keen
-    // let existing_mapping_path = existing_mapping_path
-    //     .unwrap_or_else(|| format!("crate::schema::sql_types::{}", input.ident));
+/// Generate a `DbEnum` from the `CREATE TYPE ... AS ENUM` statement of a SQL file, e.g.
+///
+/// ```ignore
+/// db_enum_from_sql!("migrations/2022-10-30-154319_setup/up.sql", "task_status");
+/// ```
+///
+/// expands to a `pub enum TaskStatus`, with a variant for each label of the type, deriving
+/// `DbEnum`, `Debug`, `Clone`, `Copy`, `PartialEq` and `Eq`. The path is relative to the
+/// crate being compiled, which is rebuilt when the file changes. The variants are the
+/// labels in UpperCamelCase, and keep the label as their db value. The labels must be plain
+/// `'...'` strings, and SQL which can't be split into tokens (e.g. an unterminated
+/// string) is an error rather than being skipped. Any attributes before
+/// the path are added to the enum, e.g.
+/// `db_enum_from_sql!(#[derive(Hash)] #[DbSchemaRoot = "crate::schema::sql_types"] "up.sql", "task_status")`.
+///
+/// Only `postgres` has enum types, but the generated enum may be used with any backend.
+#[proc_macro]
+pub fn db_enum_from_sql(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as FromSqlInput);
+    db_enum_from_sql_impl(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
 
-    let pg_internal_type = val_from_attrs(&input.attrs, "PgType");
+struct FromSqlInput {
+    attrs: Vec<Attribute>,
+    path: LitStr,
+    type_name: LitStr,
+}
 
-    if existing_mapping_path.is_some() && pg_internal_type.is_some() {
-        panic!("Cannot specify both `ExistingTypePath` and `PgType` attributes");
+impl parse::Parse for FromSqlInput {
+    fn parse(input: parse::ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let path = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let type_name = input.parse()?;
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+        Ok(FromSqlInput {
+            attrs,
+            path,
+            type_name,
+        })
     }
+}
 
-    let pg_internal_type = pg_internal_type.unwrap_or(input.ident.to_string().to_snake_case());
-
-    let new_diesel_mapping = val_from_attrs(&input.attrs, "DieselType");
-    if existing_mapping_path.is_some() && new_diesel_mapping.is_some() {
-        panic!("Cannot specify both `ExistingTypePath` and `DieselType` attributes");
-    }
-    let new_diesel_mapping =
-        new_diesel_mapping.unwrap_or_else(|| format!("{}Mapping", input.ident));
+fn db_enum_from_sql_impl(input: &FromSqlInput) -> Result<proc_macro2::TokenStream> {
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let path = std::path::Path::new(&manifest_dir).join(input.path.value());
+    let sql = std::fs::read_to_string(&path).map_err(|e| {
+        Error::new(
+            input.path.span(),
+            format!("could not read {}: {}", path.display(), e),
+        )
+    })?;
+    let type_name = input.type_name.value();
+    let labels = parse_create_enum_type(&sql, &type_name)
+        .map_err(|message| Error::new(input.type_name.span(), message))?;
+    // a schema-qualified type is named after the type itself
+    let pg_type = type_name.rsplit('.').next().unwrap_or_default();
+    let enum_ty = enum_name_from_pg_type(pg_type, &input.type_name)?;
+    let generated = generate_enum_from_labels(&input.attrs, &enum_ty, pg_type, &labels)?;
+    let sql_path = path.display().to_string();
+    Ok(quote! {
+        // rebuild when the SQL file changes
+        const _: &[u8] = include_bytes!(#sql_path);
 
-    // Maintain backwards compatibility by defaulting to snake case.
-    let case_style =
-        val_from_attrs(&input.attrs, "DbValueStyle").unwrap_or_else(|| "snake_case".to_string());
-    let case_style = CaseStyle::from_string(&case_style);
+        #generated
+    })
+}
 
-    let existing_mapping_path = existing_mapping_path.map(|v| {
-        v.parse::<proc_macro2::TokenStream>()
-            .expect("ExistingTypePath is not a valid token")
-    });
-    let new_diesel_mapping = Ident::new(new_diesel_mapping.as_ref(), Span::call_site());
-    if let Data::Enum(syn::DataEnum {
-        variants: data_variants,
-        ..
-    }) = input.data
-    {
-        generate_derive_enum_impls(
-            &existing_mapping_path,
-            &new_diesel_mapping,
-            &pg_internal_type,
-            case_style,
-            &input.ident,
-            &data_variants,
+fn enum_name_from_pg_type(pg_type: &str, span: &LitStr) -> Result<Ident> {
+    let mut enum_ty = parse_str::<Ident>(&pg_type.to_upper_camel_case()).map_err(|_| {
+        Error::new(
+            span.span(),
+            format!("`{}` does not make a valid enum name", pg_type),
         )
-    } else {
-        syn::Error::new(
-            Span::call_site(),
-            "derive(DbEnum) can only be applied to enums",
-        )
-        .to_compile_error()
-        .into()
-    }
+    })?;
+    enum_ty.set_span(span.span());
+    Ok(enum_ty)
 }
 
-fn val_from_attrs(attrs: &[Attribute], attrname: &str) -> Option<String> {
-    for attr in attrs {
-        if attr.path().is_ident(attrname) {
-            match &attr.meta {
-                Meta::NameValue(MetaNameValue {
-                    value:
-                        Expr::Lit(ExprLit {
-                            lit: Lit::Str(lit_str),
-                            ..
-                        }),
-                    ..
-                }) => return Some(lit_str.value()),
-                _ => panic!(
-                    "Attribute '{}' must have form: {} = \"value\"",
-                    attrname, attrname
+/// A `pub enum` deriving `DbEnum`, with an UpperCamelCase variant for each label
+fn generate_enum_from_labels(
+    attrs: &[Attribute],
+    enum_ty: &Ident,
+    pg_type: &str,
+    labels: &[String],
+) -> Result<proc_macro2::TokenStream> {
+    // the derive defaults to the snake_case enum name, which is usually the type already
+    let pg_type_attr = (enum_ty.to_string().to_snake_case() != pg_type).then(|| {
+        quote! { #[PgType = #pg_type] }
+    });
+    let mut variants: Vec<Ident> = Vec::with_capacity(labels.len());
+    for label in labels {
+        let variant = parse_str::<Ident>(&label.to_upper_camel_case()).map_err(|_| {
+            Error::new(
+                enum_ty.span(),
+                format!("label `{}` does not make a valid variant name", label),
+            )
+        })?;
+        if let Some(index) = variants.iter().position(|other| *other == variant) {
+            return Err(Error::new(
+                enum_ty.span(),
+                format!(
+                    "labels `{}` and `{}` both make the variant `{}`",
+                    labels[index], label, variant
                 ),
-            }
+            ));
         }
+        variants.push(variant);
     }
-    None
+    Ok(quote! {
+        #[derive(diesel_derive_enum::DbEnum, Debug, Clone, Copy, PartialEq, Eq)]
+        #pg_type_attr
+        #(#attrs)*
+        pub enum #enum_ty {
+            #(
+                #[db_rename = #labels]
+                #variants,
+            )*
+        }
+    })
 }
 
-/// Defines the casing for the database representation.  Follows serde naming convention.
-#[derive(Copy, Clone, Debug, PartialEq)]
-enum CaseStyle {
-    Camel,
-    Kebab,
-    Pascal,
-    Upper,
-    ScreamingSnake,
-    Snake,
-    Verbatim,
+#[derive(Debug, PartialEq)]
+enum SqlToken {
+    /// An unquoted word, lowercased as postgres does
+    Word(String),
+    /// A `"quoted"` identifier
+    Ident(String),
+    /// A `'string'` literal
+    Str(String),
+    /// An `E'string'` literal, with its backslash escapes as written
+    EscapeStr(String),
+    /// A `$tag$string$tag$` literal, e.g. the body of a function
+    DollarStr(String),
+    Punct(char),
 }
 
-impl CaseStyle {
-    fn from_string(name: &str) -> Self {
-        match name {
-            "camelCase" => CaseStyle::Camel,
-            "kebab-case" => CaseStyle::Kebab,
-            "PascalCase" => CaseStyle::Pascal,
-            "SCREAMING_SNAKE_CASE" => CaseStyle::ScreamingSnake,
-            "UPPERCASE" => CaseStyle::Upper,
-            "snake_case" => CaseStyle::Snake,
-            "verbatim" | "verbatimcase" => CaseStyle::Verbatim,
-            s => panic!("unsupported casing: `{}`", s),
+/// Split SQL into tokens, skipping comments and whitespace. Only what's needed to find
+/// `CREATE TYPE` statements is understood, so e.g. numbers come out as words, but every
+/// kind of string and comment is skipped over as a whole.
+fn sql_tokens(sql: &str) -> std::result::Result<Vec<SqlToken>, String> {
+    let mut chars = sql.chars().peekable();
+    let mut tokens = Vec::new();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            // `E'...'`, where a backslash escapes the next character
+            'e' | 'E' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('\\') => {
+                            value.push('\\');
+                            value.push(chars.next().ok_or("unterminated quote")?);
+                        }
+                        Some('\'') if chars.peek() == Some(&'\'') => {
+                            chars.next();
+                            value.push_str("''");
+                        }
+                        Some('\'') => break,
+                        Some(other) => value.push(other),
+                        None => return Err("unterminated quote".to_string()),
+                    }
+                }
+                tokens.push(SqlToken::EscapeStr(value));
+            }
+            // `$tag$...$tag$` (or `$$...$$`), unlike a positional parameter such as `$1`
+            '$' => match dollar_quote_tag(&chars) {
+                Some(tag) => {
+                    for _ in 0..tag.chars().count() + 1 {
+                        chars.next();
+                    }
+                    let delimiter = format!("${}$", tag);
+                    let mut value = String::new();
+                    while !value.ends_with(&delimiter) {
+                        value.push(
+                            chars
+                                .next()
+                                .ok_or_else(|| format!("unterminated `{}` string", delimiter))?,
+                        );
+                    }
+                    value.truncate(value.len() - delimiter.len());
+                    tokens.push(SqlToken::DollarStr(value));
+                }
+                None => tokens.push(SqlToken::Punct('$')),
+            },
+            '-' if chars.peek() == Some(&'-') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut depth = 1;
+                let mut prev = '\0';
+                while depth > 0 {
+                    let c = chars.next().ok_or("unterminated comment")?;
+                    match (prev, c) {
+                        ('/', '*') => {
+                            depth += 1;
+                            prev = '\0';
+                            continue;
+                        }
+                        ('*', '/') => {
+                            depth -= 1;
+                            prev = '\0';
+                            continue;
+                        }
+                        _ => {}
+                    }
+                    prev = c;
+                }
+            }
+            '\'' | '"' => {
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some(q) if q == c && chars.peek() == Some(&c) => {
+                            chars.next();
+                            value.push(c);
+                        }
+                        Some(q) if q == c => break,
+                        Some(other) => value.push(other),
+                        None => return Err("unterminated quote".to_string()),
+                    }
+                }
+                tokens.push(if c == '\'' {
+                    SqlToken::Str(value)
+                } else {
+                    SqlToken::Ident(value)
+                });
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut word = c.to_lowercase().to_string();
+                while let Some(&c) = chars.peek() {
+                    if !(c.is_alphanumeric() || c == '_' || c == '$') {
+                        break;
+                    }
+                    word.extend(c.to_lowercase());
+                    chars.next();
+                }
+                tokens.push(SqlToken::Word(word));
+            }
+            c => tokens.push(SqlToken::Punct(c)),
         }
     }
+    Ok(tokens)
 }
 
-fn generate_derive_enum_impls(
-    existing_mapping_path: &Option<proc_macro2::TokenStream>,
-    new_diesel_mapping: &Ident,
-    pg_internal_type: &str,
-    case_style: CaseStyle,
-    enum_ty: &Ident,
-    variants: &syn::punctuated::Punctuated<Variant, syn::token::Comma>,
-) -> TokenStream {
-    let modname = Ident::new(&format!("db_enum_impl_{}", enum_ty), Span::call_site());
-    let variant_ids: Vec<proc_macro2::TokenStream> = variants
-        .iter()
-        .map(|variant| {
-            if let Fields::Unit = variant.fields {
-                let id = &variant.ident;
-                quote! {
-                    #enum_ty::#id
-                }
-            } else {
-                panic!("Variants must be fieldless")
+/// The tag of the dollar quote which `chars` starts, just after its first `$`, i.e.
+/// `tag` for `tag$` and an empty one for `$`
+fn dollar_quote_tag(chars: &std::iter::Peekable<std::str::Chars<'_>>) -> Option<String> {
+    let mut ahead = chars.clone();
+    let mut tag = String::new();
+    loop {
+        match ahead.next()? {
+            '$' => return Some(tag),
+            c if c.is_alphabetic() || c == '_' || (c.is_ascii_digit() && !tag.is_empty()) => {
+                tag.push(c)
             }
-        })
-        .collect();
-
-    let variants_db: Vec<String> = variants
-        .iter()
-        .map(|variant| {
-            val_from_attrs(&variant.attrs, "db_rename")
-                .unwrap_or_else(|| stylize_value(&variant.ident.to_string(), case_style))
-        })
-        .collect();
-    let variants_db_bytes: Vec<LitByteStr> = variants_db
-        .iter()
-        .map(|variant_str| LitByteStr::new(variant_str.as_bytes(), Span::call_site()))
-        .collect();
-
-    let common = generate_common(enum_ty, &variant_ids, &variants_db, &variants_db_bytes);
-    let (diesel_mapping_def, diesel_mapping_use) =
-        // Skip this part if we already have an existing mapping
-        if existing_mapping_path.is_some() {
-            (None, None)
-        } else {
-            let new_diesel_mapping_def = generate_new_diesel_mapping(new_diesel_mapping, pg_internal_type);
-            let common_impls_on_new_diesel_mapping =
-                generate_common_impls(&quote! { #new_diesel_mapping }, enum_ty);
-            (
-                Some(quote! {
-                    #new_diesel_mapping_def
-                    #common_impls_on_new_diesel_mapping
-                }),
-                Some(quote! {
-                    pub use self::#modname::#new_diesel_mapping;
-                }),
-            )
-        };
+            _ => return None,
+        }
+    }
+}
 
-    let pg_impl = if cfg!(feature = "postgres") {
-        match existing_mapping_path {
-            Some(path) => {
-                let common_impls_on_existing_diesel_mapping = generate_common_impls(path, enum_ty);
-                let postgres_impl = generate_postgres_impl(path, enum_ty, true);
-                Some(quote! {
-                    #common_impls_on_existing_diesel_mapping
-                    #postgres_impl
-                })
+/// The labels of `CREATE TYPE <type_name> AS ENUM (...)` in `sql`. The name is matched
+/// as written in the SQL, optionally with its schema, e.g. `status` or `app.status`.
+fn parse_create_enum_type(sql: &str, type_name: &str) -> std::result::Result<Vec<String>, String> {
+    use SqlToken::*;
+    let tokens = sql_tokens(sql)?;
+    let word =
+        |token: Option<&SqlToken>, expected: &str| matches!(token, Some(Word(w)) if w == expected);
+    let mut found = None;
+    for (start, _) in tokens.iter().enumerate() {
+        if !(word(tokens.get(start), "create") && word(tokens.get(start + 1), "type")) {
+            continue;
+        }
+        // the (possibly schema-qualified) type name
+        let mut index = start + 2;
+        let mut name = Vec::new();
+        while let Some(Word(part) | Ident(part)) = tokens.get(index) {
+            name.push(part.clone());
+            index += 1;
+            if tokens.get(index) != Some(&Punct('.')) {
+                break;
             }
-            None => Some(generate_postgres_impl(
-                &quote! { #new_diesel_mapping },
-                enum_ty,
-                false,
-            )),
+            index += 1;
         }
-    } else {
-        None
-    };
+        let name = name.join(".");
+        let unqualified = name.rsplit('.').next().unwrap_or_default();
+        if name != type_name && unqualified != type_name {
+            continue;
+        }
+        if !(word(tokens.get(index), "as") && word(tokens.get(index + 1), "enum")) {
+            return Err(format!("type `{}` is not an enum", type_name));
+        }
+        index += 2;
+        if tokens.get(index) != Some(&Punct('(')) {
+            return Err(format!(
+                "expected `(` after `CREATE TYPE {} AS ENUM`",
+                type_name
+            ));
+        }
+        index += 1;
+        let mut labels = Vec::new();
+        loop {
+            match (tokens.get(index), tokens.get(index + 1)) {
+                (Some(Punct(')')), _) if labels.is_empty() => break,
+                (Some(Str(label)), Some(Punct(','))) => labels.push(label.clone()),
+                (Some(Str(label)), Some(Punct(')'))) => {
+                    labels.push(label.clone());
+                    break;
+                }
+                (Some(EscapeStr(_) | DollarStr(_)), _) => {
+                    return Err(format!(
+                        "the labels of enum type `{}` must be plain `'...'` strings",
+                        type_name
+                    ))
+                }
+                _ => return Err(format!("invalid labels of enum type `{}`", type_name)),
+            }
+            index += 2;
+        }
+        if labels.is_empty() {
+            return Err(format!("enum type `{}` has no labels", type_name));
+        }
+        if found.replace(labels).is_some() {
+            return Err(format!("type `{}` is created more than once", type_name));
+        }
+    }
+    found.ok_or_else(|| format!("no `CREATE TYPE {} AS ENUM` statement", type_name))
+}
 
-    let mysql_impl = if cfg!(feature = "mysql") {
-        Some(generate_mysql_impl(new_diesel_mapping, enum_ty))
-    } else {
-        None
+fn derive_db_enum(input: &DeriveInput) -> Result<proc_macro2::TokenStream> {
+    let data_variants = match &input.data {
+        Data::Enum(DataEnum { variants, .. }) => variants,
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "derive(DbEnum) can only be applied to enums",
+            ))
+        }
+    };
+    // report the errors of the enum and of its variants together
+    let (options, variants) = match (
+        EnumOptions::from_attrs(&input.attrs, &input.ident),
+        VariantOptions::from_variants(data_variants),
+    ) {
+        (Ok(options), Ok(variants)) => (options, variants),
+        (Err(mut error), Err(variants_error)) => {
+            error.combine(variants_error);
+            return Err(error);
+        }
+        (Err(error), _) | (_, Err(error)) => return Err(error),
     };
 
-    let sqlite_impl = if cfg!(feature = "sqlite") {
-        Some(generate_sqlite_impl(new_diesel_mapping, enum_ty))
+    // we could allow a default value here but... I'm not very keen
+    // let existing_mapping_path = existing_mapping_path
+    //     .unwrap_or_else(|| format!("crate::schema::sql_types::{}", input.ident));
+
+    let case_style = options.value_style.unwrap_or(CaseStyle::Snake);
+    let mut seen: Vec<String> = Vec::with_capacity(variants.len());
+    let mut errors = Errors::default();
+    if options.strict_attrs {
+        errors.check(check_attribute_placement(input, data_variants));
+    }
+    for variant in variants.iter() {
+        errors.check(check_variant(
+            variant, &variants, &options, case_style, &mut seen,
+        ));
+    }
+    errors.finish()?;
+
+    let schema_dependencies = if cfg!(feature = "live-check")
+        && cfg!(feature = "postgres")
+        && options.storage.postgres() == Storage::Enum
+        && options.existing_type_path.is_none()
+    {
+        let pg_type = options.pg_type_name(&input.ident);
+        let snapshot = check_schema(&pg_type, &input.ident, &variants, &options)?;
+        Some(schema_dependencies(snapshot.as_deref()))
     } else {
         None
     };
 
-    let imports = quote! {
-        use super::*;
-        use diesel::{
-            backend::{self, Backend},
-            deserialize::{self, FromSql},
-            expression::AsExpression,
-            internal::derives::as_expression::Bound,
-            query_builder::{bind_collector::RawBytesBindCollector},
-            row::Row,
-            serialize::{self, IsNull, Output, ToSql},
-            sql_types::*,
-            Queryable,
-        };
-        use std::io::Write;
-    };
+    let mut generated = generate_derive_enum_impls(&options, &input.vis, &input.ident, &variants);
+    generated.extend(schema_dependencies);
+    Ok(generated)
+}
 
-    let quoted = quote! {
-        #diesel_mapping_use
-        #[allow(non_snake_case)]
-        mod #modname {
-            #imports
+/// Check the db values against the labels of the postgres type, either in the database
+/// at `DATABASE_URL` or, without it, in the snapshot file (if there is one). Returns the
+/// path of the snapshot, if the labels were read from it
+fn check_schema(
+    pg_type: &str,
+    enum_ty: &Ident,
+    variants: &[VariantOptions],
+    options: &EnumOptions,
+) -> Result<Option<String>> {
+    match schema_labels(pg_type, enum_ty)? {
+        Some((labels, snapshot)) => {
+            check_labels(&labels, pg_type, enum_ty, variants, options)?;
+            Ok(snapshot)
+        }
+        None => Ok(None),
+    }
+}
 
-            #common
-            #diesel_mapping_def
-            #pg_impl
-            #mysql_impl
-            #sqlite_impl
+/// Makes cargo expand the derive again when what `check_schema` read changes: the
+/// environment variables it looks at, and the snapshot it read the labels from
+fn schema_dependencies(snapshot: Option<&str>) -> proc_macro2::TokenStream {
+    let env_vars = [
+        "DATABASE_URL",
+        "DIESEL_DERIVE_ENUM_SNAPSHOT",
+        "DIESEL_DERIVE_ENUM_UPDATE_SNAPSHOT",
+    ];
+    let snapshot = snapshot.map(|path| {
+        quote! {
+            const _: &[u8] = ::std::include_bytes!(#path);
         }
+    });
+    quote! {
+        #(const _: ::std::option::Option<&str> = ::std::option_env!(#env_vars);)*
+        #snapshot
+    }
+}
+
+/// The labels of the postgres type, from the database at `DATABASE_URL` (updating the
+/// snapshot if asked to), or else from the snapshot, if there is one, along with its path
+fn schema_labels(pg_type: &str, enum_ty: &Ident) -> Result<Option<(Vec<String>, Option<String>)>> {
+    let snapshot_path = snapshot_path();
+    let snapshot_error = |e: String| Error::new(enum_ty.span(), e);
+    match std::env::var("DATABASE_URL") {
+        Ok(database_url) => {
+            let labels = live_labels(&database_url, pg_type, enum_ty)?;
+            if std::env::var_os("DIESEL_DERIVE_ENUM_UPDATE_SNAPSHOT").is_some() {
+                let path = snapshot_path.ok_or_else(|| {
+                    snapshot_error("no snapshot path, set DIESEL_DERIVE_ENUM_SNAPSHOT".into())
+                })?;
+                update_snapshot(&path, pg_type, &labels).map_err(snapshot_error)?;
+            }
+            Ok(Some((labels, None)))
+        }
+        Err(_) => {
+            let path = match snapshot_path {
+                Some(path) if path.exists() => path,
+                _ => return Ok(None),
+            };
+            let mut snapshot = read_snapshot(&path).map_err(snapshot_error)?;
+            let labels = snapshot.remove(pg_type).ok_or_else(|| {
+                snapshot_error(format!(
+                    "postgres type `{}` is not in the snapshot at {}, regenerate it with \
+                     DATABASE_URL and DIESEL_DERIVE_ENUM_UPDATE_SNAPSHOT=1",
+                    pg_type,
+                    path.display()
+                ))
+            })?;
+            Ok(Some((labels, Some(path.display().to_string()))))
+        }
+    }
+}
+
+/// Add the labels of `pg_type` to the snapshot. Other derives (of this crate, or of
+/// another one built in parallel) may be updating it at the same time, so the file is
+/// read and written holding a lock file next to it, and the new contents are renamed
+/// over the old ones, so that readers never see a partial file.
+fn update_snapshot(
+    path: &std::path::Path,
+    pg_type: &str,
+    labels: &[String],
+) -> std::result::Result<(), String> {
+    let sibling = |suffix: &str| {
+        let mut name = path.file_name().unwrap_or_default().to_os_string();
+        name.push(suffix);
+        path.with_file_name(name)
+    };
+    let _lock = SnapshotLock::acquire(sibling(".lock"), std::time::Duration::from_secs(30))?;
+    let mut snapshot = if path.exists() {
+        read_snapshot(path)?
+    } else {
+        Snapshot::new()
     };
+    snapshot.insert(pg_type.to_string(), labels.to_vec());
+    let temp_path = sibling(".tmp");
+    write_snapshot(&temp_path, &snapshot)?;
+    std::fs::rename(&temp_path, path)
+        .map_err(|e| format!("could not write snapshot {}: {}", path.display(), e))
+}
 
-    quoted.into()
+/// A lock file, created exclusively and removed when dropped
+struct SnapshotLock(std::path::PathBuf);
+
+impl SnapshotLock {
+    /// Wait for the lock at `path`. A lock file older than `stale_after` was left behind
+    /// by a build which was killed while holding it, as the lock is only held to rewrite
+    /// the snapshot, so it is taken over.
+    fn acquire(
+        path: std::path::PathBuf,
+        stale_after: std::time::Duration,
+    ) -> std::result::Result<Self, String> {
+        loop {
+            match std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(_) => return Ok(SnapshotLock(path)),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    let age = std::fs::metadata(&path)
+                        .and_then(|metadata| metadata.modified())
+                        .ok()
+                        .and_then(|modified| modified.elapsed().ok());
+                    let stale = matches!(age, Some(age) if age >= stale_after);
+                    if stale {
+                        match std::fs::remove_file(&path) {
+                            Ok(()) => continue,
+                            // another build took it over first
+                            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                            Err(e) => {
+                                return Err(format!(
+                                    "could not remove the stale lock {}: {}",
+                                    path.display(),
+                                    e
+                                ))
+                            }
+                        }
+                    }
+                    std::thread::sleep(std::time::Duration::from_millis(10));
+                }
+                Err(e) => return Err(format!("could not create {}: {}", path.display(), e)),
+            }
+        }
+    }
 }
 
-fn stylize_value(value: &str, style: CaseStyle) -> String {
-    match style {
-        CaseStyle::Camel => value.to_lower_camel_case(),
-        CaseStyle::Kebab => value.to_kebab_case(),
-        CaseStyle::Pascal => value.to_upper_camel_case(),
-        CaseStyle::Upper => value.to_uppercase(),
-        CaseStyle::ScreamingSnake => value.to_shouty_snake_case(),
-        CaseStyle::Snake => value.to_snake_case(),
-        CaseStyle::Verbatim => value.to_string(),
+impl Drop for SnapshotLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
     }
 }
 
-fn generate_common(
+/// The labels of the postgres type in the database at `database_url`, queried with
+/// `psql` (so this crate needs no client library)
+fn live_labels(database_url: &str, pg_type: &str, enum_ty: &Ident) -> Result<Vec<String>> {
+    let query = format!(
+        "SELECT e.enumlabel FROM pg_enum e WHERE e.enumtypid = to_regtype('{}') \
+         ORDER BY e.enumsortorder",
+        quote_pg_type_name(pg_type).replace('\'', "''")
+    );
+    let output = std::process::Command::new("psql")
+        .args([
+            database_url,
+            "-X",
+            "-A",
+            "-t",
+            "-0",
+            "-v",
+            "ON_ERROR_STOP=1",
+            "-c",
+        ])
+        .arg(&query)
+        .output()
+        .map_err(|e| {
+            Error::new(
+                enum_ty.span(),
+                format!("could not run `psql` to check against DATABASE_URL: {}", e),
+            )
+        })?;
+    if !output.status.success() {
+        return Err(Error::new(
+            enum_ty.span(),
+            format!(
+                "could not check against DATABASE_URL: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        ));
+    }
+    let labels: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|label| !label.is_empty())
+        .map(String::from)
+        .collect();
+    if labels.is_empty() {
+        return Err(Error::new(
+            enum_ty.span(),
+            format!(
+                "postgres type `{}` does not exist (or has no labels) in the database at DATABASE_URL",
+                pg_type
+            ),
+        ));
+    }
+    Ok(labels)
+}
+
+fn check_labels(
+    labels: &[String],
+    pg_type: &str,
     enum_ty: &Ident,
-    variants_rs: &[proc_macro2::TokenStream],
-    variants_db: &[String],
-    variants_db_bytes: &[LitByteStr],
-) -> proc_macro2::TokenStream {
-    quote! {
-        fn db_str_representation(e: &#enum_ty) -> &'static str {
-            match *e {
-                #(#variants_rs => #variants_db,)*
+    variants: &[VariantOptions],
+    options: &EnumOptions,
+) -> Result<()> {
+    let case_style = options.value_style.unwrap_or(CaseStyle::Snake);
+    let mut known_values = Vec::with_capacity(variants.len());
+    for variant in variants.iter().filter(|variant| !variant.null) {
+        let (value, span) = match &variant.rename {
+            Some(rename) => (rename.value(), rename.span()),
+            // values computed by a `DbValueStyleFn` are only known at runtime
+            None if options.value_style_fn.is_some() => continue,
+            None => (
+                stylize_value(&variant.ident.to_string(), case_style),
+                variant.ident.span(),
+            ),
+        };
+        if !labels.contains(&value) {
+            return Err(Error::new(
+                span,
+                format!(
+                    "db value `{}` is not a label of postgres type `{}` (labels: {})",
+                    value,
+                    pg_type,
+                    labels.join(", ")
+                ),
+            ));
+        }
+        known_values.push(value);
+    }
+    if options.value_style_fn.is_none() {
+        let unknown: Vec<&str> = labels
+            .iter()
+            .filter(|label| !known_values.contains(label))
+            .map(String::as_str)
+            .collect();
+        if !unknown.is_empty() {
+            return Err(Error::new(
+                enum_ty.span(),
+                format!(
+                    "postgres type `{}` has labels without a variant: {}",
+                    pg_type,
+                    unknown.join(", ")
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+type Snapshot = std::collections::BTreeMap<String, Vec<String>>;
+
+/// `DIESEL_DERIVE_ENUM_SNAPSHOT`, or `db_enums.json`, relative to the crate being compiled
+fn snapshot_path() -> Option<std::path::PathBuf> {
+    let path =
+        std::env::var_os("DIESEL_DERIVE_ENUM_SNAPSHOT").unwrap_or_else(|| "db_enums.json".into());
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR")?;
+    Some(std::path::Path::new(&manifest_dir).join(path))
+}
+
+/// The snapshot is a JSON object, mapping each postgres type name to its labels,
+/// e.g. `{"my_enum": ["foo", "bar"]}`
+fn read_snapshot(path: &std::path::Path) -> std::result::Result<Snapshot, String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| format!("could not read snapshot {}: {}", path.display(), e))?;
+    parse_snapshot(&text).ok_or_else(|| format!("invalid snapshot {}", path.display()))
+}
+
+/// Only the JSON which `write_snapshot` writes (up to whitespace and escapes) is accepted
+fn parse_snapshot(text: &str) -> Option<Snapshot> {
+    let mut chars = text.chars().peekable();
+    let skip_whitespace = |chars: &mut std::iter::Peekable<std::str::Chars>| {
+        while matches!(chars.peek(), Some(c) if c.is_whitespace()) {
+            chars.next();
+        }
+    };
+    let mut snapshot = Snapshot::new();
+    skip_whitespace(&mut chars);
+    if chars.next() != Some('{') {
+        return None;
+    }
+    loop {
+        skip_whitespace(&mut chars);
+        match chars.next() {
+            Some('}') if snapshot.is_empty() => break,
+            Some('"') => {}
+            _ => return None,
+        }
+        let pg_type = parse_json_string(&mut chars)?;
+        skip_whitespace(&mut chars);
+        if chars.next() != Some(':') {
+            return None;
+        }
+        skip_whitespace(&mut chars);
+        if chars.next() != Some('[') {
+            return None;
+        }
+        let mut labels = Vec::new();
+        loop {
+            skip_whitespace(&mut chars);
+            match chars.next() {
+                Some(']') if labels.is_empty() => break,
+                Some('"') => {}
+                _ => return None,
             }
+            labels.push(parse_json_string(&mut chars)?);
+            skip_whitespace(&mut chars);
+            match chars.next() {
+                Some(',') => continue,
+                Some(']') => break,
+                _ => return None,
+            }
+        }
+        if snapshot.insert(pg_type, labels).is_some() {
+            return None;
         }
+        skip_whitespace(&mut chars);
+        match chars.next() {
+            Some(',') => continue,
+            Some('}') => break,
+            _ => return None,
+        }
+    }
+    skip_whitespace(&mut chars);
+    chars.next().is_none().then_some(snapshot)
+}
 
-        fn from_db_binary_representation(bytes: &[u8]) -> deserialize::Result<#enum_ty> {
-            match bytes {
-                #(#variants_db_bytes => Ok(#variants_rs),)*
-                v => Err(format!("Unrecognized enum variant: '{}'",
-                    String::from_utf8_lossy(v)).into()),
+fn write_snapshot(path: &std::path::Path, snapshot: &Snapshot) -> std::result::Result<(), String> {
+    let mut out = String::from("{\n");
+    for (i, (pg_type, labels)) in snapshot.iter().enumerate() {
+        out.push_str("  ");
+        push_json_string(&mut out, pg_type);
+        out.push_str(": [");
+        for (j, label) in labels.iter().enumerate() {
+            if j > 0 {
+                out.push_str(", ");
             }
+            push_json_string(&mut out, label);
         }
+        out.push(']');
+        if i + 1 < snapshot.len() {
+            out.push(',');
+        }
+        out.push('\n');
     }
+    out.push_str("}\n");
+    std::fs::write(path, out)
+        .map_err(|e| format!("could not write snapshot {}: {}", path.display(), e))
 }
 
-fn generate_new_diesel_mapping(
-    new_diesel_mapping: &Ident,
-    pg_internal_type: &str,
+/// Like the generated `push_json_string`, for writing the snapshot
+fn push_json_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Like the generated `parse_json_string`, for reading the snapshot. Expects the
+/// opening quote to have been consumed already
+fn parse_json_string(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> Option<String> {
+    fn hex4(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> Option<u32> {
+        (0..4).try_fold(0, |code, _| Some(code * 16 + chars.next()?.to_digit(16)?))
+    }
+
+    let mut value = String::new();
+    loop {
+        match chars.next()? {
+            '"' => return Some(value),
+            '\\' => match chars.next()? {
+                '"' => value.push('"'),
+                '\\' => value.push('\\'),
+                '/' => value.push('/'),
+                'b' => value.push('\u{8}'),
+                'f' => value.push('\u{c}'),
+                'n' => value.push('\n'),
+                'r' => value.push('\r'),
+                't' => value.push('\t'),
+                'u' => {
+                    let mut code = hex4(chars)?;
+                    // characters outside the basic plane are escaped as a surrogate pair
+                    if (0xd800..0xdc00).contains(&code) {
+                        if chars.next()? != '\\' || chars.next()? != 'u' {
+                            return None;
+                        }
+                        let low = hex4(chars)?;
+                        if !(0xdc00..0xe000).contains(&low) {
+                            return None;
+                        }
+                        code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+                    }
+                    value.push(char::from_u32(code)?);
+                }
+                _ => return None,
+            },
+            // control characters must be escaped
+            c if (c as u32) < 0x20 => return None,
+            c => value.push(c),
+        }
+    }
+}
+
+/// Collects errors, so that all of them are reported at once
+#[derive(Default)]
+struct Errors(Option<Error>);
+
+impl Errors {
+    fn push(&mut self, error: Error) {
+        match &mut self.0 {
+            Some(errors) => errors.combine(error),
+            None => self.0 = Some(error),
+        }
+    }
+
+    /// The value of `result`, recording its error if any
+    fn check<T>(&mut self, result: Result<T>) -> Option<T> {
+        result.map_err(|error| self.push(error)).ok()
+    }
+
+    fn finish(self) -> Result<()> {
+        match self.0 {
+            Some(errors) => Err(errors),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The helper attributes of the derive which apply to the enum (keep in sync with the
+/// `proc_macro_derive` attributes)
+const ENUM_ATTRIBUTES: &[&str] = &[
+    "db_enum",
+    "PgType",
+    "PgTypeStyle",
+    "PgOid",
+    "PgArrayOid",
+    "DieselType",
+    "ExistingTypePath",
+    "ExistingTypeAlias",
+    "DbSchemaRoot",
+    "DbValueStyle",
+    "DbRenameAll",
+    "DbValueStyleFn",
+    "DbValueNormalize",
+    "MaxDbLen",
+    "DbCharWidth",
+    "DbSerializeWith",
+    "DbDeserializeWith",
+    "DbArrayStorage",
+    "DbArrayDelimiter",
+    "DbCustomBackend",
+    "DbEnumDsl",
+    "DbEnumOrd",
+    "DbEnumIndex",
+    "DbEnumStrEq",
+    "DbEnumAsText",
+    "DbEnumTrustedRead",
+    "DbEnumCockroach",
+    "DbEnumLocal",
+    "DbEnumNoReexport",
+    "DbEnumStrictAttrs",
+    "DbEnumClap",
+    "DbEnumRocket",
+    "DbEnumFake",
+    "DbEnumArbitrary",
+    "DbEnumPostgresTypes",
+    "DbEnumSqlx",
+    "DbEnumJson",
+    "DbEnumSkip",
+    "DbReadOnly",
+    "DbWriteOnly",
+    "DbEnumCodegen",
+    "DbStorageByBackend",
+    "DieselTypeByBackend",
+    "DbTextMapping",
+    "DbEnumStr",
+    "DbEnumI18n",
+];
+
+/// The helper attributes of the derive which apply to a variant
+const VARIANT_ATTRIBUTES: &[&str] = &[
+    "db_rename",
+    "db_renamed_from",
+    "db_value",
+    "db_null_variant",
+    "db_empty_fallback",
+    "db_label",
+];
+
+/// With `DbEnumStrictAttrs`, reject helper attributes in the wrong place, which are
+/// otherwise ignored (e.g. `ExistingTypePath` on a variant)
+fn check_attribute_placement(
+    input: &DeriveInput,
+    variants: &punctuated::Punctuated<Variant, Token![,]>,
+) -> Result<()> {
+    let mut errors = Errors::default();
+    let misplaced = |attr: &Attribute, names: &[&str], message: &str| -> Result<()> {
+        match attr.path().get_ident() {
+            Some(name) if names.iter().any(|known| name == known) => {
+                Err(Error::new_spanned(attr, format!("`{}` {}", name, message)))
+            }
+            _ => Ok(()),
+        }
+    };
+    for attr in &input.attrs {
+        errors.check(misplaced(
+            attr,
+            VARIANT_ATTRIBUTES,
+            "is a variant attribute, and has no effect on the enum",
+        ));
+    }
+    for variant in variants {
+        for attr in &variant.attrs {
+            errors.check(misplaced(
+                attr,
+                ENUM_ATTRIBUTES,
+                "is an enum attribute, and has no effect on a variant",
+            ));
+        }
+    }
+    errors.finish()
+}
+
+/// Check the attributes and the db value of a variant, adding the value to `seen`
+fn check_variant(
+    variant: &VariantOptions,
+    variants: &[VariantOptions],
+    options: &EnumOptions,
+    case_style: CaseStyle,
+    seen: &mut Vec<String>,
+) -> Result<()> {
+    if variant.null && cfg!(feature = "diesel-1") {
+        return Err(Error::new_spanned(
+            &variant.ident,
+            "`db_null_variant` is not supported with the 'diesel-1' feature",
+        ));
+    }
+    if let Some(attr) = &variant.explicit_int_value {
+        if variant.null {
+            return Err(Error::new_spanned(
+                attr,
+                "`db_value` cannot be used on the `db_null_variant`",
+            ));
+        }
+        if !options.storage.declares_integer() {
+            return Err(Error::new_spanned(
+                attr,
+                "`db_value` requires integer storage, e.g. `#[DbStorageByBackend(sqlite = \"integer\")]`",
+            ));
+        }
+    }
+    if let (Some((min, max)), false) = (options.storage.mysql().int_range(), variant.null) {
+        let value = i64::from(variant.int_value);
+        if value < min || value > max {
+            let message = format!(
+                "db_value `{}` does not fit the mysql storage, which holds {} to {}",
+                value, min, max
+            );
+            return Err(match &variant.explicit_int_value {
+                Some(attr) => Error::new_spanned(attr, message),
+                None => Error::new_spanned(&variant.ident, message),
+            });
+        }
+    }
+    if let Some(renamed_from) = &variant.renamed_from {
+        if variant.null {
+            return Err(Error::new(
+                renamed_from.span(),
+                "`db_renamed_from` cannot be used on the `db_null_variant`",
+            ));
+        }
+        if options.value_style_fn.is_some() && variant.rename.is_none() {
+            return Err(Error::new(
+                renamed_from.span(),
+                "`db_renamed_from` with `DbValueStyleFn` requires a `db_rename` for the new value",
+            ));
+        }
+    }
+    if let Some(raw_rename) = &variant.raw_rename {
+        if cfg!(feature = "postgres") || cfg!(feature = "sqlite") {
+            return Err(Error::new(
+                raw_rename.span(),
+                "db values which aren't valid UTF-8 are only supported with mysql",
+            ));
+        }
+        if options.value_style_fn.is_some()
+            || options.value_normalize.is_some()
+            || options.char_width.is_some()
+            || options.serialize_with.is_some()
+            || options.deserialize_with.is_some()
+            || options.custom_backend.is_some()
+            || options.str_type.is_some()
+            || options.sqlx.is_some()
+        {
+            return Err(Error::new(
+                raw_rename.span(),
+                "db values which aren't valid UTF-8 cannot be used with `DbValueStyleFn`, \
+                 `DbValueNormalize`, `DbCharWidth`, `DbSerializeWith`, `DbDeserializeWith`, \
+                 `DbCustomBackend`, `DbEnumStr` or `DbEnumSqlx`",
+            ));
+        }
+    }
+    let (value, span) = match &variant.rename {
+        Some(rename) => (rename.value(), rename.span()),
+        // values computed by a `DbValueStyleFn` are only known at runtime
+        None if options.value_style_fn.is_some() => return Ok(()),
+        None => (
+            stylize_value(&variant.ident.to_string(), case_style),
+            variant.ident.span(),
+        ),
+    };
+    if seen.contains(&value) {
+        return Err(Error::new(span, format!("duplicate db value `{}`", value)));
+    }
+    // mysql compares ENUM members with the column's collation, which ignores case by default
+    if cfg!(feature = "mysql") && options.storage.mysql() == Storage::Enum {
+        let lowercase = value.to_lowercase();
+        if let Some(other) = seen.iter().find(|other| other.to_lowercase() == lowercase) {
+            return Err(Error::new(
+                span,
+                format!(
+                    "db values `{}` and `{}` only differ in case, which mysql rejects as duplicate enum values",
+                    other, value
+                ),
+            ));
+        }
+    }
+    if value.is_empty() {
+        if let Some(fallback) = variants.iter().find(|variant| variant.empty_fallback) {
+            return Err(Error::new(
+                span,
+                format!(
+                    "the empty db value is already read as `{}`, marked `db_empty_fallback`",
+                    fallback.ident
+                ),
+            ));
+        }
+        if let Some(ArrayStorage::Delimited(_)) = &options.array_storage {
+            return Err(Error::new(
+                span,
+                "an empty db value cannot be told apart from an empty delimited array",
+            ));
+        }
+    }
+    if let Some(ArrayStorage::Delimited(delimiter)) = &options.array_storage {
+        if value.contains(delimiter.as_str()) {
+            return Err(Error::new(
+                span,
+                format!(
+                    "db value `{}` contains the array delimiter `{}`",
+                    value, delimiter
+                ),
+            ));
+        }
+    }
+    check_backend_limits(&value, span, &options.storage)?;
+    if let Some(max_len) = options.max_db_len {
+        if value.len() > max_len {
+            return Err(Error::new(
+                span,
+                format!(
+                    "db value `{}` of `{}` is {} bytes long, but `MaxDbLen` is {}",
+                    value,
+                    variant.ident,
+                    value.len(),
+                    max_len
+                ),
+            ));
+        }
+    }
+    if let Some(width) = options.char_width {
+        let len = value.chars().count();
+        if len > width {
+            return Err(Error::new(
+                span,
+                format!(
+                    "db value `{}` of `{}` is {} characters long, but `DbCharWidth` is {}",
+                    value, variant.ident, len, width
+                ),
+            ));
+        }
+    }
+    if let Some(renamed_from) = &variant.renamed_from {
+        if renamed_from.value() == value {
+            return Err(Error::new(
+                renamed_from.span(),
+                format!("`db_renamed_from` is the same as the db value `{}`", value),
+            ));
+        }
+    }
+    seen.push(value);
+    Ok(())
+}
+
+/// Check that a db value can be stored by each of the enabled backends.
+/// Any valid UTF-8 is accepted, but some backends limit the length of enum labels.
+fn check_backend_limits(value: &str, span: Span, storage: &BackendStorage) -> Result<()> {
+    // postgres: labels are limited to NAMEDATALEN - 1 bytes, and text may not contain NUL
+    if cfg!(feature = "postgres") && storage.postgres() == Storage::Enum && value.len() > 63 {
+        return Err(Error::new(
+            span,
+            format!(
+                "db value `{}` is {} bytes long, but postgres enum labels are limited to 63 bytes",
+                value,
+                value.len()
+            ),
+        ));
+    }
+    if cfg!(feature = "postgres") && !storage.postgres().is_integer() && value.contains('\0') {
+        return Err(Error::new(
+            span,
+            "db value contains a NUL character, which postgres does not support",
+        ));
+    }
+    // sqlite: the values are spliced into the triggers of `install_validation`
+    if cfg!(feature = "sqlite") && !storage.sqlite().is_integer() && value.contains('\0') {
+        return Err(Error::new(
+            span,
+            "db value contains a NUL character, which cannot be used in the sqlite `validation_sql`",
+        ));
+    }
+    // mysql: ENUM members are limited to 255 characters, and trailing spaces are stripped
+    if cfg!(feature = "mysql") && storage.mysql() == Storage::Enum {
+        if value.contains('\0') {
+            return Err(Error::new(
+                span,
+                "db value contains a NUL character, which cannot be used in `MYSQL_COLUMN_TYPE`",
+            ));
+        }
+        // in non-strict mode, mysql stores `''` in place of invalid values
+        if value.is_empty() {
+            return Err(Error::new(
+                span,
+                "mysql stores an empty string for invalid enum values, so it cannot be a db value; \
+                 use `#[db_empty_fallback]` to read it as a variant instead",
+            ));
+        }
+        let chars = value.chars().count();
+        if chars > 255 {
+            return Err(Error::new(
+                span,
+                format!(
+                    "db value `{}` is {} characters long, but mysql enum values are limited to 255 characters",
+                    value, chars
+                ),
+            ));
+        }
+        if value.ends_with(' ') {
+            return Err(Error::new(
+                span,
+                format!(
+                    "db value `{}` ends with a space, which mysql strips from enum values",
+                    value
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Words postgres doesn't accept as a bare type name (reserved and column name keywords,
+/// and those only allowed as a function or type name, to be safe)
+#[rustfmt::skip]
+const PG_KEYWORDS: &[&str] = &[
+    "all", "analyse", "analyze", "and", "any", "array", "as", "asc", "asymmetric",
+    "authorization", "between", "bigint", "binary", "bit", "boolean", "both", "case",
+    "cast", "char", "character", "check", "coalesce", "collate", "collation", "column",
+    "concurrently", "constraint", "create", "cross", "current_catalog", "current_date",
+    "current_role", "current_schema", "current_time", "current_timestamp",
+    "current_user", "dec", "decimal", "default", "deferrable", "desc", "distinct", "do",
+    "else", "end", "except", "exists", "extract", "false", "fetch", "float", "for",
+    "foreign", "freeze", "from", "full", "grant", "greatest", "group", "grouping",
+    "having", "ilike", "in", "initially", "inner", "inout", "int", "integer",
+    "intersect", "interval", "into", "is", "isnull", "join", "json", "lateral",
+    "leading", "least", "left", "like", "limit", "localtime", "localtimestamp",
+    "national", "natural", "nchar", "none", "normalize", "not", "notnull", "null",
+    "nullif", "numeric", "offset", "on", "only", "or", "order", "out", "outer",
+    "overlaps", "overlay", "placing", "position", "precision", "primary", "real",
+    "references", "returning", "right", "row", "select", "session_user", "setof",
+    "similar", "smallint", "some", "substring", "symmetric", "system_user", "table",
+    "tablesample", "then", "time", "timestamp", "to", "trailing", "treat", "trim",
+    "true", "union", "unique", "user", "using", "values", "varchar", "variadic",
+    "verbose", "when", "where", "window", "with",
+];
+
+/// Quote a postgres type name for use in generated SQL, unless it is a plain lowercase
+/// identifier. A quoted name keeps its case, like the name diesel looks up.
+fn quote_pg_ident(name: &str) -> String {
+    let plain = name.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        && !PG_KEYWORDS.contains(&name);
+    if plain {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+}
+
+/// The schema, if any, and the name of a postgres type such as `app.status`
+fn split_pg_type_name(name: &str) -> (Option<&str>, &str) {
+    match name.split_once('.') {
+        Some((schema, name)) => (Some(schema), name),
+        None => (None, name),
+    }
+}
+
+/// A postgres type name as written in SQL: with a schema, as in `app.status`, each part is
+/// quoted on its own
+fn quote_pg_type_name(name: &str) -> String {
+    match split_pg_type_name(name) {
+        (Some(schema), name) => format!("{}.{}", quote_pg_ident(schema), quote_pg_ident(name)),
+        (None, name) => quote_pg_ident(name),
+    }
+}
+
+/// Project-wide defaults from `db-enum.toml`, which the attributes of each enum override
+#[derive(Default)]
+struct ConfigDefaults {
+    path: Option<String>,
+    value_style: Option<CaseStyle>,
+    pg_type_style: Option<CaseStyle>,
+    schema_root: Option<Path>,
+    storage: BackendStorage,
+}
+
+/// `DIESEL_DERIVE_ENUM_CONFIG`, or `db-enum.toml`, relative to the crate being compiled
+fn config_path() -> Option<std::path::PathBuf> {
+    let path =
+        std::env::var_os("DIESEL_DERIVE_ENUM_CONFIG").unwrap_or_else(|| "db-enum.toml".into());
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR")?;
+    Some(std::path::Path::new(&manifest_dir).join(path))
+}
+
+impl ConfigDefaults {
+    /// Read the config file, if there is one
+    fn load() -> Result<Self> {
+        let path = match config_path() {
+            Some(path) if path.exists() => path,
+            _ => return Ok(Self::default()),
+        };
+        let text = std::fs::read_to_string(&path).map_err(|e| {
+            Error::new(
+                Span::call_site(),
+                format!("could not read config {}: {}", path.display(), e),
+            )
+        })?;
+        Self::parse(&text, &path.display().to_string())
+    }
+
+    /// Only the subset of TOML needed for the defaults is understood: comments,
+    /// `key = "value"` (or `'value'`) lines and the `[storage_by_backend]` table, e.g.
+    ///
+    /// ```toml
+    /// value_style = "camelCase"
+    /// pg_type_style = "PascalCase"
+    /// schema_root = "crate::schema::sql_types"
+    ///
+    /// [storage_by_backend]
+    /// sqlite = "integer"
+    /// ```
+    ///
+    /// Anything else is an error rather than being misread, even if it is valid TOML
+    /// (e.g. quoted or dotted keys, inline tables, or escapes, which no value needs).
+    fn parse(text: &str, path: &str) -> Result<Self> {
+        let mut config = ConfigDefaults {
+            path: Some(path.to_string()),
+            ..Default::default()
+        };
+        let mut in_storage_table = false;
+        for (index, line) in text.lines().enumerate() {
+            let error = |message: String| {
+                Error::new(
+                    Span::call_site(),
+                    format!("{}:{}: {}", path, index + 1, message),
+                )
+            };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with('[') {
+                let table = line.split('#').next().unwrap_or_default().trim_end();
+                if table != "[storage_by_backend]" {
+                    return Err(error(format!(
+                        "unknown table `{}`, expected `[storage_by_backend]`",
+                        table
+                    )));
+                }
+                if in_storage_table {
+                    return Err(error("duplicate table `[storage_by_backend]`".to_string()));
+                }
+                in_storage_table = true;
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .and_then(|(key, rest)| {
+                    let key = key.trim();
+                    let bare_key = !key.is_empty()
+                        && key
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+                    let rest = rest.trim_start();
+                    let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+                    let (value, rest) = rest[1..].split_once(quote)?;
+                    let rest = rest.trim_start();
+                    (bare_key
+                        && !value.contains('\\')
+                        && (rest.is_empty() || rest.starts_with('#')))
+                    .then_some((key, value))
+                })
+                .ok_or_else(|| error("expected `key = \"value\"`".to_string()))?;
+            let duplicate = || error(format!("duplicate key `{}`", key));
+            if in_storage_table {
+                let slot = config.storage.slot(key).ok_or_else(|| {
+                    error(format!(
+                        "unknown backend `{}`, expected one of `postgres`, `mysql` or `sqlite`",
+                        key
+                    ))
+                })?;
+                let lit_str = LitStr::new(value, Span::call_site());
+                let storage = BackendStorage::parse_storage(key, &lit_str)
+                    .map_err(|e| error(e.to_string()))?;
+                if slot.is_some() {
+                    return Err(duplicate());
+                }
+                *slot = Some((storage, Span::call_site()));
+            } else if key == "value_style" || key == "pg_type_style" {
+                let style = CaseStyle::from_name(value)
+                    .ok_or_else(|| error(format!("unsupported casing: `{}`", value)))?;
+                let slot = if key == "value_style" {
+                    &mut config.value_style
+                } else {
+                    &mut config.pg_type_style
+                };
+                if slot.replace(style).is_some() {
+                    return Err(duplicate());
+                }
+            } else if key == "schema_root" {
+                let root = syn::parse_str::<Path>(value)
+                    .map_err(|_| error(format!("`{}` is not a module path", value)))?;
+                if config.schema_root.replace(root).is_some() {
+                    return Err(duplicate());
+                }
+            } else {
+                return Err(error(format!(
+                    "unknown key `{}`, expected `value_style`, `pg_type_style`, `schema_root` \
+                     or a `[storage_by_backend]` table",
+                    key
+                )));
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// Options set on the enum, either through the individual attributes
+/// (`#[PgType = "..."]`) or all together (`#[db_enum(pg_type = "...")]`)
+#[derive(Default)]
+struct EnumOptions {
+    existing_type_path: Option<Path>,
+    existing_type_alias: Option<Ident>,
+    pg_type: Option<LitStr>,
+    // the oids of the postgres type and of its array type
+    pg_oids: Option<(u32, u32)>,
+    diesel_type: Option<Ident>,
+    value_style: Option<CaseStyle>,
+    value_style_fn: Option<Path>,
+    pg_type_style: Option<CaseStyle>,
+    value_normalize: Option<Path>,
+    max_db_len: Option<usize>,
+    char_width: Option<usize>,
+    serialize_with: Option<Path>,
+    deserialize_with: Option<Path>,
+    array_storage: Option<ArrayStorage>,
+    dsl: bool,
+    ord: bool,
+    index: bool,
+    str_eq: bool,
+    as_text: bool,
+    clap: Option<Span>,
+    rocket: Option<Span>,
+    fake: Option<Span>,
+    arbitrary: Option<Span>,
+    postgres_types: Option<Span>,
+    sqlx: Option<Span>,
+    json: Option<Span>,
+    trusted_read: Option<Span>,
+    cockroach: Option<Span>,
+    local: Option<Span>,
+    no_reexport: bool,
+    strict_attrs: bool,
+    generate_tests: bool,
+    custom_backend: Option<Path>,
+    skip: SkipImpls,
+    read_only: Option<Span>,
+    write_only: Option<Span>,
+    codegen: Codegen,
+    storage: BackendStorage,
+    diesel_type_by_backend: BackendTypeNames,
+    text_mapping: Option<Ident>,
+    str_type: Option<Ident>,
+    i18n_prefix: Option<LitStr>,
+    doc: Option<String>,
+    config_path: Option<String>,
+}
+
+impl EnumOptions {
+    fn from_attrs(attrs: &[Attribute], enum_ty: &Ident) -> Result<Self> {
+        let mut options = EnumOptions::default();
+        let mut schema_root: Option<Path> = None;
+        // named after the `DieselType` unless given a name, which is only known at the end
+        let mut existing_type_alias: Option<(Span, Option<Ident>)> = None;
+        let mut array_storage: Option<LitStr> = None;
+        let mut array_delimiter: Option<LitStr> = None;
+        let mut pg_oid: Option<LitInt> = None;
+        let mut char_width: Option<LitInt> = None;
+        let mut pg_array_oid: Option<LitInt> = None;
+        let mut errors = Errors::default();
+        for attr in attrs {
+            // keep going after an invalid attribute, to report the others as well
+            let mut parse_attr = || -> Result<()> {
+                let path = attr.path();
+                if path.is_ident("db_enum") {
+                    attr.parse_nested_meta(|meta| {
+                        let key = &meta.path;
+                        if key.is_ident("existing_type_path") {
+                            let value = parse_nested_value(&meta, "type path")?;
+                            set_once(&mut options.existing_type_path, value, key)
+                        } else if key.is_ident("existing_type_alias") {
+                            let name = if meta.input.is_empty() || meta.input.peek(Token![,]) {
+                                None
+                            } else {
+                                Some(parse_nested_value(&meta, "identifier")?)
+                            };
+                            let value = (spanned::Spanned::span(key), name);
+                            set_once(&mut existing_type_alias, value, key)
+                        } else if key.is_ident("schema_root") {
+                            let value = parse_nested_value(&meta, "module path")?;
+                            set_once(&mut schema_root, value, key)
+                        } else if key.is_ident("pg_type") {
+                            let value = meta.value()?.parse()?;
+                            set_once(&mut options.pg_type, value, key)
+                        } else if key.is_ident("pg_oid") {
+                            let value = meta.value()?.parse()?;
+                            set_once(&mut pg_oid, value, key)
+                        } else if key.is_ident("pg_array_oid") {
+                            let value = meta.value()?.parse()?;
+                            set_once(&mut pg_array_oid, value, key)
+                        } else if key.is_ident("diesel_type") {
+                            let value = parse_nested_value(&meta, "identifier")?;
+                            set_once(&mut options.diesel_type, value, key)
+                        } else if key.is_ident("value_style") || key.is_ident("rename_all") {
+                            let value = CaseStyle::from_nested_meta(&meta)?;
+                            set_once(&mut options.value_style, value, key)
+                        } else if key.is_ident("pg_type_style") {
+                            let value = CaseStyle::from_nested_meta(&meta)?;
+                            set_once(&mut options.pg_type_style, value, key)
+                        } else if key.is_ident("value_style_fn") {
+                            let value = parse_nested_value(&meta, "function path")?;
+                            set_once(&mut options.value_style_fn, value, key)
+                        } else if key.is_ident("value_normalize") {
+                            let value = parse_nested_value(&meta, "function path")?;
+                            set_once(&mut options.value_normalize, value, key)
+                        } else if key.is_ident("max_db_len") {
+                            let value = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+                            set_once(&mut options.max_db_len, value, key)
+                        } else if key.is_ident("char_width") {
+                            let value = meta.value()?.parse()?;
+                            set_once(&mut char_width, value, key)
+                        } else if key.is_ident("serialize_with") {
+                            let value = parse_nested_value(&meta, "function path")?;
+                            set_once(&mut options.serialize_with, value, key)
+                        } else if key.is_ident("deserialize_with") {
+                            let value = parse_nested_value(&meta, "function path")?;
+                            set_once(&mut options.deserialize_with, value, key)
+                        } else if key.is_ident("array_storage") {
+                            let value = meta.value()?.parse()?;
+                            set_once(&mut array_storage, value, key)
+                        } else if key.is_ident("array_delimiter") {
+                            let value = meta.value()?.parse()?;
+                            set_once(&mut array_delimiter, value, key)
+                        } else if key.is_ident("custom_backend") {
+                            let value = parse_nested_value(&meta, "type path")?;
+                            set_once(&mut options.custom_backend, value, key)
+                        } else if key.is_ident("dsl") {
+                            set_flag(&mut options.dsl, key)
+                        } else if key.is_ident("ord") {
+                            set_flag(&mut options.ord, key)
+                        } else if key.is_ident("index") {
+                            set_flag(&mut options.index, key)
+                        } else if key.is_ident("str_eq") {
+                            set_flag(&mut options.str_eq, key)
+                        } else if key.is_ident("as_text") {
+                            set_flag(&mut options.as_text, key)
+                        } else if key.is_ident("clap") {
+                            set_once(&mut options.clap, spanned::Spanned::span(key), key)
+                        } else if key.is_ident("rocket") {
+                            set_once(&mut options.rocket, spanned::Spanned::span(key), key)
+                        } else if key.is_ident("fake") {
+                            set_once(&mut options.fake, spanned::Spanned::span(key), key)
+                        } else if key.is_ident("arbitrary") {
+                            set_once(&mut options.arbitrary, spanned::Spanned::span(key), key)
+                        } else if key.is_ident("postgres_types") {
+                            set_once(
+                                &mut options.postgres_types,
+                                spanned::Spanned::span(key),
+                                key,
+                            )
+                        } else if key.is_ident("sqlx") {
+                            set_once(&mut options.sqlx, spanned::Spanned::span(key), key)
+                        } else if key.is_ident("json") {
+                            set_once(&mut options.json, spanned::Spanned::span(key), key)
+                        } else if key.is_ident("trusted_read") {
+                            set_once(&mut options.trusted_read, spanned::Spanned::span(key), key)
+                        } else if key.is_ident("cockroach") {
+                            set_once(&mut options.cockroach, spanned::Spanned::span(key), key)
+                        } else if key.is_ident("no_reexport") {
+                            set_flag(&mut options.no_reexport, key)
+                        } else if key.is_ident("strict_attrs") {
+                            set_flag(&mut options.strict_attrs, key)
+                        } else if key.is_ident("local") {
+                            set_once(&mut options.local, spanned::Spanned::span(key), key)
+                        } else if key.is_ident("generate_tests") {
+                            set_flag(&mut options.generate_tests, key)
+                        } else if key.is_ident("skip") {
+                            meta.parse_nested_meta(|meta| options.skip.set(&meta))
+                        } else if key.is_ident("read_only") {
+                            set_once(&mut options.read_only, spanned::Spanned::span(key), key)
+                        } else if key.is_ident("write_only") {
+                            set_once(&mut options.write_only, spanned::Spanned::span(key), key)
+                        } else if key.is_ident("codegen") {
+                            meta.parse_nested_meta(|meta| options.codegen.set(&meta))
+                        } else if key.is_ident("storage_by_backend") {
+                            meta.parse_nested_meta(|meta| options.storage.set(&meta))
+                        } else if key.is_ident("text_mapping") {
+                            let value = if meta.input.is_empty() || meta.input.peek(Token![,]) {
+                                default_text_mapping(enum_ty)
+                            } else {
+                                parse_nested_value(&meta, "identifier")?
+                            };
+                            set_once(&mut options.text_mapping, value, key)
+                        } else if key.is_ident("str") {
+                            let value = if meta.input.is_empty() || meta.input.peek(Token![,]) {
+                                default_str_type(enum_ty)
+                            } else {
+                                parse_nested_value(&meta, "identifier")?
+                            };
+                            set_once(&mut options.str_type, value, key)
+                        } else if key.is_ident("i18n") {
+                            let value = if meta.input.is_empty() || meta.input.peek(Token![,]) {
+                                default_i18n_prefix(enum_ty, spanned::Spanned::span(key))
+                            } else {
+                                meta.value()?.parse()?
+                            };
+                            set_once(&mut options.i18n_prefix, value, key)
+                        } else if key.is_ident("diesel_type_by_backend") {
+                            meta.parse_nested_meta(|meta| options.diesel_type_by_backend.set(&meta))
+                        } else {
+                            Err(meta.error("unsupported db_enum attribute"))
+                        }
+                    })?;
+                } else if path.is_ident("ExistingTypePath") {
+                    let value = parse_attr_value(attr, "type path")?;
+                    set_once(&mut options.existing_type_path, value, attr)?;
+                } else if path.is_ident("ExistingTypeAlias") {
+                    let name = match &attr.meta {
+                        Meta::Path(_) => None,
+                        _ => Some(parse_attr_value(attr, "identifier")?),
+                    };
+                    let value = (spanned::Spanned::span(attr), name);
+                    set_once(&mut existing_type_alias, value, attr)?;
+                } else if path.is_ident("DbSchemaRoot") {
+                    let value = parse_attr_value(attr, "module path")?;
+                    set_once(&mut schema_root, value, attr)?;
+                } else if path.is_ident("PgType") {
+                    set_once(&mut options.pg_type, lit_str_from_attr(attr)?, attr)?;
+                } else if path.is_ident("PgOid") {
+                    set_once(&mut pg_oid, lit_int_from_attr(attr)?, attr)?;
+                } else if path.is_ident("PgArrayOid") {
+                    set_once(&mut pg_array_oid, lit_int_from_attr(attr)?, attr)?;
+                } else if path.is_ident("DieselType") {
+                    let value = parse_attr_value(attr, "identifier")?;
+                    set_once(&mut options.diesel_type, value, attr)?;
+                } else if path.is_ident("DbValueStyle") || path.is_ident("DbRenameAll") {
+                    let value = CaseStyle::from_meta(&attr.meta)?;
+                    set_once(&mut options.value_style, value, attr)?;
+                } else if path.is_ident("PgTypeStyle") {
+                    let value = CaseStyle::from_meta(&attr.meta)?;
+                    set_once(&mut options.pg_type_style, value, attr)?;
+                } else if path.is_ident("DbValueStyleFn") {
+                    let value = parse_attr_value(attr, "function path")?;
+                    set_once(&mut options.value_style_fn, value, attr)?;
+                } else if path.is_ident("DbValueNormalize") {
+                    let value = parse_attr_value(attr, "function path")?;
+                    set_once(&mut options.value_normalize, value, attr)?;
+                } else if path.is_ident("MaxDbLen") {
+                    let value = lit_int_from_attr(attr)?.base10_parse()?;
+                    set_once(&mut options.max_db_len, value, attr)?;
+                } else if path.is_ident("DbCharWidth") {
+                    set_once(&mut char_width, lit_int_from_attr(attr)?, attr)?;
+                } else if path.is_ident("DbSerializeWith") {
+                    let value = parse_attr_value(attr, "function path")?;
+                    set_once(&mut options.serialize_with, value, attr)?;
+                } else if path.is_ident("DbDeserializeWith") {
+                    let value = parse_attr_value(attr, "function path")?;
+                    set_once(&mut options.deserialize_with, value, attr)?;
+                } else if path.is_ident("DbArrayStorage") {
+                    set_once(&mut array_storage, lit_str_from_attr(attr)?, attr)?;
+                } else if path.is_ident("DbArrayDelimiter") {
+                    set_once(&mut array_delimiter, lit_str_from_attr(attr)?, attr)?;
+                } else if path.is_ident("DbCustomBackend") {
+                    let value = parse_attr_value(attr, "type path")?;
+                    set_once(&mut options.custom_backend, value, attr)?;
+                } else if path.is_ident("DbEnumDsl") {
+                    attr.meta.require_path_only()?;
+                    set_flag(&mut options.dsl, attr)?;
+                } else if path.is_ident("DbEnumOrd") {
+                    attr.meta.require_path_only()?;
+                    set_flag(&mut options.ord, attr)?;
+                } else if path.is_ident("DbEnumIndex") {
+                    attr.meta.require_path_only()?;
+                    set_flag(&mut options.index, attr)?;
+                } else if path.is_ident("DbEnumStrEq") {
+                    attr.meta.require_path_only()?;
+                    set_flag(&mut options.str_eq, attr)?;
+                } else if path.is_ident("DbEnumAsText") {
+                    attr.meta.require_path_only()?;
+                    set_flag(&mut options.as_text, attr)?;
+                } else if path.is_ident("DbEnumClap") {
+                    attr.meta.require_path_only()?;
+                    set_once(&mut options.clap, spanned::Spanned::span(attr), attr)?;
+                } else if path.is_ident("DbEnumRocket") {
+                    attr.meta.require_path_only()?;
+                    set_once(&mut options.rocket, spanned::Spanned::span(attr), attr)?;
+                } else if path.is_ident("DbEnumFake") {
+                    attr.meta.require_path_only()?;
+                    set_once(&mut options.fake, spanned::Spanned::span(attr), attr)?;
+                } else if path.is_ident("DbEnumArbitrary") {
+                    attr.meta.require_path_only()?;
+                    set_once(&mut options.arbitrary, spanned::Spanned::span(attr), attr)?;
+                } else if path.is_ident("DbEnumPostgresTypes") {
+                    attr.meta.require_path_only()?;
+                    set_once(
+                        &mut options.postgres_types,
+                        spanned::Spanned::span(attr),
+                        attr,
+                    )?;
+                } else if path.is_ident("DbEnumSqlx") {
+                    attr.meta.require_path_only()?;
+                    set_once(&mut options.sqlx, spanned::Spanned::span(attr), attr)?;
+                } else if path.is_ident("DbEnumJson") {
+                    attr.meta.require_path_only()?;
+                    set_once(&mut options.json, spanned::Spanned::span(attr), attr)?;
+                } else if path.is_ident("DbEnumTrustedRead") {
+                    attr.meta.require_path_only()?;
+                    set_once(
+                        &mut options.trusted_read,
+                        spanned::Spanned::span(attr),
+                        attr,
+                    )?;
+                } else if path.is_ident("DbEnumCockroach") {
+                    attr.meta.require_path_only()?;
+                    set_once(&mut options.cockroach, spanned::Spanned::span(attr), attr)?;
+                } else if path.is_ident("DbEnumNoReexport") {
+                    attr.meta.require_path_only()?;
+                    set_flag(&mut options.no_reexport, attr)?;
+                } else if path.is_ident("DbEnumStrictAttrs") {
+                    attr.meta.require_path_only()?;
+                    set_flag(&mut options.strict_attrs, attr)?;
+                } else if path.is_ident("DbEnumLocal") {
+                    attr.meta.require_path_only()?;
+                    set_once(&mut options.local, spanned::Spanned::span(attr), attr)?;
+                } else if path.is_ident("DbEnumSkip") {
+                    attr.parse_nested_meta(|meta| options.skip.set(&meta))?;
+                } else if path.is_ident("DbReadOnly") {
+                    attr.meta.require_path_only()?;
+                    set_once(&mut options.read_only, spanned::Spanned::span(attr), attr)?;
+                } else if path.is_ident("DbWriteOnly") {
+                    attr.meta.require_path_only()?;
+                    set_once(&mut options.write_only, spanned::Spanned::span(attr), attr)?;
+                } else if path.is_ident("DbEnumCodegen") {
+                    attr.parse_nested_meta(|meta| options.codegen.set(&meta))?;
+                } else if path.is_ident("DbStorageByBackend") {
+                    attr.parse_nested_meta(|meta| options.storage.set(&meta))?;
+                } else if path.is_ident("DbTextMapping") {
+                    let value = match &attr.meta {
+                        Meta::Path(_) => default_text_mapping(enum_ty),
+                        _ => parse_attr_value(attr, "identifier")?,
+                    };
+                    set_once(&mut options.text_mapping, value, attr)?;
+                } else if path.is_ident("DbEnumStr") {
+                    let value = match &attr.meta {
+                        Meta::Path(_) => default_str_type(enum_ty),
+                        _ => parse_attr_value(attr, "identifier")?,
+                    };
+                    set_once(&mut options.str_type, value, attr)?;
+                } else if path.is_ident("DbEnumI18n") {
+                    let value = match &attr.meta {
+                        Meta::Path(_) => default_i18n_prefix(enum_ty, spanned::Spanned::span(attr)),
+                        _ => lit_str_from_attr(attr)?,
+                    };
+                    set_once(&mut options.i18n_prefix, value, attr)?;
+                } else if path.is_ident("DieselTypeByBackend") {
+                    attr.parse_nested_meta(|meta| options.diesel_type_by_backend.set(&meta))?;
+                }
+                Ok(())
+            };
+            errors.check(parse_attr());
+        }
+        errors.finish()?;
+        options.doc = doc_comment(attrs);
+
+        // the project-wide defaults fill in whatever the enum doesn't set itself
+        let config = ConfigDefaults::load()?;
+        options.config_path = config.path;
+        if options.value_style.is_none() && options.value_style_fn.is_none() {
+            options.value_style = config.value_style;
+        }
+        if options.existing_type_path.is_none() {
+            options.storage.postgres = options.storage.postgres.or(config.storage.postgres);
+        }
+        options.storage.mysql = options.storage.mysql.or(config.storage.mysql);
+        options.storage.sqlite = options.storage.sqlite.or(config.storage.sqlite);
+        if let Some(width) = char_width {
+            let span = width.span();
+            let storages = [
+                &mut options.storage.postgres,
+                &mut options.storage.mysql,
+                &mut options.storage.sqlite,
+            ];
+            for storage in storages {
+                match storage {
+                    Some((Storage::Text, _)) => {}
+                    Some((_, storage_span)) => {
+                        return Err(Error::new(
+                            *storage_span,
+                            "DbCharWidth requires the enum to be stored as text",
+                        ));
+                    }
+                    None => *storage = Some((Storage::Text, span)),
+                }
+            }
+            if options.value_style_fn.is_some()
+                || options.serialize_with.is_some()
+                || options.deserialize_with.is_some()
+            {
+                return Err(Error::new(
+                    span,
+                    "DbCharWidth cannot be used with `DbValueStyleFn`, `DbSerializeWith` or `DbDeserializeWith`",
+                ));
+            }
+            options.char_width = Some(width.base10_parse()?);
+            // the oids of `bpchar` and its array type, which compare ignoring the padding
+            options.pg_oids = Some((1042, 1014));
+        }
+        // the schema root is only inferred for enums that don't name their postgres type otherwise
+        if cfg!(feature = "postgres")
+            && schema_root.is_none()
+            && options.existing_type_path.is_none()
+            && options.pg_type.is_none()
+            && options.pg_type_style.is_none()
+            && options.diesel_type.is_none()
+            && options.diesel_type_by_backend.postgres.is_none()
+            && options.storage.postgres() == Storage::Enum
+        {
+            schema_root = config.schema_root;
+        }
+
+        if let (Some(root), false) = (&schema_root, cfg!(feature = "postgres")) {
+            return Err(Error::new_spanned(
+                root,
+                "DbSchemaRoot attribute only applies when the 'postgres' feature is enabled",
+            ));
+        }
+        // an explicit `ExistingTypePath` takes precedence over the schema root
+        if let (None, Some(mut path)) = (&options.existing_type_path, schema_root) {
+            path.segments.push(PathSegment::from(enum_ty.clone()));
+            options.existing_type_path = Some(path);
+        }
+        if let Some(path) = &options.existing_type_path {
+            if !cfg!(feature = "postgres") {
+                return Err(Error::new_spanned(
+                    path,
+                    "ExistingTypePath attribute only applies when the 'postgres' feature is enabled",
+                ));
+            }
+            if let Some(pg_type) = &options.pg_type {
+                return Err(Error::new(
+                    pg_type.span(),
+                    "Cannot specify both `ExistingTypePath` and `PgType` attributes",
+                ));
+            }
+            if options.pg_type_style.is_some() {
+                return Err(Error::new_spanned(
+                    path,
+                    "Cannot specify both `ExistingTypePath` and `PgTypeStyle` attributes",
+                ));
+            }
+            if let Some(pg_diesel_type) = &options.diesel_type_by_backend.postgres {
+                return Err(Error::new_spanned(
+                    pg_diesel_type,
+                    "Cannot specify both `ExistingTypePath` and a postgres `DieselTypeByBackend`",
+                ));
+            }
+            if let Some((storage, span)) = options.storage.postgres {
+                if storage != Storage::Enum {
+                    return Err(Error::new(
+                        span,
+                        "`ExistingTypePath` requires the postgres storage to be `enum`",
+                    ));
+                }
+            }
+        }
+        match (pg_oid, pg_array_oid) {
+            (None, None) => {}
+            (Some(oid), Some(array_oid)) => {
+                if !cfg!(feature = "postgres") {
+                    return Err(Error::new(
+                        oid.span(),
+                        "PgOid attribute only applies when the 'postgres' feature is enabled",
+                    ));
+                }
+                if options.existing_type_path.is_some() {
+                    return Err(Error::new(
+                        oid.span(),
+                        "Cannot specify both `ExistingTypePath` and `PgOid` attributes",
+                    ));
+                }
+                if options.storage.postgres() != Storage::Enum {
+                    return Err(Error::new(
+                        oid.span(),
+                        "`PgOid` requires the postgres storage to be `enum`",
+                    ));
+                }
+                options.pg_oids = Some((oid.base10_parse()?, array_oid.base10_parse()?));
+            }
+            (Some(oid), None) | (None, Some(oid)) => {
+                return Err(Error::new(
+                    oid.span(),
+                    "`PgOid` and `PgArrayOid` must be specified together",
+                ));
+            }
+        }
+        if let Some((span, name)) = existing_type_alias {
+            if options.existing_type_path.is_none() {
+                return Err(Error::new(
+                    span,
+                    "ExistingTypeAlias attribute requires `ExistingTypePath` or `DbSchemaRoot`",
+                ));
+            }
+            let default_mapping = options
+                .diesel_type
+                .clone()
+                .unwrap_or_else(|| default_diesel_type(enum_ty));
+            let alias = name.unwrap_or_else(|| default_mapping.clone());
+            let type_names = &options.diesel_type_by_backend;
+            // the mysql and sqlite types are still generated alongside the existing type
+            let generated = [
+                (cfg!(feature = "mysql"), &type_names.mysql),
+                (cfg!(feature = "sqlite"), &type_names.sqlite),
+            ];
+            if generated.iter().any(|(enabled, name)| {
+                *enabled && *name.as_ref().unwrap_or(&default_mapping) == alias
+            }) {
+                return Err(Error::new(
+                    span,
+                    format!(
+                        "ExistingTypeAlias `{}` has the name of the diesel type generated for mysql or sqlite, give it another name with `ExistingTypeAlias = \"...\"`",
+                        alias
+                    ),
+                ));
+            }
+            options.existing_type_alias = Some(alias);
+        }
+        if let (Some(pg_type), Some(_)) = (&options.pg_type, options.pg_type_style) {
+            return Err(Error::new(
+                pg_type.span(),
+                "Cannot specify both `PgType` and `PgTypeStyle` attributes",
+            ));
+        }
+        if options.existing_type_path.is_none() && options.pg_type.is_none() {
+            options.pg_type_style = options.pg_type_style.or(config.pg_type_style);
+        }
+        if let (Some(pg_type), true) = (&options.pg_type, cfg!(feature = "postgres")) {
+            let value = pg_type.value();
+            // the schema, if given (as in `app.status`), is a name of its own
+            let (schema, name) = split_pg_type_name(&value);
+            for name in schema.into_iter().chain([name]) {
+                if name.is_empty() || name.contains('\0') {
+                    return Err(Error::new(
+                        pg_type.span(),
+                        "`PgType` must be a non-empty name without NUL characters",
+                    ));
+                }
+                if name.len() > 63 {
+                    return Err(Error::new(
+                        pg_type.span(),
+                        format!(
+                            "`PgType` is {} bytes long, but postgres names are limited to 63 bytes",
+                            name.len()
+                        ),
+                    ));
+                }
+            }
+        }
+        if cfg!(feature = "diesel-1") {
+            // these rely on diesel 2 apis, or change the impls which the diesel 1 codegen covers
+            let diesel_2_only = [
+                ("integer storage", options.storage.uses_integer()),
+                (
+                    "DieselTypeByBackend",
+                    options.diesel_type_by_backend.postgres.is_some()
+                        || options.diesel_type_by_backend.mysql.is_some()
+                        || options.diesel_type_by_backend.sqlite.is_some(),
+                ),
+                ("DbArrayStorage", array_storage.is_some()),
+                ("DbTextMapping", options.text_mapping.is_some()),
+                ("DbEnumStr", options.str_type.is_some()),
+                ("DbEnumAsText", options.as_text),
+                ("DbEnumJson", options.json.is_some()),
+                ("DbEnumDsl", options.dsl),
+                ("DbEnumCockroach", options.cockroach.is_some()),
+                ("DbCustomBackend", options.custom_backend.is_some()),
+            ];
+            if let Some((name, _)) = diesel_2_only.iter().find(|(_, used)| *used) {
+                return Err(Error::new(
+                    enum_ty.span(),
+                    format!("{} is not supported with the 'diesel-1' feature", name),
+                ));
+            }
+        }
+        if let (Some(_), Some(style_fn)) = (&options.value_style, &options.value_style_fn) {
+            return Err(Error::new_spanned(
+                style_fn,
+                "Cannot specify both `DbValueStyle` and `DbValueStyleFn` attributes",
+            ));
+        }
+        if let (Some(span), true) = (
+            options.trusted_read,
+            options.value_style_fn.is_some() || options.value_normalize.is_some(),
+        ) {
+            return Err(Error::new(
+                span,
+                "DbEnumTrustedRead cannot be used with `DbValueStyleFn` or `DbValueNormalize`",
+            ));
+        }
+        if let (Some(prefix), true) = (
+            &options.i18n_prefix,
+            options.value_style_fn.is_some() || options.value_normalize.is_some(),
+        ) {
+            return Err(Error::new(
+                prefix.span(),
+                "DbEnumI18n cannot be used with `DbValueStyleFn` or `DbValueNormalize`",
+            ));
+        }
+        if let (Some(span), true) = (
+            options.local,
+            options.dsl || options.generate_tests || options.text_mapping.is_some(),
+        ) {
+            return Err(Error::new(
+                span,
+                "DbEnumLocal cannot be used with `DbEnumDsl`, `DbTextMapping` or `generate_tests`",
+            ));
+        }
+        if let Some(str_type) = &options.str_type {
+            // the wrapper stores the db value as it is, so it must be the db value as stored
+            if options.storage.uses_integer() {
+                return Err(Error::new(
+                    str_type.span(),
+                    "DbEnumStr requires the enum to be stored as text or as a database enum",
+                ));
+            }
+            if options.trusted_read.is_some()
+                || options.serialize_with.is_some()
+                || options.deserialize_with.is_some()
+            {
+                return Err(Error::new(
+                    str_type.span(),
+                    "DbEnumStr cannot be used with `DbEnumTrustedRead`, `DbSerializeWith` or `DbDeserializeWith`",
+                ));
+            }
+        }
+        if let (Some(span), false) = (options.cockroach, cfg!(feature = "postgres")) {
+            return Err(Error::new(
+                span,
+                "DbEnumCockroach attribute requires the 'postgres' feature",
+            ));
+        }
+        if let (Some(span), false) = (options.clap, cfg!(feature = "clap")) {
+            return Err(Error::new(
+                span,
+                "DbEnumClap attribute requires the 'clap' feature",
+            ));
+        }
+        if let (Some(span), false) = (options.rocket, cfg!(feature = "rocket")) {
+            return Err(Error::new(
+                span,
+                "DbEnumRocket attribute requires the 'rocket' feature",
+            ));
+        }
+        if let (Some(span), false) = (options.fake, cfg!(feature = "fake")) {
+            return Err(Error::new(
+                span,
+                "DbEnumFake attribute requires the 'fake' feature",
+            ));
+        }
+        if let (Some(span), false) = (options.arbitrary, cfg!(feature = "arbitrary")) {
+            return Err(Error::new(
+                span,
+                "DbEnumArbitrary attribute requires the 'arbitrary' feature",
+            ));
+        }
+        if let (Some(span), false) = (options.postgres_types, cfg!(feature = "postgres-types")) {
+            return Err(Error::new(
+                span,
+                "DbEnumPostgresTypes attribute requires the 'postgres-types' feature",
+            ));
+        }
+        if let Some(span) = options.sqlx {
+            if !cfg!(feature = "sqlx") {
+                return Err(Error::new(
+                    span,
+                    "DbEnumSqlx attribute requires the 'sqlx' feature",
+                ));
+            }
+            if options.storage.uses_integer() {
+                return Err(Error::new(
+                    span,
+                    "DbEnumSqlx requires the enum to be stored as text or as a database enum",
+                ));
+            }
+            if options.serialize_with.is_some() {
+                return Err(Error::new(
+                    span,
+                    "DbEnumSqlx cannot be used with `DbSerializeWith`",
+                ));
+            }
+        }
+        if let (Some(span), false) = (
+            options.json,
+            cfg!(feature = "postgres") || cfg!(feature = "mysql"),
+        ) {
+            return Err(Error::new(
+                span,
+                "DbEnumJson attribute requires the 'postgres' or 'mysql' feature",
+            ));
+        }
+        if let Some(storage) = &array_storage {
+            if !cfg!(feature = "mysql") && !cfg!(feature = "sqlite") {
+                return Err(Error::new(
+                    storage.span(),
+                    "DbArrayStorage attribute only applies when the 'mysql' or 'sqlite' feature is enabled",
+                ));
+            }
+            options.array_storage = Some(ArrayStorage::from_lit(storage, &array_delimiter)?);
+        }
+        if let Some(delimiter) = &array_delimiter {
+            if !matches!(options.array_storage, Some(ArrayStorage::Delimited(_))) {
+                return Err(Error::new(
+                    delimiter.span(),
+                    "DbArrayDelimiter attribute requires `DbArrayStorage = \"delimited\"`",
+                ));
+            }
+        }
+        match (options.read_only, options.write_only) {
+            (Some(_), Some(span)) => {
+                return Err(Error::new(
+                    span,
+                    "Cannot specify both `DbReadOnly` and `DbWriteOnly` attributes",
+                ));
+            }
+            (Some(_), None) => {
+                options.skip.as_expression = true;
+                options.skip.to_sql = true;
+            }
+            (None, Some(_)) => {
+                options.skip.from_sql = true;
+                options.skip.queryable = true;
+            }
+            (None, None) => {}
+        }
+        options.skip.double_ref = options.codegen.minimal;
+        Ok(options)
+    }
+
+    /// The name of the postgres type: `PgType`, or else the enum name in `PgTypeStyle`
+    /// (snake_case by default)
+    fn pg_type_name(&self, enum_ty: &Ident) -> String {
+        match &self.pg_type {
+            Some(pg_type) => pg_type.value(),
+            None => stylize_value(
+                &enum_ty.to_string(),
+                self.pg_type_style.unwrap_or(CaseStyle::Snake),
+            ),
+        }
+    }
+}
+
+/// Options set on a single variant
+struct VariantOptions {
+    ident: Ident,
+    rename: Option<LitStr>,
+    /// A `db_rename` byte string which isn't valid UTF-8, whose escaped form is `rename`
+    raw_rename: Option<LitByteStr>,
+    null: bool,
+    renamed_from: Option<LitStr>,
+    /// Whether an empty string is read as this variant
+    empty_fallback: bool,
+    label: Option<LitStr>,
+    /// The `#[cfg(...)]` attributes of the variant, repeated on the generated code for it
+    cfgs: Vec<Attribute>,
+    doc: Option<String>,
+    /// The value stored with integer storage, and the `db_value` attribute setting it
+    int_value: i32,
+    explicit_int_value: Option<Attribute>,
+}
+
+impl VariantOptions {
+    fn from_variants(variants: &punctuated::Punctuated<Variant, Token![,]>) -> Result<Vec<Self>> {
+        let mut has_null_variant = false;
+        let mut has_empty_fallback = false;
+        let mut options: Vec<VariantOptions> = Vec::with_capacity(variants.len());
+        // like enum discriminants, variants without a `db_value` follow the previous one
+        let mut next_int_value = Some(0);
+        let mut errors = Errors::default();
+        for variant in variants {
+            // keep going after an invalid variant, to report the others as well
+            let mut parse_variant = || -> Result<()> {
+                if !matches!(variant.fields, Fields::Unit) {
+                    return Err(Error::new_spanned(variant, "Variants must be fieldless"));
+                }
+                let mut rename = None;
+                let mut raw_rename = None;
+                let mut renamed_from = None;
+                let mut label = None;
+                let mut explicit_int_value: Option<(i32, &Attribute)> = None;
+                let mut null = false;
+                let mut empty_fallback = false;
+                for attr in &variant.attrs {
+                    if attr.path().is_ident("db_rename") {
+                        let (value, raw) = db_rename_from_attr(attr)?;
+                        set_once(&mut rename, value, attr)?;
+                        raw_rename = raw;
+                    } else if attr.path().is_ident("db_renamed_from") {
+                        set_once(&mut renamed_from, lit_str_from_attr(attr)?, attr)?;
+                    } else if attr.path().is_ident("db_label") {
+                        set_once(&mut label, lit_str_from_attr(attr)?, attr)?;
+                    } else if attr.path().is_ident("db_value") {
+                        set_once(&mut explicit_int_value, (int_from_attr(attr)?, attr), attr)?;
+                    } else if attr.path().is_ident("db_null_variant") {
+                        attr.meta.require_path_only()?;
+                        if has_null_variant {
+                            return Err(Error::new_spanned(
+                                attr,
+                                "Only one variant may be marked with `db_null_variant`",
+                            ));
+                        }
+                        has_null_variant = true;
+                        null = true;
+                    } else if attr.path().is_ident("db_empty_fallback") {
+                        attr.meta.require_path_only()?;
+                        if has_empty_fallback {
+                            return Err(Error::new_spanned(
+                                attr,
+                                "Only one variant may be marked with `db_empty_fallback`",
+                            ));
+                        }
+                        has_empty_fallback = true;
+                        empty_fallback = true;
+                    }
+                }
+                let int_value = match explicit_int_value {
+                    Some((value, _)) => value,
+                    None => next_int_value.ok_or_else(|| {
+                        Error::new_spanned(&variant.ident, "db value overflows `i32`")
+                    })?,
+                };
+                if let Some(other) = options.iter().find(|other| other.int_value == int_value) {
+                    let message = format!(
+                        "duplicate db_value `{}`, also used by `{}`",
+                        int_value, other.ident
+                    );
+                    return Err(match explicit_int_value {
+                        Some((_, attr)) => Error::new_spanned(attr, message),
+                        None => Error::new_spanned(&variant.ident, message),
+                    });
+                }
+                next_int_value = int_value.checked_add(1);
+                options.push(VariantOptions {
+                    ident: variant.ident.clone(),
+                    rename,
+                    raw_rename,
+                    null,
+                    renamed_from,
+                    empty_fallback,
+                    label,
+                    cfgs: variant
+                        .attrs
+                        .iter()
+                        .filter(|attr| attr.path().is_ident("cfg"))
+                        .cloned()
+                        .collect(),
+                    doc: doc_comment(&variant.attrs),
+                    int_value,
+                    explicit_int_value: explicit_int_value.map(|(_, attr)| attr.clone()),
+                });
+                Ok(())
+            };
+            errors.check(parse_variant());
+        }
+        errors.finish()?;
+        Ok(options)
+    }
+}
+
+/// The text of the `///` comments in `attrs`, if any
+fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(MetaNameValue {
+                value:
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(doc), ..
+                    }),
+                ..
+            }) => Some(doc.value()),
+            _ => None,
+        })
+        .collect();
+    let doc = lines
+        .iter()
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let doc = doc.trim();
+    (!doc.is_empty()).then(|| doc.to_string())
+}
+
+fn default_diesel_type(enum_ty: &Ident) -> Ident {
+    Ident::new(&format!("{}Mapping", enum_ty), Span::call_site())
+}
+
+fn default_text_mapping(enum_ty: &Ident) -> Ident {
+    Ident::new(&format!("{}TextMapping", enum_ty), Span::call_site())
+}
+
+fn default_str_type(enum_ty: &Ident) -> Ident {
+    Ident::new(&format!("{}Str", enum_ty), Span::call_site())
+}
+
+fn default_drift_report(enum_ty: &Ident) -> Ident {
+    Ident::new(&format!("{}DriftReport", enum_ty), Span::call_site())
+}
+
+fn default_rank_type(enum_ty: &Ident) -> Ident {
+    Ident::new(&format!("{}Rank", enum_ty), Span::call_site())
+}
+
+/// The default prefix of `DbEnumI18n`, spanned at the attribute for error messages
+fn default_i18n_prefix(enum_ty: &Ident, span: Span) -> LitStr {
+    let prefix = format!(
+        "enum.{}",
+        stylize_value(&enum_ty.to_string(), CaseStyle::Snake)
+    );
+    LitStr::new(&prefix, span)
+}
+
+fn set_once<T>(slot: &mut Option<T>, value: T, spanned: impl quote::ToTokens) -> Result<()> {
+    if slot.is_some() {
+        return Err(Error::new_spanned(spanned, "duplicate attribute"));
+    }
+    *slot = Some(value);
+    Ok(())
+}
+
+fn set_flag(flag: &mut bool, spanned: impl quote::ToTokens) -> Result<()> {
+    if *flag {
+        return Err(Error::new_spanned(spanned, "duplicate attribute"));
+    }
+    *flag = true;
+    Ok(())
+}
+
+fn attr_name(attr: &Attribute) -> String {
+    attr.path()
+        .get_ident()
+        .map(|ident| ident.to_string())
+        .unwrap_or_default()
+}
+
+/// Parse an integer attribute, either as `attr = 42` or as `attr(42)`, which also
+/// allows negative numbers (`attr(-1)`)
+fn int_from_attr(attr: &Attribute) -> Result<i32> {
+    match &attr.meta {
+        Meta::NameValue(MetaNameValue {
+            value:
+                Expr::Lit(ExprLit {
+                    lit: Lit::Int(lit_int),
+                    ..
+                }),
+            ..
+        }) => lit_int.base10_parse(),
+        Meta::List(list) => list
+            .parse_args::<LitInt>()
+            .and_then(|lit_int| lit_int.base10_parse()),
+        meta => {
+            let attrname = attr_name(attr);
+            Err(Error::new_spanned(
+                meta,
+                format!("Attribute '{}' must have form: {} = 42", attrname, attrname),
+            ))
+        }
+    }
+}
+
+/// Parse an unsigned integer attribute, either as `Attr = 42` or as `Attr(42)`
+fn lit_int_from_attr(attr: &Attribute) -> Result<LitInt> {
+    match &attr.meta {
+        Meta::NameValue(MetaNameValue {
+            value:
+                Expr::Lit(ExprLit {
+                    lit: Lit::Int(lit_int),
+                    ..
+                }),
+            ..
+        }) => Ok(lit_int.clone()),
+        Meta::List(list) => list.parse_args::<LitInt>(),
+        meta => {
+            let attrname = attr_name(attr);
+            Err(Error::new_spanned(
+                meta,
+                format!("Attribute '{}' must have form: {} = 42", attrname, attrname),
+            ))
+        }
+    }
+}
+
+/// Parse a string attribute, either as `Attr = "value"` or as `Attr("value")`
+fn lit_str_from_attr(attr: &Attribute) -> Result<LitStr> {
+    match &attr.meta {
+        Meta::NameValue(MetaNameValue {
+            value:
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(lit_str),
+                    ..
+                }),
+            ..
+        }) => Ok(lit_str.clone()),
+        Meta::List(list) => list.parse_args::<LitStr>(),
+        meta => {
+            let attrname = attr_name(attr);
+            Err(Error::new_spanned(
+                meta,
+                format!(
+                    "Attribute '{}' must have form: {} = \"value\"",
+                    attrname, attrname
+                ),
+            ))
+        }
+    }
+}
+
+/// Parse `db_rename`, which may also be a byte string. A value which isn't valid UTF-8 is
+/// returned escaped, along with the byte string itself.
+fn db_rename_from_attr(attr: &Attribute) -> Result<(LitStr, Option<LitByteStr>)> {
+    if let Meta::NameValue(MetaNameValue {
+        value:
+            Expr::Lit(ExprLit {
+                lit: Lit::ByteStr(lit_bytes),
+                ..
+            }),
+        ..
+    }) = &attr.meta
+    {
+        return Ok(match String::from_utf8(lit_bytes.value()) {
+            Ok(value) => (LitStr::new(&value, lit_bytes.span()), None),
+            Err(err) => (
+                LitStr::new(&escape_invalid_utf8(err.as_bytes()), lit_bytes.span()),
+                Some(lit_bytes.clone()),
+            ),
+        });
+    }
+    Ok((lit_str_from_attr(attr)?, None))
+}
+
+/// `bytes` as a string, with each byte which isn't part of valid UTF-8 escaped as e.g. `\xE9`
+fn escape_invalid_utf8(mut bytes: &[u8]) -> String {
+    let mut escaped = String::new();
+    loop {
+        match std::str::from_utf8(bytes) {
+            Ok(valid) => {
+                escaped.push_str(valid);
+                return escaped;
+            }
+            Err(err) => {
+                let (valid, rest) = bytes.split_at(err.valid_up_to());
+                escaped.push_str(&String::from_utf8_lossy(valid));
+                let invalid_len = err.error_len().unwrap_or(rest.len());
+                for byte in &rest[..invalid_len] {
+                    escaped.push_str(&format!("\\x{:02X}", byte));
+                }
+                bytes = &rest[invalid_len..];
+            }
+        }
+    }
+}
+
+/// Parse an attribute holding e.g. a type path, either as a string
+/// (`Attr = "crate::Path"`) or as plain tokens (`Attr(crate::Path)`)
+fn parse_attr_value<T: parse::Parse>(attr: &Attribute, expected: &str) -> Result<T> {
+    let message = format!(
+        "Attribute '{}' must be a valid {}",
+        attr_name(attr),
+        expected
+    );
+    match &attr.meta {
+        Meta::List(list) if list.parse_args::<LitStr>().is_err() => list
+            .parse_args::<T>()
+            .map_err(|_| Error::new_spanned(&list.tokens, message)),
+        _ => {
+            let lit_str = lit_str_from_attr(attr)?;
+            lit_str
+                .parse::<T>()
+                .map_err(|_| Error::new(lit_str.span(), message))
+        }
+    }
+}
+
+/// The `db_enum(key = "crate::Path")` / `db_enum(key(crate::Path))` equivalent
+/// of [`parse_attr_value`]
+fn parse_nested_value<T: parse::Parse>(meta: &meta::ParseNestedMeta, expected: &str) -> Result<T> {
+    let message = format!("expected a valid {}", expected);
+    if meta.input.peek(Token![=]) {
+        let lit_str: LitStr = meta.value()?.parse()?;
+        lit_str
+            .parse::<T>()
+            .map_err(|_| Error::new(lit_str.span(), message))
+    } else {
+        let content;
+        parenthesized!(content in meta.input);
+        content
+            .parse::<T>()
+            .map_err(|e| Error::new(e.span(), message))
+    }
+}
+
+/// Defines the casing for the database representation.  Follows serde naming convention.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum CaseStyle {
+    Camel,
+    Kebab,
+    Pascal,
+    Upper,
+    ScreamingSnake,
+    Snake,
+    Verbatim,
+}
+
+impl CaseStyle {
+    /// Parse the style from either of the accepted attribute forms:
+    /// `Attr = "style"` or `Attr(style)`
+    fn from_meta(meta: &Meta) -> Result<Self> {
+        let (name, span) = match meta {
+            Meta::NameValue(MetaNameValue {
+                value:
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(lit_str),
+                        ..
+                    }),
+                ..
+            }) => (lit_str.value(), lit_str.span()),
+            Meta::List(list) => {
+                let tokens = list.tokens.clone();
+                if tokens.is_empty() {
+                    return Err(Error::new_spanned(
+                        list,
+                        "expected a casing style such as `snake_case`",
+                    ));
+                }
+                let name = match parse2::<LitStr>(tokens.clone()) {
+                    Ok(lit_str) => lit_str.value(),
+                    // allows e.g. `kebab-case`, which is not a single ident
+                    Err(_) => tokens.clone().into_iter().map(|t| t.to_string()).collect(),
+                };
+                (name, spanned::Spanned::span(&tokens))
+            }
+            Meta::Path(path) => {
+                let name = path
+                    .get_ident()
+                    .map(|ident| ident.to_string())
+                    .unwrap_or_default();
+                return Err(Error::new_spanned(
+                    path,
+                    format!("Attribute '{0}' must have form: {0} = \"value\"", name),
+                ));
+            }
+            Meta::NameValue(name_value) => {
+                return Err(Error::new_spanned(
+                    &name_value.value,
+                    "expected a casing style such as `snake_case`",
+                ))
+            }
+        };
+        Self::from_name(&name)
+            .ok_or_else(|| Error::new(span, format!("unsupported casing: `{}`", name)))
+    }
+
+    /// Parse the style inside `db_enum(...)`: `value_style = "style"` or `value_style(style)`
+    fn from_nested_meta(meta: &meta::ParseNestedMeta) -> Result<Self> {
+        let (name, span) = if meta.input.peek(Token![=]) {
+            let lit_str: LitStr = meta.value()?.parse()?;
+            (lit_str.value(), lit_str.span())
+        } else {
+            let content;
+            parenthesized!(content in meta.input);
+            let tokens: proc_macro2::TokenStream = content.parse()?;
+            let name = tokens.clone().into_iter().map(|t| t.to_string()).collect();
+            (name, spanned::Spanned::span(&tokens))
+        };
+        Self::from_name(&name)
+            .ok_or_else(|| Error::new(span, format!("unsupported casing: `{}`", name)))
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        let style = match name {
+            "camelCase" => CaseStyle::Camel,
+            "kebab-case" => CaseStyle::Kebab,
+            "PascalCase" => CaseStyle::Pascal,
+            "SCREAMING_SNAKE_CASE" => CaseStyle::ScreamingSnake,
+            "UPPERCASE" => CaseStyle::Upper,
+            "snake_case" => CaseStyle::Snake,
+            "verbatim" | "verbatimcase" => CaseStyle::Verbatim,
+            _ => return None,
+        };
+        Some(style)
+    }
+}
+
+/// Generated impls which the user has opted out of, e.g. to write their own
+#[derive(Clone, Copy, Default)]
+struct SkipImpls {
+    as_expression: bool,
+    to_sql: bool,
+    from_sql: bool,
+    queryable: bool,
+    /// The `AsExpression` impls for `&&Enum`, see `DbEnumCodegen(minimal)`
+    double_ref: bool,
+}
+
+impl SkipImpls {
+    /// Parse a single entry of `DbEnumSkip(...)` or `db_enum(skip(...))`
+    fn set(&mut self, meta: &meta::ParseNestedMeta) -> Result<()> {
+        let key = &meta.path;
+        let flag = if key.is_ident("as_expression") {
+            &mut self.as_expression
+        } else if key.is_ident("to_sql") {
+            &mut self.to_sql
+        } else if key.is_ident("from_sql") {
+            &mut self.from_sql
+        } else if key.is_ident("queryable") {
+            &mut self.queryable
+        } else {
+            return Err(
+                meta.error("expected one of `as_expression`, `to_sql`, `from_sql` or `queryable`")
+            );
+        };
+        set_flag(flag, key)
+    }
+}
+
+/// Trade-offs in the generated code chosen with `DbEnumCodegen(...)`
+#[derive(Clone, Copy, Default)]
+struct Codegen {
+    inline: bool,
+    minimal: bool,
+}
+
+impl Codegen {
+    /// Parse a single entry of `DbEnumCodegen(...)` or `db_enum(codegen(...))`
+    fn set(&mut self, meta: &meta::ParseNestedMeta) -> Result<()> {
+        let key = &meta.path;
+        let flag = if key.is_ident("inline") {
+            &mut self.inline
+        } else if key.is_ident("minimal") {
+            &mut self.minimal
+        } else {
+            return Err(meta.error("expected `inline` or `minimal`"));
+        };
+        set_flag(flag, key)
+    }
+}
+
+/// How the enum is stored on a given backend
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Storage {
+    /// The backend's enum type (`CREATE TYPE ... AS ENUM` or mysql `ENUM(...)`)
+    Enum,
+    /// The db value of the variant, as text
+    Text,
+    /// The declaration index of the variant, as a 32 bit integer
+    Integer,
+    /// The declaration index of the variant, as a 64 bit integer
+    BigInt,
+    /// The declaration index of the variant, as a mysql `INT UNSIGNED`
+    UnsignedInteger,
+    /// The declaration index of the variant, as a mysql `SMALLINT UNSIGNED`
+    UnsignedSmallInt,
+}
+
+impl Storage {
+    fn from_lit(lit_str: &LitStr) -> Result<Self> {
+        match lit_str.value().as_str() {
+            "enum" => Ok(Storage::Enum),
+            // `varchar` reads better for mysql, where `text` suggests a `TEXT` column
+            "text" | "varchar" => Ok(Storage::Text),
+            "integer" => Ok(Storage::Integer),
+            "bigint" => Ok(Storage::BigInt),
+            "unsigned_integer" => Ok(Storage::UnsignedInteger),
+            "unsigned_smallint" => Ok(Storage::UnsignedSmallInt),
+            s => Err(Error::new(
+                lit_str.span(),
+                format!(
+                    "unsupported storage: `{}`, expected `enum`, `text` (or `varchar`), `integer`, `bigint`, `unsigned_integer` or `unsigned_smallint`",
+                    s
+                ),
+            )),
+        }
+    }
+
+    fn is_integer(self) -> bool {
+        matches!(
+            self,
+            Storage::Integer
+                | Storage::BigInt
+                | Storage::UnsignedInteger
+                | Storage::UnsignedSmallInt
+        )
+    }
+
+    /// The range of numbers the storage can hold, if narrower than `i32`
+    fn int_range(self) -> Option<(i64, i64)> {
+        match self {
+            Storage::UnsignedInteger => Some((0, i64::from(i32::MAX))),
+            Storage::UnsignedSmallInt => Some((0, i64::from(u16::MAX))),
+            _ => None,
+        }
+    }
+}
+
+/// The storage chosen for each backend through `DbStorageByBackend`, if any
+#[derive(Clone, Copy, Default)]
+struct BackendStorage {
+    postgres: Option<(Storage, Span)>,
+    mysql: Option<(Storage, Span)>,
+    sqlite: Option<(Storage, Span)>,
+}
+
+impl BackendStorage {
+    /// Parse a single entry of `DbStorageByBackend(...)` or `db_enum(storage_by_backend(...))`
+    fn set(&mut self, meta: &meta::ParseNestedMeta) -> Result<()> {
+        let key = &meta.path;
+        let backend = key.get_ident().map(Ident::to_string).unwrap_or_default();
+        let slot = self
+            .slot(&backend)
+            .ok_or_else(|| meta.error("expected one of `postgres`, `mysql` or `sqlite`"))?;
+        let lit_str: LitStr = meta.value()?.parse()?;
+        let storage = Self::parse_storage(&backend, &lit_str)?;
+        set_once(slot, (storage, lit_str.span()), key)
+    }
+
+    fn slot(&mut self, backend: &str) -> Option<&mut Option<(Storage, Span)>> {
+        match backend {
+            "postgres" => Some(&mut self.postgres),
+            "mysql" => Some(&mut self.mysql),
+            "sqlite" => Some(&mut self.sqlite),
+            _ => None,
+        }
+    }
+
+    /// Parse the storage of `backend`, rejecting the ones it doesn't support
+    fn parse_storage(backend: &str, lit_str: &LitStr) -> Result<Storage> {
+        let storage = Storage::from_lit(lit_str)?;
+        if storage == Storage::Enum && backend == "sqlite" {
+            return Err(Error::new(
+                lit_str.span(),
+                "sqlite has no enum type, use `text`, `integer` or `bigint`",
+            ));
+        }
+        if storage.int_range().is_some() && backend != "mysql" {
+            return Err(Error::new(
+                lit_str.span(),
+                "unsigned storage is only supported for mysql, use `integer` or `bigint`",
+            ));
+        }
+        Ok(storage)
+    }
+
+    fn postgres(&self) -> Storage {
+        self.postgres.map_or(Storage::Enum, |(storage, _)| storage)
+    }
+
+    fn mysql(&self) -> Storage {
+        self.mysql.map_or(Storage::Enum, |(storage, _)| storage)
+    }
+
+    fn sqlite(&self) -> Storage {
+        self.sqlite.map_or(Storage::Text, |(storage, _)| storage)
+    }
+
+    /// Whether any backend, enabled or not, is set to `integer` or `bigint` storage
+    fn declares_integer(&self) -> bool {
+        [self.postgres(), self.mysql(), self.sqlite()]
+            .iter()
+            .any(|storage| storage.is_integer())
+    }
+
+    /// Whether the enabled backends of `backends` all compare the stored values in
+    /// declaration order: postgres and mysql enums, and integers. Text compares lexically
+    fn sorts_in_declaration_order(&self, backends: MappingBackends) -> bool {
+        let ordered = |storage: Storage| storage == Storage::Enum || storage.is_integer();
+        (!(cfg!(feature = "postgres") && backends.postgres) || ordered(self.postgres()))
+            && (!(cfg!(feature = "mysql") && backends.mysql) || ordered(self.mysql()))
+            && (!(cfg!(feature = "sqlite") && backends.sqlite) || self.sqlite().is_integer())
+    }
+
+    /// Whether any enabled backend stores variants as integers
+    fn uses_integer(&self) -> bool {
+        (cfg!(feature = "postgres") && self.postgres().is_integer())
+            || (cfg!(feature = "mysql") && self.mysql().is_integer())
+            || (cfg!(feature = "sqlite") && self.sqlite().is_integer())
+    }
+}
+
+/// The diesel type names chosen for each backend through `DieselTypeByBackend`, if any
+#[derive(Default)]
+struct BackendTypeNames {
+    postgres: Option<Ident>,
+    mysql: Option<Ident>,
+    sqlite: Option<Ident>,
+}
+
+impl BackendTypeNames {
+    /// Parse a single entry of `DieselTypeByBackend(...)` or `db_enum(diesel_type_by_backend(...))`
+    fn set(&mut self, meta: &meta::ParseNestedMeta) -> Result<()> {
+        let key = &meta.path;
+        let slot = if key.is_ident("postgres") {
+            &mut self.postgres
+        } else if key.is_ident("mysql") {
+            &mut self.mysql
+        } else if key.is_ident("sqlite") {
+            &mut self.sqlite
+        } else {
+            return Err(meta.error("expected one of `postgres`, `mysql` or `sqlite`"));
+        };
+        let value = parse_nested_value(meta, "identifier")?;
+        set_once(slot, value, key)
+    }
+}
+
+/// The backends for which a generated diesel type is used
+#[derive(Clone, Copy, Default)]
+struct MappingBackends {
+    postgres: bool,
+    mysql: bool,
+    sqlite: bool,
+}
+
+impl MappingBackends {
+    fn add(&mut self, other: MappingBackends) {
+        self.postgres |= other.postgres;
+        self.mysql |= other.mysql;
+        self.sqlite |= other.sqlite;
+    }
+}
+
+/// How a `Vec` of the enum is stored on backends without native array support
+#[derive(Clone, Debug, PartialEq)]
+enum ArrayStorage {
+    Json,
+    Delimited(String),
+}
+
+impl ArrayStorage {
+    fn from_lit(storage: &LitStr, delimiter: &Option<LitStr>) -> Result<Self> {
+        match storage.value().as_str() {
+            "json" => Ok(ArrayStorage::Json),
+            "delimited" => {
+                let delimiter = match delimiter {
+                    Some(delimiter) if delimiter.value().is_empty() => {
+                        return Err(Error::new(
+                            delimiter.span(),
+                            "DbArrayDelimiter must not be empty",
+                        ))
+                    }
+                    Some(delimiter) => delimiter.value(),
+                    None => ",".to_string(),
+                };
+                Ok(ArrayStorage::Delimited(delimiter))
+            }
+            s => Err(Error::new(
+                storage.span(),
+                format!("unsupported array storage: `{}`", s),
+            )),
+        }
+    }
+}
+
+fn generate_derive_enum_impls(
+    options: &EnumOptions,
+    vis: &Visibility,
+    enum_ty: &Ident,
+    variants: &[VariantOptions],
+) -> proc_macro2::TokenStream {
+    let existing_mapping_path = &options
+        .existing_type_path
+        .as_ref()
+        .map(|path| quote! { #path });
+    let pg_internal_type = &options.pg_type_name(enum_ty);
+    // the type name as it appears in generated SQL
+    let pg_type_sql = &quote_pg_type_name(pg_internal_type);
+    let new_diesel_mapping = &options
+        .diesel_type
+        .clone()
+        .unwrap_or_else(|| default_diesel_type(enum_ty));
+    // Maintain backwards compatibility by defaulting to snake case.
+    let case_style = options.value_style.unwrap_or(CaseStyle::Snake);
+    let value_style_fn = &options.value_style_fn.as_ref().map(|path| quote! { #path });
+    let skip = options.skip;
+
+    let modname = Ident::new(&format!("db_enum_impl_{}", enum_ty), Span::call_site());
+    let variant_paths: Vec<proc_macro2::TokenStream> = variants
+        .iter()
+        .map(|variant| {
+            let id = &variant.ident;
+            quote! {
+                #enum_ty::#id
+            }
+        })
+        .collect();
+    let variant_cfgs: Vec<proc_macro2::TokenStream> = variants
+        .iter()
+        .map(|variant| {
+            let cfgs = &variant.cfgs;
+            quote! { #(#cfgs)* }
+        })
+        .collect();
+    // carrying the `cfg`s, for match arms and array elements
+    let variant_ids: Vec<proc_macro2::TokenStream> = variant_cfgs
+        .iter()
+        .zip(&variant_paths)
+        .map(|(cfgs, path)| quote! { #cfgs #path })
+        .collect();
+
+    let null_variant = variants.iter().find(|variant| variant.null).map(|variant| {
+        let id = &variant.ident;
+        quote! {
+            #enum_ty::#id
+        }
+    });
+
+    let variants_db: Vec<String> = variants
+        .iter()
+        .map(|variant| match &variant.rename {
+            Some(rename) => rename.value(),
+            None => stylize_value(&variant.ident.to_string(), case_style),
+        })
+        .collect();
+    // the exact bytes written and read, which differ for byte strings which aren't UTF-8
+    let variants_db_bytes: Vec<Vec<u8>> = variants
+        .iter()
+        .zip(&variants_db)
+        .map(|(variant, value)| match &variant.raw_rename {
+            Some(raw_rename) => raw_rename.value(),
+            None => value.clone().into_bytes(),
+        })
+        .collect();
+    let empty_fallback = variants
+        .iter()
+        .find(|variant| variant.empty_fallback)
+        .map(|variant| {
+            let id = &variant.ident;
+            quote! {
+                if bytes.is_empty() {
+                    return Ok(#enum_ty::#id);
+                }
+            }
+        });
+    let value_normalize = &options
+        .value_normalize
+        .as_ref()
+        .map(|path| quote! { #path });
+    let inline = options.codegen.inline.then(|| quote! { #[inline] });
+    let common = match (value_style_fn, value_normalize) {
+        (None, None) => generate_common(
+            enum_ty,
+            &variant_cfgs,
+            &variant_paths,
+            &variants_db,
+            &variants_db_bytes,
+            options.trusted_read.is_some(),
+            &empty_fallback,
+            &inline,
+        ),
+        _ => {
+            // the variant names, and their values if known up front (always, without a style fn)
+            let variants_named: Vec<(String, Option<String>)> = variants
+                .iter()
+                .zip(&variants_db)
+                .map(|(variant, value)| {
+                    let renamed = match value_style_fn {
+                        Some(_) => variant.rename.as_ref().map(|rename| rename.value()),
+                        None => Some(value.clone()),
+                    };
+                    (variant.ident.to_string(), renamed)
+                })
+                .collect();
+            generate_common_with_style_fn(
+                enum_ty,
+                &variant_cfgs,
+                &variant_paths,
+                &variants_named,
+                value_style_fn,
+                value_normalize,
+                &empty_fallback,
+                &inline,
+            )
+        }
+    };
+    let all_variants = generate_all_variants(enum_ty, &variant_ids);
+    let markdown_table = generate_markdown_table(enum_ty, variants, &variant_paths);
+    // the db values are only known up front without a style fn or normalization, e.g. for
+    // constants
+    let known_variants_db =
+        (value_style_fn.is_none() && value_normalize.is_none()).then_some(&variants_db[..]);
+    let i18n_keys = options.i18n_prefix.as_ref().map(|prefix| {
+        generate_i18n_keys(
+            enum_ty,
+            &variant_cfgs,
+            &variant_paths,
+            &prefix.value(),
+            &variants_db,
+        )
+    });
+    let labels = generate_labels(
+        enum_ty,
+        variants,
+        &variant_cfgs,
+        &variant_paths,
+        known_variants_db,
+    );
+    // the type name is only known for types we name ourselves
+    let pg_named_enum = cfg!(feature = "postgres")
+        && options.storage.postgres() == Storage::Enum
+        && existing_mapping_path.is_none();
+    // the helpers which run queries are written against the diesel 2 connection apis
+    let diesel_2 = !cfg!(feature = "diesel-1");
+    let pg_renames: Vec<(String, &String)> = variants
+        .iter()
+        .zip(&variants_db)
+        .filter_map(|(variant, value)| Some((variant.renamed_from.as_ref()?.value(), value)))
+        .collect();
+    let pg_type_names =
+        pg_named_enum.then(|| generate_pg_type_names(enum_ty, pg_internal_type, pg_type_sql));
+    let pg_rename_values = (pg_named_enum && !pg_renames.is_empty())
+        .then(|| generate_pg_rename_values(enum_ty, pg_type_sql, &pg_renames));
+    let cockroach = options.cockroach.is_some();
+    let pg_remove_values = pg_named_enum.then(|| {
+        let variants_no_null: Vec<&proc_macro2::TokenStream> = variant_ids
+            .iter()
+            .zip(variants)
+            .filter(|(_, variant)| !variant.null)
+            .map(|(id, _)| id)
+            .collect();
+        generate_pg_remove_values(enum_ty, pg_internal_type, &variants_no_null)
+    });
+    let pg_sync_variants = (pg_named_enum && diesel_2).then(|| {
+        let variants_no_null: Vec<&proc_macro2::TokenStream> = variant_ids
+            .iter()
+            .zip(variants)
+            .filter(|(_, variant)| !variant.null)
+            .map(|(id, _)| id)
+            .collect();
+        let sync_variants =
+            generate_pg_sync_variants(enum_ty, pg_type_sql, &variants_no_null, cockroach);
+        let migration =
+            generate_pg_migration(enum_ty, pg_internal_type, &variants_no_null, cockroach);
+        let sort_order = generate_pg_sort_order(enum_ty, pg_type_sql);
+        let find_columns = generate_pg_find_columns(enum_ty, pg_type_sql);
+        let remove_values_migration = generate_pg_remove_values_migration(enum_ty, pg_type_sql);
+        quote! {
+            #sync_variants
+            #migration
+            #sort_order
+            #find_columns
+            #remove_values_migration
+        }
+    });
+    let pg_refinery_migration = (pg_named_enum && cfg!(feature = "refinery")).then(|| {
+        let variants_no_null: Vec<&proc_macro2::TokenStream> = variant_ids
+            .iter()
+            .zip(variants)
+            .filter(|(_, variant)| !variant.null)
+            .map(|(id, _)| id)
+            .collect();
+        generate_pg_refinery_migration(enum_ty, pg_internal_type, &variants_no_null)
+    });
+    let sqlite_validation = (cfg!(feature = "sqlite") && diesel_2).then(|| {
+        let variants_no_null: Vec<&proc_macro2::TokenStream> = variant_ids
+            .iter()
+            .zip(variants)
+            .filter(|(_, variant)| !variant.null)
+            .map(|(id, _)| id)
+            .collect();
+        let validation =
+            generate_sqlite_validation(enum_ty, &variants_no_null, options.storage.sqlite());
+        let check_constraint =
+            generate_sqlite_check_constraint(enum_ty, &variants_no_null, options.storage.sqlite());
+        quote! {
+            #validation
+            #check_constraint
+        }
+    });
+    // cockroachdb doesn't support `COMMENT ON TYPE` (in all versions)
+    let pg_comment = (pg_named_enum && !cockroach).then(|| {
+        let variant_docs: Vec<(String, &str)> = variants
+            .iter()
+            .zip(&variants_db)
+            .filter(|(variant, _)| !variant.null)
+            .filter_map(|(variant, value)| {
+                let doc = variant.doc.as_deref()?;
+                // with a style fn, name the variant instead of its (runtime) db value
+                let name = match value_style_fn {
+                    Some(_) if variant.rename.is_none() => variant.ident.to_string(),
+                    _ => value.clone(),
+                };
+                Some((name, doc))
+            })
+            .collect();
+        generate_pg_comment(enum_ty, pg_type_sql, options.doc.as_deref(), &variant_docs)
+    });
+    // with a style fn, the db values are only known at runtime
+    let mysql_column_type = (cfg!(feature = "mysql") && value_style_fn.is_none()).then(|| {
+        let variants_db_no_null: Vec<&String> = variants_db
+            .iter()
+            .zip(variants)
+            .filter(|(_, variant)| !variant.null)
+            .map(|(value, _)| value)
+            .collect();
+        generate_mysql_column_type(
+            enum_ty,
+            &variants_db_no_null,
+            options.storage.mysql(),
+            options.char_width,
+        )
+    });
+    let int_representation = if options.storage.uses_integer() {
+        let int_values: Vec<i32> = variants.iter().map(|variant| variant.int_value).collect();
+        Some(generate_int_representation(
+            enum_ty,
+            &variant_cfgs,
+            &variant_paths,
+            &int_values,
+            &inline,
+        ))
+    } else {
+        None
+    };
+    let type_names = &options.diesel_type_by_backend;
+    let pg_mapping = type_names.postgres.as_ref().unwrap_or(new_diesel_mapping);
+    let mysql_mapping = type_names.mysql.as_ref().unwrap_or(new_diesel_mapping);
+    let sqlite_mapping = type_names.sqlite.as_ref().unwrap_or(new_diesel_mapping);
+    // The default mapping is generated for any backend without a name of its own.
+    // With an existing (postgres) mapping, it's only generated for the other enabled backends
+    let mut new_mappings: Vec<(&Ident, MappingBackends)> = Vec::new();
+    if existing_mapping_path.is_none() {
+        new_mappings.push((new_diesel_mapping, MappingBackends::default()));
+    }
+    let backend_mappings = [
+        (
+            pg_mapping,
+            MappingBackends {
+                postgres: true,
+                ..Default::default()
+            },
+        ),
+        (
+            mysql_mapping,
+            MappingBackends {
+                mysql: true,
+                ..Default::default()
+            },
+        ),
+        (
+            sqlite_mapping,
+            MappingBackends {
+                sqlite: true,
+                ..Default::default()
+            },
+        ),
+    ];
+    for (mapping, backend) in backend_mappings {
+        if existing_mapping_path.is_some()
+            && (backend.postgres
+                || (backend.mysql && !cfg!(feature = "mysql"))
+                || (backend.sqlite && !cfg!(feature = "sqlite")))
+        {
+            continue;
+        }
+        match new_mappings.iter_mut().find(|(name, _)| *name == mapping) {
+            Some((_, backends)) => backends.add(backend),
+            None => new_mappings.push((mapping, backend)),
+        }
+    }
+    let mapping_labels: Option<Vec<proc_macro2::TokenStream>> = known_variants_db.map(|values| {
+        variants
+            .iter()
+            .zip(&variant_cfgs)
+            .zip(values)
+            .filter(|((variant, _), _)| !variant.null)
+            .map(|((_, cfgs), value)| quote! { #cfgs #value })
+            .collect()
+    });
+    let diesel_mapping_decl: Vec<_> = new_mappings
+        .iter()
+        .map(|(mapping, backends)| {
+            let doc = format!("The diesel SQL type of `{}`", enum_ty);
+            let generate = if cfg!(feature = "diesel-1") {
+                generate_diesel1_mapping
+            } else {
+                generate_new_diesel_mapping
+            };
+            let mapping_decl = generate(
+                mapping,
+                &doc,
+                pg_internal_type,
+                options.pg_oids,
+                &options.storage,
+                *backends,
+            );
+            // only a postgres enum has a type of its own to name
+            let sql_type_name = (backends.postgres && options.storage.postgres() == Storage::Enum)
+                .then_some(pg_type_sql.as_str());
+            let mapping_consts = generate_mapping_consts(
+                &quote! { #mapping },
+                enum_ty,
+                sql_type_name,
+                mapping_labels.as_deref(),
+            );
+            quote! {
+                #mapping_decl
+                #mapping_consts
+            }
+        })
+        .collect();
+    let (diesel_mapping_def, diesel_mapping_use): (Vec<_>, Vec<_>) = new_mappings
+        .iter()
+        .map(|(mapping, _)| {
+            (
+                generate_common_impls(&quote! { #mapping }, enum_ty, &null_variant, skip),
+                quote! {
+                    pub use self::#modname::#mapping;
+                },
+            )
+        })
+        .unzip();
+
+    // fixed-width values are padded and trimmed by generated hooks
+    let char_width_hooks: Option<(Path, Path)> = options.char_width.map(|_| {
+        (
+            parse_quote!(db_char_representation),
+            parse_quote!(from_db_char_representation),
+        )
+    });
+    // values which aren't UTF-8 are written from their bytes
+    let db_bytes = variants
+        .iter()
+        .any(|variant| variant.raw_rename.is_some())
+        .then(|| generate_db_bytes(enum_ty, &variant_cfgs, &variant_paths, &variants_db_bytes));
+    let db_bytes_hook: Option<Path> = db_bytes
+        .as_ref()
+        .map(|_| parse_quote!(db_bytes_representation));
+    let hooks = match (&char_width_hooks, &db_bytes_hook) {
+        (Some((serialize, deserialize)), _) => SqlHooks {
+            serialize_with: Some(serialize),
+            deserialize_with: Some(deserialize),
+        },
+        (None, Some(serialize)) => SqlHooks {
+            serialize_with: Some(serialize),
+            deserialize_with: None,
+        },
+        (None, None) => SqlHooks {
+            serialize_with: options.serialize_with.as_ref(),
+            deserialize_with: options.deserialize_with.as_ref(),
+        },
+    };
+    let char_width = options.char_width.map(|width| {
+        generate_char_width(enum_ty, &variant_cfgs, &variant_paths, &variants_db, width)
+    });
+    let pg_impl = if cfg!(feature = "postgres") {
+        match existing_mapping_path {
+            Some(path) => {
+                let common_impls_on_existing_diesel_mapping =
+                    generate_common_impls(path, enum_ty, &null_variant, skip);
+                let postgres_impl =
+                    generate_postgres_impl(path, enum_ty, true, Storage::Enum, skip, hooks);
+                Some(quote! {
+                    #common_impls_on_existing_diesel_mapping
+                    #postgres_impl
+                })
+            }
+            None => Some(generate_postgres_impl(
+                &quote! { #pg_mapping },
+                enum_ty,
+                false,
+                options.storage.postgres(),
+                skip,
+                hooks,
+            )),
+        }
+    } else {
+        None
+    };
+
+    let (array_mapping_decl, array_mapping_def, array_mapping_use) = match &options.array_storage {
+        Some(storage) => {
+            let array_mapping = Ident::new(&format!("{}ArrayMapping", enum_ty), Span::call_site());
+            let array_ty = Ident::new(&format!("{}Array", enum_ty), Span::call_site());
+            let array_decl = generate_array_storage_types(&array_mapping, &array_ty, enum_ty);
+            let array_impls =
+                generate_array_storage_impls(&array_mapping, &array_ty, enum_ty, storage);
+            (
+                Some(array_decl),
+                Some(array_impls),
+                Some(quote! {
+                    pub use self::#modname::{#array_mapping, #array_ty};
+                }),
+            )
+        }
+        None => (None, None, None),
+    };
+
+    let (text_mapping_def, text_mapping_use) = match &options.text_mapping {
+        Some(text_mapping) => (
+            Some(generate_text_mapping(
+                text_mapping,
+                enum_ty,
+                &null_variant,
+                skip,
+            )),
+            Some(quote! {
+                pub use self::#modname::#text_mapping;
+            }),
+        ),
+        None => (None, None),
+    };
+
+    let (existing_type_alias_decl, existing_type_alias_use) =
+        match (&options.existing_type_alias, existing_mapping_path) {
+            (Some(alias), Some(path)) => {
+                let doc = format!(
+                    "The diesel SQL type of `{}`, an alias of `{}`",
+                    enum_ty, path
+                )
+                .replace(" :: ", "::");
+                // on the existing type, which must be declared in this crate
+                let sql_type_name =
+                    (options.storage.postgres() == Storage::Enum).then_some(pg_type_sql.as_str());
+                let mapping_consts = generate_mapping_consts(
+                    path,
+                    enum_ty,
+                    sql_type_name,
+                    mapping_labels.as_deref(),
+                );
+                (
+                    Some(quote! {
+                        #[doc = #doc]
+                        pub type #alias = #path;
+
+                        #mapping_consts
+                    }),
+                    Some(quote! {
+                        pub use self::#modname::#alias;
+                    }),
+                )
+            }
+            _ => (None, None),
+        };
+
+    let (str_type_decl, str_type_def, str_type_use) = match &options.str_type {
+        Some(str_type) => {
+            // every mapping the enum can be read from and written to
+            let mut mappings: Vec<proc_macro2::TokenStream> = Vec::new();
+            if cfg!(feature = "postgres") {
+                mappings.push(match existing_mapping_path {
+                    Some(path) => path.clone(),
+                    None => quote! { #pg_mapping },
+                });
+            }
+            if cfg!(feature = "mysql") {
+                mappings.push(quote! { #mysql_mapping });
+            }
+            if cfg!(feature = "sqlite") {
+                mappings.push(quote! { #sqlite_mapping });
+            }
+            if options.custom_backend.is_some() {
+                mappings.push(match existing_mapping_path {
+                    Some(path) => path.clone(),
+                    None => quote! { #new_diesel_mapping },
+                });
+            }
+            if let Some(text_mapping) = &options.text_mapping {
+                mappings.push(quote! { #text_mapping });
+            }
+            if options.as_text {
+                mappings.push(quote! { Text });
+            }
+            let mut seen = Vec::new();
+            mappings.retain(|mapping| {
+                let name = mapping.to_string();
+                let new = !seen.contains(&name);
+                seen.push(name);
+                new
+            });
+            (
+                Some(generate_str_type(str_type, enum_ty)),
+                Some(generate_str_type_impls(str_type, enum_ty, &mappings)),
+                Some(quote! {
+                    pub use self::#modname::#str_type;
+                }),
+            )
+        }
+        None => (None, None, None),
+    };
+
+    let dsl = if options.dsl {
+        let variants_no_null: Vec<&VariantOptions> =
+            variants.iter().filter(|variant| !variant.null).collect();
+        Some(generate_dsl(vis, enum_ty, &variants_no_null))
+    } else {
+        None
+    };
+
+    // a module in a function body can't name the items of the function, so there is no
+    // serde module for local enums
+    let (serde_def, serde_use) = if cfg!(feature = "serde") && options.local.is_none() {
+        let serde_mod = Ident::new(
+            &format!("{}_as_db_string", enum_ty.to_string().to_snake_case()),
+            Span::call_site(),
+        );
+        (
+            Some(generate_serde_with(&serde_mod, enum_ty)),
+            Some(quote! {
+                #vis use self::#modname::#serde_mod;
+            }),
+        )
+    } else {
+        (None, None)
+    };
+
+    let clap_value_enum = if options.clap.is_some() {
+        // the null variant has no db value, so it is not offered on the command line
+        let variants_no_null: Vec<&proc_macro2::TokenStream> = variant_ids
+            .iter()
+            .zip(variants)
+            .filter(|(_, variant)| !variant.null)
+            .map(|(id, _)| id)
+            .collect();
+        Some(generate_clap_value_enum(enum_ty, &variants_no_null))
+    } else {
+        None
+    };
+
+    let rocket = if options.rocket.is_some() {
+        Some(generate_rocket(enum_ty))
+    } else {
+        None
+    };
+
+    let fake = if options.fake.is_some() {
+        Some(generate_fake_dummy(enum_ty, &variant_ids))
+    } else {
+        None
+    };
+
+    let arbitrary = if options.arbitrary.is_some() {
+        Some(generate_arbitrary(enum_ty, &variant_ids))
+    } else {
+        None
+    };
+
+    let postgres_types = if options.postgres_types.is_some() {
+        let variants_no_null: Vec<&proc_macro2::TokenStream> = variant_ids
+            .iter()
+            .zip(variants)
+            .filter(|(_, variant)| !variant.null)
+            .map(|(id, _)| id)
+            .collect();
+        Some(generate_postgres_types(
+            enum_ty,
+            &variants_no_null,
+            &null_variant,
+            hooks,
+        ))
+    } else {
+        None
+    };
+
+    let sqlx = options.sqlx.map(|_| {
+        let pg_type = (options.storage.postgres() == Storage::Enum)
+            .then_some((pg_internal_type.as_str(), pg_type_sql.as_str()));
+        generate_sqlx(enum_ty, &null_variant, pg_type, hooks)
+    });
+
+    let as_text_impls = options
+        .as_text
+        .then(|| generate_as_text_impls(enum_ty, &null_variant, skip));
+
+    let json_impls = options
+        .json
+        .map(|_| generate_json_impls(enum_ty, &null_variant, skip));
+    let json_string_codec = (options.json.is_some()
+        || matches!(options.array_storage, Some(ArrayStorage::Json)))
+    .then(generate_json_string_codec);
+
+    let index = options
+        .index
+        .then(|| generate_index(enum_ty, &variant_cfgs, &variant_paths));
+    let str_eq = if options.str_eq {
+        Some(generate_str_eq(enum_ty))
+    } else {
+        None
+    };
+
+    let tests = if options.generate_tests {
+        Some(generate_tests(enum_ty))
+    } else {
+        None
+    };
+
+    let variants_null: Vec<bool> = variants.iter().map(|variant| variant.null).collect();
+    // the helpers reading the values of an enum type are written against diesel 2
+    let pg_enum =
+        diesel_2 && cfg!(feature = "postgres") && options.storage.postgres() == Storage::Enum;
+    let mysql_enum =
+        diesel_2 && cfg!(feature = "mysql") && options.storage.mysql() == Storage::Enum;
+    // the drift report is declared with the other types, so that it can be named
+    let drift_report = (pg_enum || mysql_enum).then(|| default_drift_report(enum_ty));
+    let (drift_report_decl, drift_report_use) = match &drift_report {
+        Some(drift_report) => (
+            Some(generate_drift_report(drift_report, enum_ty)),
+            Some(quote! {
+                pub use self::#modname::#drift_report;
+            }),
+        ),
+        None => (None, None),
+    };
+    let drift_report_impls = drift_report.as_ref().map(|drift_report| {
+        generate_drift_report_impls(
+            enum_ty,
+            drift_report,
+            &variant_ids,
+            &variants_null,
+            pg_type_sql,
+            pg_enum,
+            mysql_enum,
+        )
+    });
+    // likewise the expression returned by `db_rank`, so it can be named in signatures
+    let rank_type = diesel_2.then(|| default_rank_type(enum_ty));
+    let (rank_type_decl, rank_type_use) = match &rank_type {
+        Some(rank_type) => (
+            Some(generate_rank_type(rank_type, enum_ty)),
+            Some(quote! {
+                pub use self::#modname::#rank_type;
+            }),
+        ),
+        None => (None, None),
+    };
+
+    let db_rank = rank_type.as_ref().map(|rank_type| {
+        generate_db_rank(
+            enum_ty,
+            rank_type,
+            &variant_ids,
+            &variants_null,
+            options.storage,
+        )
+    });
+
+    let ord = if options.ord {
+        Some(generate_ord(
+            enum_ty,
+            &variant_ids,
+            &variants_null,
+            pg_enum,
+            mysql_enum,
+        ))
+    } else {
+        None
+    };
+
+    let mysql_impl = if cfg!(feature = "mysql") {
+        Some(generate_mysql_impl(
+            mysql_mapping,
+            enum_ty,
+            options.storage.mysql(),
+            skip,
+            hooks,
+        ))
+    } else {
+        None
+    };
+
+    let sqlite_impl = if cfg!(feature = "sqlite") {
+        Some(generate_sqlite_impl(
+            sqlite_mapping,
+            enum_ty,
+            options.storage.sqlite(),
+            skip,
+            hooks,
+        ))
+    } else {
+        None
+    };
+
+    let custom_backend_impl = options.custom_backend.as_ref().map(|backend| {
+        let diesel_mapping = match existing_mapping_path {
+            Some(path) => path.clone(),
+            None => quote! { #new_diesel_mapping },
+        };
+        generate_custom_backend_impl(&diesel_mapping, enum_ty, backend, skip)
+    });
+
+    let diesel_imports = if cfg!(feature = "diesel-1") {
+        quote! {
+            use diesel::{
+                backend::Backend,
+                deserialize::{self, FromSql, FromSqlRow},
+                expression::{bound::Bound, AsExpression},
+                row::Row,
+                serialize::{self, IsNull, Output, ToSql},
+                sql_types::*,
+                Queryable,
+            };
+        }
+    } else {
+        quote! {
+            use diesel::{
+                backend::Backend,
+                deserialize::{self, FromSql},
+                expression::AsExpression,
+                internal::derives::as_expression::Bound,
+                row::Row,
+                serialize::{self, IsNull, Output, ToSql},
+                sql_types::*,
+                Queryable,
+            };
+        }
+    };
+    let imports = quote! {
+        // name everything from std and the extern prelude explicitly, which takes precedence
+        // over the names from `super`, and works under `#![no_implicit_prelude]`
+        #[allow(unused_imports)]
+        use ::std::{
+            self, assert, assert_ne,
+            boxed::Box,
+            clone::Clone,
+            cmp::{Eq, Ord, PartialEq, PartialOrd},
+            convert::{AsRef, From, Into, TryFrom},
+            default::Default,
+            format, include_bytes,
+            iter::{IntoIterator as _, Iterator as _},
+            marker::{Copy, Send, Sized, Sync},
+            option::Option::{self, None, Some},
+            option_env, panic,
+            result::Result::{self, Err, Ok},
+            string::{String, ToString},
+            vec,
+            vec::Vec,
+            write,
+        };
+        use ::diesel;
+        #diesel_imports
+        use std::io::Write;
+    };
+
+    // rebuild when the config file changes, or `DIESEL_DERIVE_ENUM_CONFIG` points elsewhere.
+    // A config file which doesn't exist yet can't be tracked, so adding one needs a rebuild
+    let config_file = options.config_path.as_ref().map(|path| {
+        quote! {
+            const _: &[u8] = include_bytes!(#path);
+        }
+    });
+    let config_dependency = quote! {
+        const _: Option<&str> = option_env!("DIESEL_DERIVE_ENUM_CONFIG");
+        #config_file
+    };
+
+    let decls = quote! {
+        #(#diesel_mapping_decl)*
+        #array_mapping_decl
+        #existing_type_alias_decl
+        #str_type_decl
+        #drift_report_decl
+        #rank_type_decl
+    };
+    let body = quote! {
+        #config_dependency
+
+        #common
+        #char_width
+        #db_bytes
+        #all_variants
+        #markdown_table
+        #labels
+        #i18n_keys
+        #mysql_column_type
+        #pg_type_names
+        #pg_comment
+        #pg_rename_values
+        #pg_remove_values
+        #pg_sync_variants
+        #pg_refinery_migration
+        #sqlite_validation
+        #int_representation
+        #ord
+        #drift_report_impls
+        #db_rank
+        #index
+        #str_eq
+        #serde_def
+        #clap_value_enum
+        #rocket
+        #fake
+        #arbitrary
+        #postgres_types
+        #sqlx
+        #tests
+        #(#diesel_mapping_def)*
+        #json_string_codec
+        #array_mapping_def
+        #text_mapping_def
+        #str_type_def
+        #as_text_impls
+        #json_impls
+        #pg_impl
+        #mysql_impl
+        #sqlite_impl
+        #custom_backend_impl
+    };
+
+    if options.local.is_some() {
+        // the types are declared in place, where the function body can name them, and
+        // everything else goes in an anonymous const, which sees the items of the function
+        quote! {
+            #decls
+            const _: () = {
+                #imports
+                #body
+            };
+        }
+    } else {
+        let reexports = (!options.no_reexport).then(|| {
+            quote! {
+                #(#diesel_mapping_use)*
+                #existing_type_alias_use
+                #array_mapping_use
+                #text_mapping_use
+                #str_type_use
+                #drift_report_use
+                #rank_type_use
+            }
+        });
+        quote! {
+            #reexports
+            #serde_use
+            #dsl
+            #[allow(non_snake_case)]
+            mod #modname {
+                use super::*;
+                #imports
+                #decls
+                #body
+            }
+        }
+    }
+}
+
+fn stylize_value(value: &str, style: CaseStyle) -> String {
+    match style {
+        CaseStyle::Camel => value.to_lower_camel_case(),
+        CaseStyle::Kebab => value.to_kebab_case(),
+        CaseStyle::Pascal => value.to_upper_camel_case(),
+        CaseStyle::Upper => value.to_uppercase(),
+        CaseStyle::ScreamingSnake => value.to_shouty_snake_case(),
+        CaseStyle::Snake => value.to_snake_case(),
+        CaseStyle::Verbatim => value.to_string(),
+    }
+}
+
+/// The case conversions of `heck` 0.4 (without its `unicode` feature), which the db values
+/// and generated names have always used, so they must not change
+trait Casing {
+    fn to_snake_case(&self) -> String;
+    fn to_kebab_case(&self) -> String;
+    fn to_shouty_snake_case(&self) -> String;
+    fn to_lower_camel_case(&self) -> String;
+    fn to_upper_camel_case(&self) -> String;
+}
+
+impl Casing for str {
+    fn to_snake_case(&self) -> String {
+        join_words(self, "_", |_, word, out| push_lowercase(word, out))
+    }
+
+    fn to_kebab_case(&self) -> String {
+        join_words(self, "-", |_, word, out| push_lowercase(word, out))
+    }
+
+    fn to_shouty_snake_case(&self) -> String {
+        join_words(self, "_", |_, word, out| {
+            out.extend(word.chars().flat_map(char::to_uppercase))
+        })
+    }
+
+    fn to_lower_camel_case(&self) -> String {
+        join_words(self, "", |first, word, out| {
+            if first {
+                push_lowercase(word, out)
+            } else {
+                push_capitalized(word, out)
+            }
+        })
+    }
+
+    fn to_upper_camel_case(&self) -> String {
+        join_words(self, "", |_, word, out| push_capitalized(word, out))
+    }
+}
+
+/// Split `s` into words, and join them with `separator` after transforming each. Words
+/// are separated by anything but ASCII letters and digits, by `_`, by a lowercase letter
+/// followed by an uppercase one (`fooBar`), and before the last uppercase letter of a run
+/// followed by a lowercase one (`HTTPServer`).
+fn join_words(
+    s: &str,
+    separator: &str,
+    mut push_word: impl FnMut(bool, &str, &mut String),
+) -> String {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Mode {
+        Boundary,
+        Lowercase,
+        Uppercase,
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut first = true;
+    let mut push = |word: &str, out: &mut String| {
+        if !first {
+            out.push_str(separator);
+        }
+        push_word(first, word, out);
+        first = false;
+    };
+    for word in s.split(|c: char| !c.is_ascii_alphanumeric()) {
+        let mut chars = word.char_indices().peekable();
+        let mut start = 0;
+        let mut mode = Mode::Boundary;
+        while let Some((i, c)) = chars.next() {
+            if c == '_' {
+                if start == i {
+                    start += 1;
+                }
+                continue;
+            }
+            let (next_i, next) = match chars.peek() {
+                Some(&next) => next,
+                None => {
+                    push(&word[start..], &mut out);
+                    break;
+                }
+            };
+            let next_mode = if c.is_lowercase() {
+                Mode::Lowercase
+            } else if c.is_uppercase() {
+                Mode::Uppercase
+            } else {
+                mode
+            };
+            if next == '_' || (next_mode == Mode::Lowercase && next.is_uppercase()) {
+                push(&word[start..next_i], &mut out);
+                start = next_i;
+                mode = Mode::Boundary;
+            } else if mode == Mode::Uppercase && c.is_uppercase() && next.is_lowercase() {
+                push(&word[start..i], &mut out);
+                start = i;
+                mode = Mode::Boundary;
+            } else {
+                mode = next_mode;
+            }
+        }
+    }
+    out
+}
+
+fn push_lowercase(word: &str, out: &mut String) {
+    let mut chars = word.chars().peekable();
+    while let Some(c) = chars.next() {
+        // a final capital sigma lowercases to the final form
+        if c == 'Σ' && chars.peek().is_none() {
+            out.push('ς');
+        } else {
+            out.extend(c.to_lowercase());
+        }
+    }
+}
+
+fn push_capitalized(word: &str, out: &mut String) {
+    let mut chars = word.chars();
+    if let Some(c) = chars.next() {
+        out.extend(c.to_uppercase());
+        push_lowercase(chars.as_str(), out);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn generate_common(
+    enum_ty: &Ident,
+    variants_cfg: &[proc_macro2::TokenStream],
+    variants_rs: &[proc_macro2::TokenStream],
+    variants_db: &[String],
+    variants_db_bytes: &[Vec<u8>],
+    trusted_read: bool,
+    empty_fallback: &Option<proc_macro2::TokenStream>,
+    inline: &Option<proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    let from_db_binary_representation = if trusted_read {
+        let len = distinguishing_prefix_len(variants_db_bytes);
+        let prefixes = variants_db_bytes
+            .iter()
+            .map(|bytes| LitByteStr::new(&bytes[..len.min(bytes.len())], Span::call_site()));
+        quote! {
+            #inline
+            fn from_db_binary_representation(bytes: &[u8]) -> deserialize::Result<#enum_ty> {
+                #empty_fallback
+                match bytes.get(..#len).unwrap_or(bytes) {
+                    #(#variants_cfg #prefixes => Ok(#variants_rs),)*
+                    _ => Err("Unrecognized enum variant".into()),
+                }
+            }
+        }
+    } else {
+        let variants_db_bytes = variants_db_bytes
+            .iter()
+            .map(|bytes| LitByteStr::new(bytes, Span::call_site()));
+        quote! {
+            #inline
+            fn from_db_binary_representation(bytes: &[u8]) -> deserialize::Result<#enum_ty> {
+                #empty_fallback
+                match bytes {
+                    #(#variants_cfg #variants_db_bytes => Ok(#variants_rs),)*
+                    v => Err(format!("Unrecognized enum variant: '{}'",
+                        String::from_utf8_lossy(v)).into()),
+                }
+            }
+        }
+    };
+    quote! {
+        #inline
+        fn db_str_representation(e: &#enum_ty) -> &'static str {
+            match *e {
+                #(#variants_cfg #variants_rs => #variants_db,)*
+            }
+        }
+
+        #from_db_binary_representation
+    }
+}
+
+/// The exact bytes of each db value, for values which aren't valid UTF-8
+fn generate_db_bytes(
+    enum_ty: &Ident,
+    variants_cfg: &[proc_macro2::TokenStream],
+    variants_rs: &[proc_macro2::TokenStream],
+    variants_db_bytes: &[Vec<u8>],
+) -> proc_macro2::TokenStream {
+    let variants_db_bytes = variants_db_bytes
+        .iter()
+        .map(|bytes| LitByteStr::new(bytes, Span::call_site()));
+    quote! {
+        fn db_bytes_representation(e: &#enum_ty) -> &'static [u8] {
+            match *e {
+                #(#variants_cfg #variants_rs => #variants_db_bytes,)*
+            }
+        }
+    }
+}
+
+/// The shortest length to which the (unique) values can be cut and still all differ
+fn distinguishing_prefix_len(values: &[Vec<u8>]) -> usize {
+    let max_len = values.iter().map(Vec::len).max().unwrap_or(0);
+    (0..max_len)
+        .find(|&len| {
+            let mut prefixes: Vec<&[u8]> = values
+                .iter()
+                .map(|value| &value[..len.min(value.len())])
+                .collect();
+            prefixes.sort_unstable();
+            prefixes.windows(2).all(|pair| pair[0] != pair[1])
+        })
+        .unwrap_or(max_len)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn generate_common_with_style_fn(
+    enum_ty: &Ident,
+    variants_cfg: &[proc_macro2::TokenStream],
+    variants_rs: &[proc_macro2::TokenStream],
+    variants_named: &[(String, Option<String>)],
+    style_fn: &Option<proc_macro2::TokenStream>,
+    normalize: &Option<proc_macro2::TokenStream>,
+    empty_fallback: &Option<proc_macro2::TokenStream>,
+    inline: &Option<proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    let n_variants = variants_rs.len();
+    let indices: Vec<usize> = (0..n_variants).collect();
+    // Explicitly renamed variants keep their literal value, the rest are
+    // computed by the user function the first time they are needed
+    let values = variants_named.iter().map(|(name, renamed)| {
+        let value = match renamed {
+            Some(renamed) => quote! { #renamed },
+            None => quote! { Into::<String>::into(#style_fn(#name)) },
+        };
+        match (normalize, renamed) {
+            (Some(normalize), _) => quote! {
+                Box::leak(
+                    Into::<String>::into(#normalize(AsRef::<str>::as_ref(&#value)))
+                        .into_boxed_str(),
+                )
+            },
+            (None, Some(_)) => value,
+            (None, None) => quote! { Box::leak(#value.into_boxed_str()) },
+        }
+    });
+    let normalized_bytes = normalize.as_ref().map(|normalize| {
+        quote! {
+            let normalized = match std::str::from_utf8(bytes) {
+                Ok(value) => Into::<String>::into(#normalize(value)),
+                Err(_) => {
+                    return Err(format!("Unrecognized enum variant: '{}'",
+                        String::from_utf8_lossy(bytes)).into())
+                }
+            };
+        }
+    });
+    let compared_bytes = match normalize {
+        Some(_) => quote! { normalized.as_bytes() },
+        None => quote! { bytes },
+    };
+    quote! {
+        fn db_values() -> &'static [&'static str; #n_variants] {
+            // `Mutex::new` is a `const fn` on every supported compiler, unlike `OnceLock`
+            static VALUES: std::sync::Mutex<Option<&'static [&'static str; #n_variants]>> =
+                std::sync::Mutex::new(None);
+            let mut values = VALUES
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            *values.get_or_insert_with(|| {
+                let values: [&'static str; #n_variants] = [#(#values,)*];
+                &*Box::leak(Box::new(values))
+            })
+        }
+
+        #inline
+        fn db_str_representation(e: &#enum_ty) -> &'static str {
+            match *e {
+                #(#variants_cfg #variants_rs => db_values()[#indices],)*
+            }
+        }
+
+        #inline
+        fn from_db_binary_representation(bytes: &[u8]) -> deserialize::Result<#enum_ty> {
+            #empty_fallback
+            #normalized_bytes
+            let values = db_values();
+            #(
+                #variants_cfg
+                if #compared_bytes == values[#indices].as_bytes() {
+                    return Ok(#variants_rs);
+                }
+            )*
+            Err(format!("Unrecognized enum variant: '{}'",
+                String::from_utf8_lossy(bytes)).into())
+        }
+    }
+}
+
+/// The hooks of `DbCharWidth`, padding the db values to the column width when writing,
+/// and trimming the padding when reading
+fn generate_char_width(
+    enum_ty: &Ident,
+    variants_cfg: &[proc_macro2::TokenStream],
+    variants_rs: &[proc_macro2::TokenStream],
+    variants_db: &[String],
+    width: usize,
+) -> proc_macro2::TokenStream {
+    let variants_padded = variants_db
+        .iter()
+        .map(|value| format!("{:<width$}", value, width = width));
+    quote! {
+        fn db_char_representation(e: &#enum_ty) -> &'static str {
+            match *e {
+                #(#variants_cfg #variants_rs => #variants_padded,)*
+            }
+        }
+
+        fn from_db_char_representation(bytes: &[u8]) -> deserialize::Result<#enum_ty> {
+            let len = bytes.iter().rposition(|b| *b != b' ').map_or(0, |i| i + 1);
+            from_db_binary_representation(&bytes[..len])
+        }
+    }
+}
+
+fn generate_int_representation(
+    enum_ty: &Ident,
+    variants_cfg: &[proc_macro2::TokenStream],
+    variants_rs: &[proc_macro2::TokenStream],
+    indices: &[i32],
+    inline: &Option<proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    // read as `i64`, so that both `Integer` and `BigInt` columns can be decoded
+    let wide_indices: Vec<i64> = indices.iter().copied().map(i64::from).collect();
+    quote! {
+        #inline
+        fn db_int_representation(e: &#enum_ty) -> i32 {
+            match *e {
+                #(#variants_cfg #variants_rs => #indices,)*
+            }
+        }
+
+        #inline
+        fn from_db_int_representation(value: i64) -> deserialize::Result<#enum_ty> {
+            match value {
+                #(#variants_cfg #wide_indices => Ok(#variants_rs),)*
+                v => Err(format!("Unrecognized enum variant index: {}", v).into()),
+            }
+        }
+    }
+}
+
+fn generate_all_variants(
+    enum_ty: &Ident,
+    variants_rs: &[proc_macro2::TokenStream],
+) -> proc_macro2::TokenStream {
+    let mysql_check = if cfg!(feature = "mysql") {
+        quote! {
+            std::any::TypeId::of::<DB>() == std::any::TypeId::of::<diesel::mysql::Mysql>()
+        }
+    } else {
+        quote! { false }
+    };
+    quote! {
+        impl #enum_ty {
+            /// All variants of this enum, in declaration order. Can be passed directly
+            /// to `eq_any` (any known value) or `ne_all` (no known value).
+            pub fn all() -> &'static [#enum_ty] {
+                const ALL: &[#enum_ty] = &[#(#variants_rs),*];
+                ALL
+            }
+
+            /// Whether `s` is the db value of this variant, e.g. for data from raw SQL
+            /// or other sources which carry the database spelling.
+            pub fn matches_db_str(&self, s: &str) -> bool {
+                db_str_representation(self) == s
+            }
+
+            /// The db value of this variant as a quoted SQL string literal, e.g. `'foo'`,
+            /// for embedding in DDL or `sql_query` strings.
+            pub fn to_sql_literal(&self) -> String {
+                quote_sql_literal(db_str_representation(self), false)
+            }
+
+            /// Like `to_sql_literal`, but also escapes backslashes for MySQL, which
+            /// treats them as escape characters in string literals by default.
+            pub fn to_sql_literal_for<DB: Backend + 'static>(&self) -> String {
+                quote_sql_literal(db_str_representation(self), #mysql_check)
+            }
+        }
+
+        fn quote_sql_literal(value: &str, escape_backslash: bool) -> String {
+            let mut out = String::with_capacity(value.len() + 2);
+            out.push('\'');
+            for c in value.chars() {
+                match c {
+                    '\'' => out.push_str("''"),
+                    '\\' if escape_backslash => out.push_str("\\\\"),
+                    c => out.push(c),
+                }
+            }
+            out.push('\'');
+            out
+        }
+    }
+}
+
+fn generate_labels(
+    enum_ty: &Ident,
+    variants: &[VariantOptions],
+    variants_cfg: &[proc_macro2::TokenStream],
+    variants_rs: &[proc_macro2::TokenStream],
+    variants_db: Option<&[String]>,
+) -> proc_macro2::TokenStream {
+    let labels: Vec<String> = variants
+        .iter()
+        .map(|variant| match &variant.label {
+            Some(label) => label.value(),
+            None => variant.ident.to_string(),
+        })
+        .collect();
+    // the null variant is written as `NULL`, so it has no db value to choose
+    let choices: Vec<proc_macro2::TokenStream> = variants
+        .iter()
+        .enumerate()
+        .filter(|(_, variant)| !variant.null)
+        .map(|(i, _)| {
+            let (cfgs, path, label) = (&variants_cfg[i], &variants_rs[i], &labels[i]);
+            match variants_db {
+                Some(variants_db) => {
+                    let value = &variants_db[i];
+                    quote! { #cfgs (#path, #value, #label) }
+                }
+                None => quote! { #cfgs (#path, db_str_representation(&#path), #label) },
+            }
+        })
+        .collect();
+    let choices = match variants_db {
+        Some(_) => quote! {
+            const CHOICES: &[(#enum_ty, &str, &str)] = &[#(#choices,)*];
+            CHOICES
+        },
+        None => quote! {
+            static CHOICES: std::sync::Mutex<
+                Option<&'static [(#enum_ty, &'static str, &'static str)]>,
+            > = std::sync::Mutex::new(None);
+            let mut choices = CHOICES
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            *choices.get_or_insert_with(|| {
+                let choices: Vec<(#enum_ty, &'static str, &'static str)> = vec![#(#choices,)*];
+                &*Box::leak(choices.into_boxed_slice())
+            })
+        },
+    };
+    quote! {
+        impl #enum_ty {
+            /// The human-readable label of this variant, set with `db_label` (or else
+            /// the variant name).
+            pub fn label(&self) -> &'static str {
+                match *self {
+                    #(#variants_cfg #variants_rs => #labels,)*
+                }
+            }
+
+            /// Every variant with its db value and its label, in declaration order,
+            /// e.g. to render the options of a select. A `db_null_variant` is left out,
+            /// as it is stored as `NULL`.
+            pub fn choices() -> &'static [(#enum_ty, &'static str, &'static str)] {
+                #choices
+            }
+        }
+    }
+}
+
+fn generate_i18n_keys(
+    enum_ty: &Ident,
+    variants_cfg: &[proc_macro2::TokenStream],
+    variants_rs: &[proc_macro2::TokenStream],
+    prefix: &str,
+    variants_db: &[String],
+) -> proc_macro2::TokenStream {
+    let keys = variants_db
+        .iter()
+        .map(|value| format!("{}.{}", prefix, value));
+    let doc = format!(
+        "The key of this variant in localization catalogs, `{}.` followed by the db value.",
+        prefix
+    );
+    quote! {
+        impl #enum_ty {
+            #[doc = #doc]
+            pub fn i18n_key(&self) -> &'static str {
+                match *self {
+                    #(#variants_cfg #variants_rs => #keys,)*
+                }
+            }
+        }
+    }
+}
+
+fn generate_markdown_table(
+    enum_ty: &Ident,
+    variants: &[VariantOptions],
+    variants_rs: &[proc_macro2::TokenStream],
+) -> proc_macro2::TokenStream {
+    let rows = variants.iter().zip(variants_rs).map(|(variant, path)| {
+        let cfgs = &variant.cfgs;
+        let name = variant.ident.to_string();
+        // one line per row, and `|` would end the cell
+        let doc = variant
+            .doc
+            .as_deref()
+            .unwrap_or_default()
+            .lines()
+            .map(str::trim)
+            .collect::<Vec<_>>()
+            .join(" ")
+            .replace('|', "\\|");
+        let value = if variant.null {
+            quote! { None }
+        } else {
+            quote! { Some(db_str_representation(&#path)) }
+        };
+        quote! {
+            #(#cfgs)*
+            push_markdown_row(&mut table, #name, #value, #doc);
+        }
+    });
+    quote! {
+        impl #enum_ty {
+            /// A Markdown table of the variants, their db values and their doc comments,
+            /// e.g. to embed in runbooks or schema docs.
+            pub fn markdown_table() -> String {
+                let mut table = String::from("| Variant | DB value | Description |\n| --- | --- | --- |\n");
+                #(#rows)*
+                table
+            }
+        }
+
+        fn push_markdown_row(table: &mut String, variant: &str, value: Option<&str>, doc: &str) {
+            let value = match value {
+                // a code span can't hold a backtick, unless it is delimited by two
+                Some(value) if value.contains('`') => format!("`` {} ``", value.replace('|', "\\|")),
+                Some(value) => format!("`{}`", value.replace('|', "\\|")),
+                None => "NULL".to_string(),
+            };
+            table.push_str(&format!("| {} | {} | {} |\n", variant, value, doc));
+        }
+    }
+}
+
+fn generate_mysql_column_type(
+    enum_ty: &Ident,
+    variants_db: &[&String],
+    storage: Storage,
+    char_width: Option<usize>,
+) -> proc_macro2::TokenStream {
+    let column_type = match storage {
+        Storage::Enum => {
+            let values: Vec<String> = variants_db
+                .iter()
+                .map(|value| format!("'{}'", value.replace('\\', "\\\\").replace('\'', "''")))
+                .collect();
+            format!("ENUM({})", values.join(", "))
+        }
+        Storage::Text => match char_width {
+            Some(width) => format!("CHAR({})", width),
+            // mysql counts the length of a `VARCHAR` in characters
+            None => {
+                let len = variants_db
+                    .iter()
+                    .map(|value| value.chars().count())
+                    .max()
+                    .unwrap_or_default()
+                    .max(1);
+                format!("VARCHAR({})", len)
+            }
+        },
+        Storage::Integer => "INT".to_string(),
+        Storage::BigInt => "BIGINT".to_string(),
+        Storage::UnsignedInteger => "INT UNSIGNED".to_string(),
+        Storage::UnsignedSmallInt => "SMALLINT UNSIGNED".to_string(),
+    };
+    let doc = format!(
+        "The MySQL column type for this enum, `{}`, for use in migrations.",
+        column_type
+    );
+    quote! {
+        impl #enum_ty {
+            #[doc = #doc]
+            pub const MYSQL_COLUMN_TYPE: &'static str = #column_type;
+        }
+    }
+}
+
+fn generate_pg_type_names(
+    enum_ty: &Ident,
+    pg_internal_type: &str,
+    pg_type_sql: &str,
+) -> proc_macro2::TokenStream {
+    let scalar_doc = format!(
+        "The name of the postgres type, quoted as needed for SQL, e.g. `CAST($1 AS {})`",
+        pg_type_sql
+    );
+    let array_name = format!("{}[]", pg_type_sql);
+    let array_doc = format!(
+        "The name of the postgres array type in SQL, e.g. `CAST($1 AS {})`",
+        array_name
+    );
+    // the attribute `diesel print-schema` puts on the type it generates for the enum
+    let diesel_attr = match split_pg_type_name(pg_internal_type) {
+        (Some(schema), name) => format!(
+            "#[diesel(postgres_type(name = {:?}, schema = {:?}))]",
+            name, schema
+        ),
+        (None, name) => format!("#[diesel(postgres_type(name = {:?}))]", name),
+    };
+    let patch_doc = format!(
+        "A `patch_file` for `diesel print-schema`, which replaces the type it generates for \
+         `{}` in `schema` (its output without the patch) with `pub use <mapping_path> as ...;`. \
+         `None` if `schema` has no such type.",
+        pg_internal_type
+    );
+    quote! {
+        impl #enum_ty {
+            #[doc = #scalar_doc]
+            pub const PG_TYPE_NAME: &'static str = #pg_type_sql;
+            #[doc = #array_doc]
+            pub const PG_ARRAY_TYPE_NAME: &'static str = #array_name;
+
+            #[doc = #patch_doc]
+            pub fn print_schema_patch(schema: &str, mapping_path: &str) -> Option<String> {
+                let lines: Vec<&str> = schema.lines().collect();
+                let attr = lines.iter().position(|line| line.trim() == #diesel_attr)?;
+                // the struct with its docs and attributes
+                let is_attr = |line: &&str| {
+                    let line = line.trim_start();
+                    line.starts_with("#[") || line.starts_with("///")
+                };
+                let start = lines[..attr]
+                    .iter()
+                    .rposition(|line| !is_attr(line))
+                    .map_or(0, |line| line + 1);
+                let struct_line = attr + lines[attr..].iter().position(|line| !is_attr(line))?;
+                let line = lines[struct_line];
+                let name = line
+                    .trim()
+                    .strip_prefix("pub struct ")?
+                    .strip_suffix(';')?
+                    .trim();
+                let indent = &line[..line.len() - line.trim_start().len()];
+                let end = struct_line + 1;
+                let before = start.saturating_sub(3);
+                let after = (end + 3).min(lines.len());
+                let mut patch = format!(
+                    "--- original\n+++ modified\n@@ -{},{} +{},{} @@\n",
+                    before + 1,
+                    after - before,
+                    before + 1,
+                    after - before - (end - start) + 1,
+                );
+                for line in &lines[before..start] {
+                    patch.push_str(&format!(" {}\n", line));
+                }
+                for line in &lines[start..end] {
+                    patch.push_str(&format!("-{}\n", line));
+                }
+                patch.push_str(&format!("+{}pub use {} as {};\n", indent, mapping_path, name));
+                for line in &lines[end..after] {
+                    patch.push_str(&format!(" {}\n", line));
+                }
+                Some(patch)
+            }
+        }
+    }
+}
+
+fn generate_pg_comment(
+    enum_ty: &Ident,
+    pg_type_sql: &str,
+    doc: Option<&str>,
+    variant_docs: &[(String, &str)],
+) -> proc_macro2::TokenStream {
+    let mut comment = doc.unwrap_or_default().to_string();
+    for (value, doc) in variant_docs {
+        if !comment.is_empty() {
+            comment.push('\n');
+        }
+        // join the lines of multi-line variant docs, to keep one line per variant
+        let doc = doc.lines().map(str::trim).collect::<Vec<_>>().join(" ");
+        comment.push_str(&format!("- {}: {}", value, doc));
+    }
+    let comment = if comment.is_empty() {
+        "NULL".to_string()
+    } else {
+        format!("'{}'", comment.replace('\'', "''"))
+    };
+    let sql = format!("COMMENT ON TYPE {} IS {};", pg_type_sql, comment);
+    quote! {
+        impl #enum_ty {
+            /// SQL which attaches the doc comments of this enum and its variants to the
+            /// postgres type, e.g. for a migration.
+            pub const PG_COMMENT_SQL: &'static str = #sql;
+        }
+    }
+}
+
+fn generate_pg_sync_variants(
+    enum_ty: &Ident,
+    pg_type_sql: &str,
+    variants_rs: &[&proc_macro2::TokenStream],
+    cockroach: bool,
+) -> proc_macro2::TokenStream {
+    let enum_range = format!("enum_range(NULL::{})::text[]", pg_type_sql);
+    // cockroachdb runs each schema change as a separate job, which may have been applied
+    // even if a later one failed, so the statements must be safe to run again
+    let alter_type = if cockroach {
+        format!("ALTER TYPE {} ADD VALUE IF NOT EXISTS ", pg_type_sql)
+    } else {
+        format!("ALTER TYPE {} ADD VALUE ", pg_type_sql)
+    };
+    // new values can't be used in the transaction which adds them on cockroachdb,
+    // so refuse to add them inside one
+    let check_transaction = cockroach.then(|| {
+        quote! {
+            use diesel::connection::{Connection, TransactionManager};
+            type Manager = <diesel::pg::PgConnection as Connection>::TransactionManager;
+            let depth = Manager::transaction_manager_status_mut(conn).transaction_depth()?;
+            if !dry_run && depth.is_some() {
+                return Err(diesel::result::Error::QueryBuilderError(
+                    "cockroachdb does not support adding enum values inside a transaction".into(),
+                ));
+            }
+        }
+    });
+    quote! {
+        impl #enum_ty {
+            /// Add the variants which are missing from the postgres enum type, with
+            /// `ALTER TYPE ... ADD VALUE`, keeping the declaration order. Returns the
+            /// statements, which are only returned (not run) with `dry_run`.
+            pub fn sync_variants(
+                conn: &mut diesel::pg::PgConnection,
+                dry_run: bool,
+            ) -> diesel::QueryResult<Vec<String>> {
+                use diesel::connection::SimpleConnection;
+                use diesel::RunQueryDsl;
+                #check_transaction
+                let db_values: Vec<String> =
+                    diesel::select(diesel::dsl::sql::<Array<Text>>(#enum_range)).get_result(conn)?;
+                let statements = Self::missing_variants_sql(&db_values);
+                if !dry_run {
+                    for statement in &statements {
+                        conn.batch_execute(statement)?;
+                    }
+                }
+                Ok(statements)
+            }
+
+            /// The statements run by `sync_variants`, given the current values of the
+            /// postgres enum type in order.
+            pub fn missing_variants_sql(db_values: &[String]) -> Vec<String> {
+                let mut statements = Vec::new();
+                let mut previous: Option<&str> = None;
+                for variant in [#(#variants_rs),*].iter() {
+                    let value = db_str_representation(variant);
+                    if !db_values.iter().any(|db_value| db_value == value) {
+                        let position = match (previous, db_values.first()) {
+                            (Some(previous), _) => {
+                                format!(" AFTER {}", quote_sql_literal(previous, false))
+                            }
+                            (None, Some(first)) => {
+                                format!(" BEFORE {}", quote_sql_literal(first, false))
+                            }
+                            (None, None) => String::new(),
+                        };
+                        statements.push(format!(
+                            "{}{}{};",
+                            #alter_type,
+                            quote_sql_literal(value, false),
+                            position
+                        ));
+                    }
+                    previous = Some(value);
+                }
+                statements
+            }
+        }
+    }
+}
+
+fn generate_pg_sort_order(enum_ty: &Ident, pg_type_sql: &str) -> proc_macro2::TokenStream {
+    let sort_orders = format!(
+        "ARRAY(SELECT enumlabel::text FROM pg_enum \
+         WHERE enumtypid = pg_typeof(NULL::{ty})::oid ORDER BY enumsortorder), \
+         ARRAY(SELECT enumsortorder FROM pg_enum \
+         WHERE enumtypid = pg_typeof(NULL::{ty})::oid ORDER BY enumsortorder)",
+        ty = pg_type_sql
+    );
+    quote! {
+        impl #enum_ty {
+            /// The `enumsortorder` of each label of the postgres enum type, as stored in
+            /// `pg_enum`, in that order. This is the order postgres compares values in,
+            /// which need not match the declaration order (e.g. after `ADD VALUE ... BEFORE`).
+            /// Fails if the type has a label which is not a variant.
+            pub fn pg_sort_order(
+                conn: &mut diesel::pg::PgConnection,
+            ) -> diesel::QueryResult<Vec<(Self, f32)>> {
+                use diesel::RunQueryDsl;
+                let (labels, sort_orders): (Vec<String>, Vec<f32>) =
+                    diesel::select(diesel::dsl::sql::<(Array<Text>, Array<Float4>)>(#sort_orders))
+                        .get_result(conn)?;
+                labels
+                    .iter()
+                    .zip(sort_orders)
+                    .map(|(label, sort_order)| {
+                        from_db_binary_representation(label.as_bytes())
+                            .map(|variant| (variant, sort_order))
+                            .map_err(diesel::result::Error::DeserializationError)
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+fn generate_pg_remove_values(
+    enum_ty: &Ident,
+    pg_internal_type: &str,
+    variants_rs: &[&proc_macro2::TokenStream],
+) -> proc_macro2::TokenStream {
+    let pg_type_sql = quote_pg_type_name(pg_internal_type);
+    // `RENAME TO` takes a bare name, the renamed type stays in the same schema
+    let (schema, name) = split_pg_type_name(pg_internal_type);
+    let old_name = format!("{}_old", name);
+    let old_type_sql = match schema {
+        Some(schema) => quote_pg_type_name(&format!("{}.{}", schema, old_name)),
+        None => quote_pg_ident(&old_name),
+    };
+    let rename_type = format!(
+        "ALTER TYPE {} RENAME TO {};\n",
+        pg_type_sql,
+        quote_pg_ident(&old_name)
+    );
+    let create_type = format!("CREATE TYPE {} AS ENUM ", pg_type_sql);
+    let drop_type = format!("DROP TYPE {};\n", old_type_sql);
+    quote! {
+        impl #enum_ty {
+            /// Statements which remove the labels of `previous` (the db values of the
+            /// variants before) which are no longer variants, as postgres can't drop labels
+            /// in place, or nothing if no label was removed. Each of the `(schema, table,
+            /// column)`s of `columns` (of the type) and `array_columns` (of its array type)
+            /// is first checked to hold no removed value. Then the type is renamed to
+            /// `<type>_old`, created again with the current variants, each column is
+            /// converted to it, and the old type is dropped. The `(schema, table, column,
+            /// default)`s of `defaults` are dropped around the conversion and set again.
+            /// Views using the columns must be dropped first and created again after.
+            /// See `pg_remove_values_migration` to find the columns and defaults.
+            pub fn pg_remove_values_sql(
+                previous: &[&str],
+                columns: &[(&str, &str, &str)],
+                array_columns: &[(&str, &str, &str)],
+                defaults: &[(&str, &str, &str, &str)],
+            ) -> String {
+                fn quote_ident(ident: &str) -> String {
+                    format!("\"{}\"", ident.replace('"', "\"\""))
+                }
+                let current: Vec<&str> = [#(#variants_rs),*]
+                    .iter()
+                    .map(db_str_representation)
+                    .collect();
+                let removed: Vec<String> = previous
+                    .iter()
+                    .filter(|label| !current.contains(label))
+                    .map(|label| quote_sql_literal(label, false))
+                    .collect();
+                if removed.is_empty() {
+                    return String::new();
+                }
+                let removed = removed.join(", ");
+                let all_columns: Vec<(bool, &(&str, &str, &str))> = columns
+                    .iter()
+                    .map(|column| (false, column))
+                    .chain(array_columns.iter().map(|column| (true, column)))
+                    .collect();
+                let mut sql = String::new();
+                for (array, (schema, table, column)) in &all_columns {
+                    let holds_removed = if *array {
+                        format!("{}::text[] && ARRAY[{}]::text[]", quote_ident(column), removed)
+                    } else {
+                        format!("{}::text IN ({})", quote_ident(column), removed)
+                    };
+                    let message = quote_sql_literal(
+                        &format!("{}.{}.{} holds removed values", schema, table, column),
+                        false,
+                    );
+                    sql.push_str(&format!(
+                        "DO $guard$ BEGIN IF EXISTS (SELECT 1 FROM {}.{} WHERE {}) \
+                         THEN RAISE EXCEPTION '%', {}; END IF; END $guard$;\n",
+                        quote_ident(schema),
+                        quote_ident(table),
+                        holds_removed,
+                        message,
+                    ));
+                }
+                sql.push_str(#rename_type);
+                let values: Vec<String> = current
+                    .iter()
+                    .map(|value| quote_sql_literal(value, false))
+                    .collect();
+                sql.push_str(&format!("{}({});\n", #create_type, values.join(", ")));
+                for (array, (schema, table, column)) in &all_columns {
+                    let alter = format!(
+                        "ALTER TABLE {}.{} ALTER COLUMN {}",
+                        quote_ident(schema),
+                        quote_ident(table),
+                        quote_ident(column),
+                    );
+                    let default = defaults
+                        .iter()
+                        .find(|default| {
+                            (default.0, default.1, default.2) == (*schema, *table, *column)
+                        })
+                        .map(|default| default.3);
+                    if default.is_some() {
+                        sql.push_str(&format!("{} DROP DEFAULT;\n", alter));
+                    }
+                    let suffix = if *array { "[]" } else { "" };
+                    sql.push_str(&format!(
+                        "{alter} TYPE {ty}{suffix} USING {column}::text{suffix}::{ty}{suffix};\n",
+                        alter = alter,
+                        ty = #pg_type_sql,
+                        suffix = suffix,
+                        column = quote_ident(column),
+                    ));
+                    if let Some(default) = default {
+                        sql.push_str(&format!("{} SET DEFAULT {};\n", alter, default));
+                    }
+                }
+                sql.push_str(#drop_type);
+                sql
+            }
+        }
+    }
+}
+
+fn generate_pg_remove_values_migration(
+    enum_ty: &Ident,
+    pg_type_sql: &str,
+) -> proc_macro2::TokenStream {
+    // columns of tables, leaving out those inherited by partitions and child tables, which
+    // are converted with their parent
+    let from_where = format!(
+        "FROM pg_attribute a \
+         JOIN pg_class c ON c.oid = a.attrelid \
+         JOIN pg_namespace n ON n.oid = c.relnamespace \
+         LEFT JOIN pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum \
+         WHERE a.atttypid IN (pg_typeof(NULL::{ty})::oid, pg_typeof(NULL::{ty}[])::oid) \
+         AND a.attnum > 0 AND NOT a.attisdropped AND a.attinhcount = 0 \
+         AND c.relkind IN ('r', 'p') \
+         ORDER BY n.nspname, c.relname, a.attnum",
+        ty = pg_type_sql
+    );
+    let columns = format!(
+        "ARRAY(SELECT n.nspname::text {from_where}), \
+         ARRAY(SELECT c.relname::text {from_where}), \
+         ARRAY(SELECT a.attname::text {from_where}), \
+         ARRAY(SELECT a.atttypid = pg_typeof(NULL::{ty}[])::oid {from_where}), \
+         ARRAY(SELECT pg_get_expr(d.adbin, d.adrelid) {from_where})",
+        from_where = from_where,
+        ty = pg_type_sql
+    );
+    quote! {
+        impl #enum_ty {
+            /// `pg_remove_values_sql` for every column of the postgres enum type or of its
+            /// array type in the tables of the database, with their defaults.
+            pub fn pg_remove_values_migration(
+                conn: &mut diesel::pg::PgConnection,
+                previous: &[&str],
+            ) -> diesel::QueryResult<String> {
+                use diesel::RunQueryDsl;
+                #[allow(clippy::type_complexity)]
+                let (schemas, tables, columns, arrays, defaults): (
+                    Vec<String>,
+                    Vec<String>,
+                    Vec<String>,
+                    Vec<bool>,
+                    Vec<Option<String>>,
+                ) = diesel::select(diesel::dsl::sql::<(
+                    Array<Text>,
+                    Array<Text>,
+                    Array<Text>,
+                    Array<Bool>,
+                    Array<Nullable<Text>>,
+                )>(#columns))
+                .get_result(conn)?;
+                let mut scalar_columns = Vec::new();
+                let mut array_columns = Vec::new();
+                let mut column_defaults = Vec::new();
+                for i in 0..schemas.len() {
+                    let column = (schemas[i].as_str(), tables[i].as_str(), columns[i].as_str());
+                    if arrays[i] {
+                        array_columns.push(column);
+                    } else {
+                        scalar_columns.push(column);
+                    }
+                    if let Some(default) = &defaults[i] {
+                        column_defaults.push((column.0, column.1, column.2, default.as_str()));
+                    }
+                }
+                Ok(Self::pg_remove_values_sql(
+                    previous,
+                    &scalar_columns,
+                    &array_columns,
+                    &column_defaults,
+                ))
+            }
+        }
+    }
+}
+
+fn generate_pg_find_columns(enum_ty: &Ident, pg_type_sql: &str) -> proc_macro2::TokenStream {
+    // tables, views, materialized views and foreign tables
+    let from_where = format!(
+        "FROM pg_attribute a \
+         JOIN pg_class c ON c.oid = a.attrelid \
+         JOIN pg_namespace n ON n.oid = c.relnamespace \
+         WHERE a.atttypid IN (pg_typeof(NULL::{ty})::oid, pg_typeof(NULL::{ty}[])::oid) \
+         AND a.attnum > 0 AND NOT a.attisdropped \
+         AND c.relkind IN ('r', 'p', 'v', 'm', 'f') \
+         ORDER BY n.nspname, c.relname, a.attnum",
+        ty = pg_type_sql
+    );
+    let columns = format!(
+        "ARRAY(SELECT n.nspname::text {from_where}), \
+         ARRAY(SELECT c.relname::text {from_where}), \
+         ARRAY(SELECT a.attname::text {from_where})",
+        from_where = from_where
+    );
+    quote! {
+        impl #enum_ty {
+            /// The `(schema, table, column)` of every column of the postgres enum type or
+            /// of its array type, in tables and views, e.g. to review before renaming or
+            /// removing values (see `pg_remove_values_sql`).
+            pub fn find_columns_using_type(
+                conn: &mut diesel::pg::PgConnection,
+            ) -> diesel::QueryResult<Vec<(String, String, String)>> {
+                use diesel::RunQueryDsl;
+                let (schemas, tables, columns): (Vec<String>, Vec<String>, Vec<String>) =
+                    diesel::select(diesel::dsl::sql::<(Array<Text>, Array<Text>, Array<Text>)>(
+                        #columns,
+                    ))
+                    .get_result(conn)?;
+                Ok(schemas
+                    .into_iter()
+                    .zip(tables)
+                    .zip(columns)
+                    .map(|((schema, table), column)| (schema, table, column))
+                    .collect())
+            }
+        }
+    }
+}
+
+fn generate_sqlite_validation(
+    enum_ty: &Ident,
+    variants_rs: &[&proc_macro2::TokenStream],
+    storage: Storage,
+) -> proc_macro2::TokenStream {
+    let value_sql = if storage.is_integer() {
+        quote! { db_int_representation(variant).to_string() }
+    } else {
+        quote! { quote_sql_literal(db_str_representation(variant), false) }
+    };
+    quote! {
+        impl #enum_ty {
+            /// Enforce the allowed values of `table.column` in the sqlite database itself,
+            /// as sqlite has no enum type: creates `BEFORE INSERT` and `BEFORE UPDATE`
+            /// triggers which abort on any other (non-`NULL`) value. The triggers are named
+            /// `<table>_<column>_insert_check` and `<table>_<column>_update_check`, and are
+            /// replaced if they exist, so this can be run again after adding variants.
+            pub fn install_validation(
+                conn: &mut diesel::sqlite::SqliteConnection,
+                table: &str,
+                column: &str,
+            ) -> diesel::QueryResult<()> {
+                use diesel::connection::SimpleConnection;
+                conn.batch_execute(&Self::validation_sql(table, column))
+            }
+
+            /// The statements run by `install_validation`.
+            pub fn validation_sql(table: &str, column: &str) -> String {
+                fn quote_ident(ident: &str) -> String {
+                    format!("\"{}\"", ident.replace('"', "\"\""))
+                }
+                let values: Vec<String> = [#(#variants_rs),*]
+                    .iter()
+                    .map(|variant| #value_sql)
+                    .collect();
+                let message = quote_sql_literal(
+                    &format!("invalid value for {}.{}", table, column),
+                    false,
+                );
+                let mut sql = String::new();
+                let events = [
+                    ("insert_check", "INSERT".to_string()),
+                    ("update_check", format!("UPDATE OF {}", quote_ident(column))),
+                ];
+                for (suffix, event) in &events {
+                    let trigger = quote_ident(&format!("{}_{}_{}", table, column, suffix));
+                    sql.push_str(&format!(
+                        "DROP TRIGGER IF EXISTS {trigger};\n\
+                         CREATE TRIGGER {trigger} BEFORE {event} ON {table}\n\
+                         WHEN NEW.{column} NOT IN ({values})\n\
+                         BEGIN SELECT RAISE(ABORT, {message}); END;\n",
+                        trigger = trigger,
+                        event = event,
+                        table = quote_ident(table),
+                        column = quote_ident(column),
+                        values = values.join(", "),
+                        message = message,
+                    ));
+                }
+                sql
+            }
+        }
+    }
+}
+
+fn generate_sqlite_check_constraint(
+    enum_ty: &Ident,
+    variants_rs: &[&proc_macro2::TokenStream],
+    storage: Storage,
+) -> proc_macro2::TokenStream {
+    let value = if storage.is_integer() {
+        quote! { db_int_representation(variant).to_string() }
+    } else {
+        quote! { db_str_representation(variant).to_string() }
+    };
+    quote! {
+        impl #enum_ty {
+            /// Check that the `CHECK (column IN (...))` constraint on `table.column` in the
+            /// sqlite database allows exactly the db values of the variants, e.g. at startup,
+            /// to catch a schema which drifted from the enum.
+            pub fn assert_check_matches_db(
+                conn: &mut diesel::sqlite::SqliteConnection,
+                table: &str,
+                column: &str,
+            ) -> Result<(), String> {
+                use diesel::RunQueryDsl;
+                let table_sql: Option<String> = diesel::select(
+                    diesel::dsl::sql::<Nullable<Text>>(
+                        "(SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ",
+                    )
+                    .bind::<Text, _>(table)
+                    .sql(")"),
+                )
+                .get_result(conn)
+                .map_err(|e| e.to_string())?;
+                let table_sql = table_sql.ok_or_else(|| format!("no table named `{}`", table))?;
+                Self::check_constraint_matches(&table_sql, column)
+            }
+
+            /// The check done by `assert_check_matches_db`, given the `CREATE TABLE`
+            /// statement of the table (as stored in `sqlite_master`).
+            pub fn check_constraint_matches(table_sql: &str, column: &str) -> Result<(), String> {
+                let mut db_values = sqlite_check_values(table_sql, column).ok_or_else(|| {
+                    format!("no `CHECK ({} IN (...))` constraint in the table", column)
+                })?;
+                let mut rust_values: Vec<String> = [#(#variants_rs),*]
+                    .iter()
+                    .map(|variant| #value)
+                    .collect();
+                db_values.sort();
+                rust_values.sort();
+                if db_values == rust_values {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "CHECK constraint mismatch for {}: database allows {:?}, rust has {:?}",
+                        column, db_values, rust_values
+                    ))
+                }
+            }
+        }
+
+        /// The values listed by the first `CHECK (column IN (...))` constraint in a
+        /// `CREATE TABLE` statement, if there is one
+        fn sqlite_check_values(table_sql: &str, column: &str) -> Option<Vec<String>> {
+            enum Token {
+                Word(String),
+                QuotedName(String),
+                Value(String),
+                Punct(char),
+            }
+            let mut tokens = Vec::new();
+            let mut chars = table_sql.chars().peekable();
+            while let Some(c) = chars.next() {
+                match c {
+                    '-' if chars.peek() == Some(&'-') => {
+                        while chars.next().map_or(false, |c| c != '\n') {}
+                    }
+                    '/' if chars.peek() == Some(&'*') => {
+                        chars.next();
+                        let mut previous = ' ';
+                        while let Some(c) = chars.next() {
+                            if previous == '*' && c == '/' {
+                                break;
+                            }
+                            previous = c;
+                        }
+                    }
+                    '\'' | '"' | '`' | '[' => {
+                        let close = if c == '[' { ']' } else { c };
+                        let mut text = String::new();
+                        while let Some(next) = chars.next() {
+                            if next == close {
+                                // inside quotes, a doubled quote stands for itself
+                                if close != ']' && chars.peek() == Some(&close) {
+                                    chars.next();
+                                } else {
+                                    break;
+                                }
+                            }
+                            text.push(next);
+                        }
+                        tokens.push(if c == '\'' {
+                            Token::Value(text)
+                        } else {
+                            Token::QuotedName(text)
+                        });
+                    }
+                    c if c.is_ascii_digit()
+                        || (c == '-' && chars.peek().map_or(false, char::is_ascii_digit)) =>
+                    {
+                        let mut number = c.to_string();
+                        while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                            number.push(digit);
+                        }
+                        tokens.push(Token::Value(number));
+                    }
+                    c if c.is_alphanumeric() || c == '_' => {
+                        let mut word = c.to_string();
+                        while let Some(next) = chars.next_if(|c| c.is_alphanumeric() || *c == '_') {
+                            word.push(next);
+                        }
+                        tokens.push(Token::Word(word));
+                    }
+                    c if c.is_whitespace() => {}
+                    c => tokens.push(Token::Punct(c)),
+                }
+            }
+
+            // sqlite compares keywords and names case-insensitively
+            let is_word = |token: Option<&Token>, word: &str| match token {
+                Some(Token::Word(w)) => w.eq_ignore_ascii_case(word),
+                _ => false,
+            };
+            let is_column = |token: &Token| match token {
+                Token::Word(name) | Token::QuotedName(name) => name.eq_ignore_ascii_case(column),
+                _ => false,
+            };
+            let mut in_check = false;
+            for (i, token) in tokens.iter().enumerate() {
+                in_check |= is_word(Some(token), "check");
+                if !in_check
+                    || !is_column(token)
+                    || !is_word(tokens.get(i + 1), "in")
+                    || (i > 0 && is_word(tokens.get(i - 1), "not"))
+                {
+                    continue;
+                }
+                if let Some(Token::Punct('(')) = tokens.get(i + 2) {
+                    let mut values = Vec::new();
+                    for (k, token) in tokens[i + 3..].iter().enumerate() {
+                        match (k % 2, token) {
+                            (0, Token::Value(value)) => values.push(value.clone()),
+                            (1, Token::Punct(',')) => {}
+                            (1, Token::Punct(')')) => return Some(values),
+                            _ => break,
+                        }
+                    }
+                }
+            }
+            None
+        }
+    }
+}
+
+fn generate_pg_migration(
+    enum_ty: &Ident,
+    pg_internal_type: &str,
+    variants_rs: &[&proc_macro2::TokenStream],
+    cockroach: bool,
+) -> proc_macro2::TokenStream {
+    let pg_type_sql = quote_pg_type_name(pg_internal_type);
+    let create_type = format!("CREATE TYPE {} AS ENUM ", pg_type_sql);
+    // cockroachdb recommends running schema changes outside of explicit transactions
+    let metadata = if cockroach {
+        quote! {
+            impl diesel::migration::MigrationMetadata for CreateTypeMigration {
+                fn run_in_transaction(&self) -> bool {
+                    false
+                }
+            }
+        }
+    } else {
+        quote! {
+            impl diesel::migration::MigrationMetadata for CreateTypeMigration {}
+        }
+    };
+    let drop_type = format!("DROP TYPE {};", pg_type_sql);
+    let name_suffix = format!("_create_{}", pg_internal_type);
+    quote! {
+        impl #enum_ty {
+            /// A diesel `Migration` which creates the postgres enum type, and drops it
+            /// when reverted, for harnesses which assemble their migrations in code.
+            /// `version` orders it among the other migrations, e.g. `"2024-01-01-000000"`.
+            pub fn create_type_migration(
+                version: &str,
+            ) -> Box<dyn diesel::migration::Migration<diesel::pg::Pg>> {
+                Box::new(CreateTypeMigration {
+                    version: version.to_string(),
+                })
+            }
+        }
+
+        struct CreateTypeMigration {
+            version: String,
+        }
+
+        impl std::fmt::Display for CreateTypeMigration {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}{}", self.version, #name_suffix)
+            }
+        }
+
+        impl diesel::migration::MigrationName for CreateTypeMigration {
+            fn version(&self) -> diesel::migration::MigrationVersion<'_> {
+                self.version.as_str().into()
+            }
+        }
+
+        #metadata
+
+        impl diesel::migration::Migration<diesel::pg::Pg> for CreateTypeMigration {
+            fn run(
+                &self,
+                conn: &mut dyn diesel::connection::BoxableConnection<diesel::pg::Pg>,
+            ) -> diesel::migration::Result<()> {
+                let values: Vec<String> = [#(#variants_rs),*]
+                    .iter()
+                    .map(|variant| quote_sql_literal(db_str_representation(variant), false))
+                    .collect();
+                conn.batch_execute(&format!("{}({});", #create_type, values.join(", ")))?;
+                Ok(())
+            }
+
+            fn revert(
+                &self,
+                conn: &mut dyn diesel::connection::BoxableConnection<diesel::pg::Pg>,
+            ) -> diesel::migration::Result<()> {
+                conn.batch_execute(#drop_type)?;
+                Ok(())
+            }
+
+            fn metadata(&self) -> &dyn diesel::migration::MigrationMetadata {
+                self
+            }
+
+            fn name(&self) -> &dyn diesel::migration::MigrationName {
+                self
+            }
+        }
+    }
+}
+
+fn generate_pg_refinery_migration(
+    enum_ty: &Ident,
+    pg_internal_type: &str,
+    variants_rs: &[&proc_macro2::TokenStream],
+) -> proc_macro2::TokenStream {
+    let create_type = format!(
+        "CREATE TYPE {} AS ENUM ",
+        quote_pg_type_name(pg_internal_type)
+    );
+    // refinery takes the name from the word characters following the version
+    let name_suffix: String = pg_internal_type
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    let name_suffix = format!("__create_{}", name_suffix);
+    quote! {
+        impl #enum_ty {
+            /// A refinery `Migration` which creates the postgres enum type, for harnesses
+            /// running refinery rather than diesel migrations. `version` orders it among
+            /// the other migrations. Refinery has no down migrations, so nothing drops it.
+            pub fn create_type_refinery_migration(
+                version: u32,
+            ) -> Result<::refinery::Migration, ::refinery::Error> {
+                let values: Vec<String> = [#(#variants_rs),*]
+                    .iter()
+                    .map(|variant| quote_sql_literal(db_str_representation(variant), false))
+                    .collect();
+                ::refinery::Migration::unapplied(
+                    &format!("V{}{}", version, #name_suffix),
+                    &format!("{}({});", #create_type, values.join(", ")),
+                )
+            }
+        }
+    }
+}
+
+fn generate_pg_rename_values(
+    enum_ty: &Ident,
+    pg_type_sql: &str,
+    renames: &[(String, &String)],
+) -> proc_macro2::TokenStream {
+    let statements: Vec<String> = renames
+        .iter()
+        .map(|(old, new)| {
+            format!(
+                "ALTER TYPE {} RENAME VALUE '{}' TO '{}';",
+                pg_type_sql,
+                old.replace('\'', "''"),
+                new.replace('\'', "''")
+            )
+        })
+        .collect();
+    quote! {
+        impl #enum_ty {
+            /// `ALTER TYPE ... RENAME VALUE` statements for the variants marked with
+            /// `db_renamed_from`, to rename the labels in place (postgres 10 or later).
+            pub const PG_RENAME_VALUES_SQL: &'static [&'static str] = &[#(#statements),*];
+        }
+    }
+}
+
+fn generate_serde_with(serde_mod: &Ident, enum_ty: &Ident) -> proc_macro2::TokenStream {
+    let mod_doc = format!(
+        "(De)serialize a `{}` as its db value, with `#[serde(with = \"{}\")]`",
+        enum_ty, serde_mod
+    );
+    quote! {
+        #[doc = #mod_doc]
+        #[allow(dead_code)]
+        pub mod #serde_mod {
+            use super::*;
+            use ::serde;
+
+            /// Serialize as the db value
+            pub fn serialize<S>(value: &#enum_ty, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serializer.serialize_str(db_str_representation(value))
+            }
+
+            /// Deserialize from the db value
+            pub fn deserialize<'de, D>(deserializer: D) -> Result<#enum_ty, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let value = <std::borrow::Cow<'de, str> as serde::Deserialize>::deserialize(
+                    deserializer,
+                )?;
+                from_db_binary_representation(value.as_bytes()).map_err(serde::de::Error::custom)
+            }
+        }
+    }
+}
+
+fn generate_clap_value_enum(
+    enum_ty: &Ident,
+    variants_rs: &[&proc_macro2::TokenStream],
+) -> proc_macro2::TokenStream {
+    quote! {
+        use ::clap;
+
+        impl clap::ValueEnum for #enum_ty {
+            fn value_variants<'a>() -> &'a [Self] {
+                &[#(#variants_rs),*]
+            }
+
+            fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
+                Some(clap::builder::PossibleValue::new(db_str_representation(self)))
+            }
+        }
+    }
+}
+
+fn generate_rocket(enum_ty: &Ident) -> proc_macro2::TokenStream {
+    quote! {
+        use ::rocket;
+
+        impl<'a> rocket::request::FromParam<'a> for #enum_ty {
+            type Error = &'a str;
+
+            fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+                from_db_binary_representation(param.as_bytes()).map_err(|_| param)
+            }
+        }
+
+        impl<'v> rocket::form::FromFormField<'v> for #enum_ty {
+            fn from_value(field: rocket::form::ValueField<'v>) -> rocket::form::Result<'v, Self> {
+                from_db_binary_representation(field.value.as_bytes()).map_err(|_| {
+                    rocket::form::Error::validation(format!("unknown value `{}`", field.value))
+                        .into()
+                })
+            }
+        }
+    }
+}
+
+fn generate_fake_dummy(
+    enum_ty: &Ident,
+    variants_rs: &[proc_macro2::TokenStream],
+) -> proc_macro2::TokenStream {
+    quote! {
+        use ::fake;
+
+        // a compile error rather than a panic in `gen_range` when every variant is cfg'd out
+        const _: () = {
+            let variants: &[#enum_ty] = &[#(#variants_rs),*];
+            assert!(!variants.is_empty(), "`DbEnumFake` needs at least one variant");
+        };
+
+        impl fake::Dummy<fake::Faker> for #enum_ty {
+            fn dummy_with_rng<R: fake::Rng + ?Sized>(_: &fake::Faker, rng: &mut R) -> Self {
+                let variants = [#(#variants_rs),*];
+                let index = fake::Rng::gen_range(rng, 0..variants.len());
+                // by value in every edition, unlike `variants.into_iter()` before 2021
+                std::iter::IntoIterator::into_iter(variants).nth(index).unwrap()
+            }
+        }
+    }
+}
+
+fn generate_arbitrary(
+    enum_ty: &Ident,
+    variants_rs: &[proc_macro2::TokenStream],
+) -> proc_macro2::TokenStream {
+    quote! {
+        use ::arbitrary;
+
+        impl<'a> arbitrary::Arbitrary<'a> for #enum_ty {
+            fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+                let variants = [#(#variants_rs),*];
+                // an error rather than a panic when every variant is cfg'd out
+                let index = u.choose_index(variants.len())?;
+                Ok(std::iter::IntoIterator::into_iter(variants).nth(index).unwrap())
+            }
+        }
+    }
+}
+
+fn generate_postgres_types(
+    enum_ty: &Ident,
+    variants_rs: &[&proc_macro2::TokenStream],
+    null_variant: &Option<proc_macro2::TokenStream>,
+    hooks: SqlHooks,
+) -> proc_macro2::TokenStream {
+    let (write_null, from_sql_null) = match null_variant {
+        Some(null_variant) => (
+            Some(quote! {
+                if let #null_variant = *self {
+                    return Ok(postgres_types::IsNull::Yes);
+                }
+            }),
+            Some(quote! {
+                fn from_sql_null(
+                    _ty: &postgres_types::Type,
+                ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+                    Ok(#null_variant)
+                }
+            }),
+        ),
+        None => (None, None),
+    };
+    // the same bytes as diesel and sqlx write and read
+    let db_bytes = |value: proc_macro2::TokenStream| match hooks.serialize_with {
+        Some(path) => quote! { AsRef::<[u8]>::as_ref(&#path(#value)) },
+        None => quote! { db_str_representation(#value).as_bytes() },
+    };
+    let (self_bytes, variant_bytes) = (db_bytes(quote! { self }), db_bytes(quote! { variant }));
+    let from_raw = hooks.read_bytes(quote! { raw });
+    quote! {
+        use ::postgres_types;
+
+        impl postgres_types::ToSql for #enum_ty {
+            fn to_sql(
+                &self,
+                _ty: &postgres_types::Type,
+                out: &mut postgres_types::private::BytesMut,
+            ) -> Result<postgres_types::IsNull, Box<dyn std::error::Error + Sync + Send>> {
+                #write_null
+                out.extend_from_slice(#self_bytes);
+                Ok(postgres_types::IsNull::No)
+            }
+
+            fn accepts(ty: &postgres_types::Type) -> bool {
+                match ty.kind() {
+                    postgres_types::Kind::Enum(labels) => [#(#variants_rs),*]
+                        .iter()
+                        .all(|variant| labels.iter().any(|label| label.as_bytes() == #variant_bytes)),
+                    _ => <&str as postgres_types::ToSql>::accepts(ty),
+                }
+            }
+
+            postgres_types::to_sql_checked!();
+        }
+
+        impl<'a> postgres_types::FromSql<'a> for #enum_ty {
+            fn from_sql(
+                _ty: &postgres_types::Type,
+                raw: &'a [u8],
+            ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+                #from_raw
+            }
+
+            #from_sql_null
+
+            fn accepts(ty: &postgres_types::Type) -> bool {
+                <#enum_ty as postgres_types::ToSql>::accepts(ty)
+            }
+        }
+    }
+}
+
+fn generate_sqlx(
+    enum_ty: &Ident,
+    null_variant: &Option<proc_macro2::TokenStream>,
+    pg_type: Option<(&str, &str)>,
+    hooks: SqlHooks,
+) -> proc_macro2::TokenStream {
+    let text_type = |db: proc_macro2::TokenStream| {
+        quote! {
+            impl sqlx::Type<#db> for #enum_ty {
+                fn type_info() -> <#db as sqlx::Database>::TypeInfo {
+                    <str as sqlx::Type<#db>>::type_info()
+                }
+
+                fn compatible(ty: &<#db as sqlx::Database>::TypeInfo) -> bool {
+                    <str as sqlx::Type<#db>>::compatible(ty)
+                }
+            }
+        }
+    };
+    let pg_type_impl = cfg!(feature = "postgres").then(|| match pg_type {
+        // the type name of a column is unquoted, unlike the one to look up
+        Some((pg_internal_type, pg_type_sql)) => quote! {
+            impl sqlx::Type<sqlx::Postgres> for #enum_ty {
+                fn type_info() -> sqlx::postgres::PgTypeInfo {
+                    sqlx::postgres::PgTypeInfo::with_name(#pg_type_sql)
+                }
+
+                fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
+                    *ty == Self::type_info()
+                        || sqlx::TypeInfo::name(ty) == #pg_internal_type
+                        || <str as sqlx::Type<sqlx::Postgres>>::compatible(ty)
+                }
+            }
+        },
+        None => text_type(quote! { sqlx::Postgres }),
+    });
+    let mysql_type_impl = cfg!(feature = "mysql").then(|| text_type(quote! { sqlx::MySql }));
+    let sqlite_type_impl = cfg!(feature = "sqlite").then(|| text_type(quote! { sqlx::Sqlite }));
+    let (write_null, read_null) = match null_variant {
+        Some(null_variant) => (
+            Some(quote! {
+                if let #null_variant = *self {
+                    return sqlx::encode::IsNull::Yes;
+                }
+            }),
+            Some(quote! {
+                if sqlx::ValueRef::is_null(&value) {
+                    return Ok(#null_variant);
+                }
+            }),
+        ),
+        None => (None, None),
+    };
+    let value = match hooks.serialize_with {
+        Some(path) => quote! { #path(self) },
+        None => quote! { db_str_representation(self) },
+    };
+    let from_bytes = hooks.read_bytes(quote! { value.as_bytes() });
+    quote! {
+        use ::sqlx;
+
+        #pg_type_impl
+        #mysql_type_impl
+        #sqlite_type_impl
+
+        impl<'q, DB: sqlx::Database> sqlx::Encode<'q, DB> for #enum_ty
+        where
+            &'q str: sqlx::Encode<'q, DB>,
+        {
+            fn encode_by_ref(
+                &self,
+                buf: &mut <DB as sqlx::database::HasArguments<'q>>::ArgumentBuffer,
+            ) -> sqlx::encode::IsNull {
+                #write_null
+                <&'q str as sqlx::Encode<'q, DB>>::encode(#value, buf)
+            }
+        }
+
+        impl<'r, DB: sqlx::Database> sqlx::Decode<'r, DB> for #enum_ty
+        where
+            &'r str: sqlx::Decode<'r, DB>,
+        {
+            fn decode(
+                value: <DB as sqlx::database::HasValueRef<'r>>::ValueRef,
+            ) -> Result<Self, sqlx::error::BoxDynError> {
+                #read_null
+                let value = <&'r str as sqlx::Decode<'r, DB>>::decode(value)?;
+                #from_bytes
+            }
+        }
+    }
+}
+
+fn generate_index(
+    enum_ty: &Ident,
+    variant_cfgs: &[proc_macro2::TokenStream],
+    variant_paths: &[proc_macro2::TokenStream],
+) -> proc_macro2::TokenStream {
+    let indexes: Vec<usize> = (0..variant_paths.len()).collect();
+    quote! {
+        impl #enum_ty {
+            /// The position of this variant in declaration order, starting at 0.
+            pub fn index(&self) -> usize {
+                match *self {
+                    #(#variant_cfgs #variant_paths => #indexes,)*
+                }
+            }
+        }
+
+        impl TryFrom<usize> for #enum_ty {
+            type Error = usize;
+
+            fn try_from(index: usize) -> Result<Self, usize> {
+                match index {
+                    #(#variant_cfgs #indexes => Ok(#variant_paths),)*
+                    _ => Err(index),
+                }
+            }
+        }
+    }
+}
+
+fn generate_str_eq(enum_ty: &Ident) -> proc_macro2::TokenStream {
+    quote! {
+        impl PartialEq<str> for #enum_ty {
+            fn eq(&self, other: &str) -> bool {
+                self.matches_db_str(other)
+            }
+        }
+
+        impl<'a> PartialEq<&'a str> for #enum_ty {
+            fn eq(&self, other: &&'a str) -> bool {
+                self.matches_db_str(other)
+            }
+        }
+
+        impl PartialEq<#enum_ty> for str {
+            fn eq(&self, other: &#enum_ty) -> bool {
+                other.matches_db_str(self)
+            }
+        }
+
+        impl<'a> PartialEq<#enum_ty> for &'a str {
+            fn eq(&self, other: &#enum_ty) -> bool {
+                other.matches_db_str(self)
+            }
+        }
+    }
+}
+
+fn generate_ord(
+    enum_ty: &Ident,
+    variants_rs: &[proc_macro2::TokenStream],
+    variants_null: &[bool],
+    pg_enum: bool,
+    mysql_enum: bool,
+) -> proc_macro2::TokenStream {
+    let ranks: Vec<usize> = (0..variants_rs.len()).collect();
+    let variants_db: Vec<&proc_macro2::TokenStream> = variants_rs
+        .iter()
+        .zip(variants_null)
+        .filter(|(_, is_null)| !**is_null)
+        .map(|(variant, _)| variant)
+        .collect();
+    let compare_db_order = (pg_enum || mysql_enum).then(|| {
+        quote! {
+            fn compare_db_order(db_order: &[String]) -> Result<(), String> {
+                let rust_order: Vec<&str> = [#(#variants_db),*]
+                    .iter()
+                    .map(db_str_representation)
+                    .collect();
+                if db_order == rust_order.as_slice() {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "Enum order mismatch: database has {:?}, rust has {:?}",
+                        db_order, rust_order
+                    ))
+                }
+            }
+        }
+    });
+    // the queries are shared with the drift report, which every postgres and mysql enum has
+    let pg_check = if pg_enum {
+        Some(quote! {
+            impl #enum_ty {
+                /// Check that the variants of the postgres enum type are declared in
+                /// the same order as in rust, so that `ORDER BY` and `Ord` agree.
+                pub fn assert_order_matches_db(
+                    conn: &mut diesel::pg::PgConnection,
+                ) -> Result<(), String> {
+                    use diesel::RunQueryDsl;
+                    let db_order: Vec<String> = Self::db_order_query()
+                        .get_result(conn)
+                        .map_err(|e| e.to_string())?;
+                    Self::check_db_order(&db_order)
+                }
+
+                /// The query used by `assert_order_matches_db`, returning the values of
+                /// the postgres enum type in order. Run it on any connection (e.g. with
+                /// `diesel-async`) and pass the result to `check_db_order`.
+                pub fn db_order_query(
+                ) -> diesel::dsl::BareSelect<diesel::expression::SqlLiteral<Array<Text>>> {
+                    pg_enum_values_query()
+                }
+
+                /// Compare the values returned by `db_order_query` with the rust order.
+                pub fn check_db_order(db_order: &[String]) -> Result<(), String> {
+                    compare_db_order(db_order)
+                }
+            }
+        })
+    } else {
+        None
+    };
+    let mysql_check = mysql_enum.then(|| {
+        quote! {
+            impl #enum_ty {
+                /// Check that the `ENUM` definition of `table`.`column` lists the variants in
+                /// the same order as in rust. MySQL sorts and compares `ENUM` values by their
+                /// index, so a reordered definition silently changes `ORDER BY` and `<`.
+                pub fn assert_mysql_order_matches_db<C>(
+                    conn: &mut C,
+                    table: &str,
+                    column: &str,
+                ) -> Result<(), String>
+                where
+                    C: diesel::connection::LoadConnection<Backend = diesel::mysql::Mysql>,
+                {
+                    Self::check_mysql_column_type(&mysql_column_type(conn, table, column)?)
+                }
+
+                /// Compare the `COLUMN_TYPE` of a column in `information_schema.COLUMNS`,
+                /// e.g. `enum('foo','bar')`, with the rust order. For connections which
+                /// `assert_mysql_order_matches_db` doesn't support, e.g. with `diesel-async`.
+                pub fn check_mysql_column_type(column_type: &str) -> Result<(), String> {
+                    let db_order = parse_mysql_enum_values(column_type)
+                        .ok_or_else(|| format!("Not an ENUM column type: {}", column_type))?;
+                    compare_db_order(&db_order)
+                }
+            }
+        }
+    });
+    quote! {
+        fn db_enum_rank(e: &#enum_ty) -> usize {
+            match *e {
+                #(#variants_rs => #ranks,)*
+            }
+        }
+
+        impl PartialOrd for #enum_ty {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for #enum_ty {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                db_enum_rank(self).cmp(&db_enum_rank(other))
+            }
+        }
+
+        #compare_db_order
+        #pg_check
+        #mysql_check
+    }
+}
+
+/// The drift report of a postgres or mysql enum, and the helpers filling it from the database
+fn generate_drift_report_impls(
+    enum_ty: &Ident,
+    drift_report: &Ident,
+    variants_rs: &[proc_macro2::TokenStream],
+    variants_null: &[bool],
+    pg_type_sql: &str,
+    pg_enum: bool,
+    mysql_enum: bool,
+) -> proc_macro2::TokenStream {
+    let variants_db: Vec<&proc_macro2::TokenStream> = variants_rs
+        .iter()
+        .zip(variants_null)
+        .filter(|(_, is_null)| !**is_null)
+        .map(|(variant, _)| variant)
+        .collect();
+    let enum_name = enum_ty.to_string();
+    let serialize = cfg!(feature = "serde").then(|| {
+        let report_name = drift_report.to_string();
+        quote! {
+            impl ::serde::Serialize for #drift_report {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: ::serde::Serializer,
+                {
+                    use ::serde::ser::SerializeStruct;
+                    let mut report = serializer.serialize_struct(#report_name, 5)?;
+                    report.serialize_field("enum", #enum_name)?;
+                    report.serialize_field("status", self.status())?;
+                    report.serialize_field("missing", &self.missing)?;
+                    report.serialize_field("extra", &self.extra)?;
+                    report.serialize_field("order_mismatches", &self.order_mismatches)?;
+                    report.end()
+                }
+            }
+        }
+    });
+    let report = quote! {
+        impl #drift_report {
+            /// The name of the rust enum the report is about
+            pub const ENUM_NAME: &'static str = #enum_name;
+
+            /// Whether the database has exactly the variants, in the same order
+            pub fn is_in_sync(&self) -> bool {
+                self.missing.is_empty()
+                    && self.extra.is_empty()
+                    && self.order_mismatches.is_empty()
+            }
+
+            /// `"in_sync"` or `"drifted"`
+            pub fn status(&self) -> &'static str {
+                if self.is_in_sync() {
+                    "in_sync"
+                } else {
+                    "drifted"
+                }
+            }
+        }
+
+        #serialize
+
+        impl #enum_ty {
+            /// Compare the values of the database type, in order, with the variants,
+            /// and report every difference.
+            pub fn drift_report(db_order: &[String]) -> #drift_report {
+                let rust_order: Vec<&str> = [#(#variants_db),*]
+                    .iter()
+                    .map(db_str_representation)
+                    .collect();
+                let missing: Vec<String> = rust_order
+                    .iter()
+                    .filter(|value| !db_order.iter().any(|db_value| db_value == *value))
+                    .map(|value| value.to_string())
+                    .collect();
+                let extra: Vec<String> = db_order
+                    .iter()
+                    .filter(|db_value| !rust_order.contains(&db_value.as_str()))
+                    .cloned()
+                    .collect();
+                // the values both sides have, each in its own order
+                let common_rust = rust_order
+                    .iter()
+                    .filter(|value| !missing.iter().any(|missing| missing == *value));
+                let common_db = db_order
+                    .iter()
+                    .filter(|db_value| !extra.contains(db_value));
+                let order_mismatches: Vec<String> = common_rust
+                    .zip(common_db)
+                    .filter(|(value, db_value)| **value != db_value.as_str())
+                    .map(|(value, _)| value.to_string())
+                    .collect();
+                #drift_report {
+                    missing,
+                    extra,
+                    order_mismatches,
+                }
+            }
+        }
+    };
+    let pg_report = pg_enum.then(|| {
+        let enum_range = format!("enum_range(NULL::{})::text[]", pg_type_sql);
+        quote! {
+            impl #enum_ty {
+                /// Report every difference between the postgres enum type and the variants.
+                pub fn pg_drift_report(
+                    conn: &mut diesel::pg::PgConnection,
+                ) -> Result<#drift_report, String> {
+                    use diesel::RunQueryDsl;
+                    let db_order: Vec<String> = pg_enum_values_query()
+                        .get_result(conn)
+                        .map_err(|e| e.to_string())?;
+                    Ok(Self::drift_report(&db_order))
+                }
+            }
+
+            fn pg_enum_values_query(
+            ) -> diesel::dsl::BareSelect<diesel::expression::SqlLiteral<Array<Text>>> {
+                diesel::select(diesel::dsl::sql::<Array<Text>>(#enum_range))
+            }
+        }
+    });
+    let mysql_report = mysql_enum.then(|| {
+        quote! {
+            impl #enum_ty {
+                /// Report every difference between the `ENUM` definition of
+                /// `table`.`column` and the variants.
+                pub fn mysql_drift_report<C>(
+                    conn: &mut C,
+                    table: &str,
+                    column: &str,
+                ) -> Result<#drift_report, String>
+                where
+                    C: diesel::connection::LoadConnection<Backend = diesel::mysql::Mysql>,
+                {
+                    Self::mysql_column_drift_report(&mysql_column_type(conn, table, column)?)
+                }
+
+                /// Like `mysql_drift_report`, from the `COLUMN_TYPE` of the column in
+                /// `information_schema.COLUMNS`, e.g. `enum('foo','bar')`.
+                pub fn mysql_column_drift_report(
+                    column_type: &str,
+                ) -> Result<#drift_report, String> {
+                    let db_order = parse_mysql_enum_values(column_type)
+                        .ok_or_else(|| format!("Not an ENUM column type: {}", column_type))?;
+                    Ok(Self::drift_report(&db_order))
+                }
+            }
+
+            fn mysql_column_type<C>(
+                conn: &mut C,
+                table: &str,
+                column: &str,
+            ) -> Result<String, String>
+            where
+                C: diesel::connection::LoadConnection<Backend = diesel::mysql::Mysql>,
+            {
+                use diesel::RunQueryDsl;
+                let column_type: Option<String> = diesel::select(
+                    diesel::dsl::sql::<Nullable<Text>>(
+                        "(SELECT COLUMN_TYPE FROM information_schema.COLUMNS \
+                         WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ",
+                    )
+                    .bind::<Text, _>(table)
+                    .sql(" AND COLUMN_NAME = ")
+                    .bind::<Text, _>(column)
+                    .sql(")"),
+                )
+                .get_result(conn)
+                .map_err(|e| e.to_string())?;
+                column_type.ok_or_else(|| format!("Column `{}`.`{}` not found", table, column))
+            }
+
+            fn parse_mysql_enum_values(column_type: &str) -> Option<Vec<String>> {
+                let inner = column_type.trim().strip_suffix(')')?;
+                if !inner.get(..5)?.eq_ignore_ascii_case("enum(") {
+                    return None;
+                }
+                let mut chars = inner[5..].chars().peekable();
+                let mut values = Vec::new();
+                loop {
+                    if chars.next()? != '\'' {
+                        return None;
+                    }
+                    let mut value = String::new();
+                    loop {
+                        match chars.next()? {
+                            '\'' if chars.peek() == Some(&'\'') => {
+                                chars.next();
+                                value.push('\'');
+                            }
+                            '\'' => break,
+                            '\\' => value.push(chars.next()?),
+                            c => value.push(c),
+                        }
+                    }
+                    values.push(value);
+                    match chars.next() {
+                        Some(',') => {}
+                        None => return Some(values),
+                        Some(_) => return None,
+                    }
+                }
+            }
+        }
+    });
+    quote! {
+        #report
+        #pg_report
+        #mysql_report
+    }
+}
+
+fn generate_db_rank(
+    enum_ty: &Ident,
+    rank_type: &Ident,
+    variants_rs: &[proc_macro2::TokenStream],
+    variants_null: &[bool],
+    storage: BackendStorage,
+) -> proc_macro2::TokenStream {
+    // the null variant is stored as `NULL`, which no `WHEN` matches, so it ranks as `NULL`
+    let (ranked, ranks): (Vec<_>, Vec<i32>) = variants_rs
+        .iter()
+        .zip(variants_null)
+        .zip(0..)
+        .filter(|((_, is_null), _)| !**is_null)
+        .map(|((variant, _), rank)| (variant, rank))
+        .unzip();
+    let sql_type = if ranks.len() < variants_rs.len() {
+        quote! { diesel::sql_types::Nullable<Integer> }
+    } else {
+        quote! { Integer }
+    };
+    // the backends storing the enum as numbers compare against `db_int_representation`
+    let int_backends: Vec<proc_macro2::TokenStream> = [
+        (
+            cfg!(feature = "postgres") && storage.postgres().is_integer(),
+            quote! { diesel::pg::Pg },
+        ),
+        (
+            cfg!(feature = "mysql") && storage.mysql().is_integer(),
+            quote! { diesel::mysql::Mysql },
+        ),
+        (
+            cfg!(feature = "sqlite") && storage.sqlite().is_integer(),
+            quote! { diesel::sqlite::Sqlite },
+        ),
+    ]
+    .into_iter()
+    .filter(|(used, _)| *used)
+    .map(|(_, backend)| backend)
+    .collect();
+    let db_value = if int_backends.is_empty() {
+        quote! { variant.to_sql_literal_for::<DB>() }
+    } else {
+        quote! {
+            if #(std::any::TypeId::of::<DB>() == std::any::TypeId::of::<#int_backends>())||* {
+                db_int_representation(variant).to_string()
+            } else {
+                variant.to_sql_literal_for::<DB>()
+            }
+        }
+    };
+    quote! {
+        impl #enum_ty {
+            /// An `ORDER BY`-able expression which ranks the values of `col` in
+            /// declaration order, i.e. `CASE col WHEN 'a' THEN 0 WHEN 'b' THEN 1 ... END`.
+            /// Useful when the enum is stored as text, which sorts lexically.
+            pub fn db_rank<C>(col: C) -> #rank_type<C> {
+                #rank_type(col)
+            }
+        }
+
+        impl<C: diesel::Expression> diesel::Expression for #rank_type<C> {
+            type SqlType = #sql_type;
+        }
+
+        impl<C, QS> diesel::AppearsOnTable<QS> for #rank_type<C>
+        where
+            C: diesel::AppearsOnTable<QS>,
+            Self: diesel::Expression,
+        {
+        }
+
+        impl<C, QS> diesel::SelectableExpression<QS> for #rank_type<C>
+        where
+            C: diesel::SelectableExpression<QS>,
+            Self: diesel::AppearsOnTable<QS>,
+        {
+        }
+
+        impl<C, GB> diesel::expression::ValidGrouping<GB> for #rank_type<C>
+        where
+            C: diesel::expression::ValidGrouping<GB>,
+        {
+            type IsAggregate = C::IsAggregate;
+        }
+
+        impl<C, DB> diesel::query_builder::QueryFragment<DB> for #rank_type<C>
+        where
+            DB: Backend + 'static,
+            C: diesel::query_builder::QueryFragment<DB>,
+        {
+            fn walk_ast<'b>(
+                &'b self,
+                mut out: diesel::query_builder::AstPass<'_, 'b, DB>,
+            ) -> diesel::QueryResult<()> {
+                const RANKED: &[(#enum_ty, i32)] = &[#((#ranked, #ranks)),*];
+                out.push_sql("CASE ");
+                self.0.walk_ast(out.reborrow())?;
+                for (variant, rank) in RANKED {
+                    out.push_sql(" WHEN ");
+                    out.push_sql(&#db_value);
+                    out.push_sql(" THEN ");
+                    out.push_sql(&rank.to_string());
+                }
+                out.push_sql(" END");
+                Ok(())
+            }
+        }
+    }
+}
+
+fn generate_tests(enum_ty: &Ident) -> proc_macro2::TokenStream {
+    quote! {
+        #[cfg(test)]
+        mod db_enum_tests {
+            use super::*;
+
+            #[test]
+            fn db_values_round_trip() {
+                for variant in #enum_ty::all() {
+                    let value = db_str_representation(variant);
+                    let parsed = from_db_binary_representation(value.as_bytes())
+                        .unwrap_or_else(|e| panic!("failed to parse '{}': {}", value, e));
+                    assert!(
+                        std::mem::discriminant(variant) == std::mem::discriminant(&parsed),
+                        "db value '{}' does not round trip",
+                        value
+                    );
+                }
+            }
+
+            #[test]
+            fn db_values_unique() {
+                let mut values: Vec<&str> = #enum_ty::all()
+                    .iter()
+                    .map(db_str_representation)
+                    .collect();
+                values.sort_unstable();
+                for pair in values.windows(2) {
+                    assert_ne!(pair[0], pair[1], "duplicate db value");
+                }
+            }
+        }
+    }
+}
+
+fn generate_dsl(
+    vis: &Visibility,
+    enum_ty: &Ident,
+    variants: &[&VariantOptions],
+) -> proc_macro2::TokenStream {
+    let modname = Ident::new(
+        &format!("{}_dsl", enum_ty.to_string().to_snake_case()),
+        Span::call_site(),
+    );
+    let mod_doc = format!("Per-variant filter helpers for `{}`", enum_ty);
+    let trait_name = Ident::new(&format!("{}ExpressionMethods", enum_ty), Span::call_site());
+    let trait_doc = format!("Per-variant filter methods on `{}` expressions", enum_ty);
+    let fns = variants.iter().map(|variant| {
+        let id = &variant.ident;
+        let cfgs = &variant.cfgs;
+        let fn_name = Ident::new(&format!("is_{}", id.to_string().to_snake_case()), id.span());
+        let doc = format!("`col = {}::{}`", enum_ty, id);
+        quote! {
+            #(#cfgs)*
+            #[doc = #doc]
+            pub fn #fn_name<C>(col: C) -> diesel::dsl::Eq<C, #enum_ty>
+            where
+                C: diesel::ExpressionMethods,
+                C::SqlType: diesel::sql_types::SqlType,
+                #enum_ty: diesel::expression::AsExpression<C::SqlType>,
+            {
+                col.eq(#enum_ty::#id)
+            }
+        }
+    });
+    let methods = variants.iter().map(|variant| {
+        let id = &variant.ident;
+        let cfgs = &variant.cfgs;
+        let fn_name = Ident::new(&format!("is_{}", id.to_string().to_snake_case()), id.span());
+        let doc = format!("`self = {}::{}`", enum_ty, id);
+        quote! {
+            #(#cfgs)*
+            #[doc = #doc]
+            fn #fn_name(self) -> diesel::dsl::Eq<Self, #enum_ty>
+            where
+                Self::SqlType: diesel::sql_types::SqlType,
+                #enum_ty: diesel::expression::AsExpression<Self::SqlType>,
+            {
+                self.eq(#enum_ty::#id)
+            }
+        }
+    });
+    quote! {
+        #[doc = #mod_doc]
+        #[allow(dead_code)]
+        #vis mod #modname {
+            use super::*;
+            use ::diesel;
+            use diesel::ExpressionMethods;
+
+            #(#fns)*
+
+            #[doc = #trait_doc]
+            pub trait #trait_name: ExpressionMethods + ::std::marker::Sized {
+                #(#methods)*
+            }
+
+            // only for expressions of the enum's types, so that the methods of several
+            // enums' traits don't clash
+            impl<T> #trait_name for T
+            where
+                T: ExpressionMethods,
+                T::SqlType: diesel::sql_types::SqlType,
+                #enum_ty: diesel::expression::AsExpression<T::SqlType>,
+            {
+            }
+        }
+    }
+}
+
+fn generate_new_diesel_mapping(
+    new_diesel_mapping: &Ident,
+    doc: &str,
+    pg_internal_type: &str,
+    pg_oids: Option<(u32, u32)>,
+    storage: &BackendStorage,
+    backends: MappingBackends,
+) -> proc_macro2::TokenStream {
+    // Note - we only generate a new mapping for mysql and sqlite, postgres
+    // should already have one
+    let postgres_type = backends.postgres.then(|| {
+        let postgres_type = match storage.postgres() {
+            Storage::Enum => match pg_oids {
+                Some((oid, array_oid)) => {
+                    // diesel expects plain integers, without a `u32` suffix
+                    let oid = proc_macro2::Literal::u32_unsuffixed(oid);
+                    let array_oid = proc_macro2::Literal::u32_unsuffixed(array_oid);
+                    quote! { postgres_type(oid = #oid, array_oid = #array_oid) }
+                }
+                None => match split_pg_type_name(pg_internal_type) {
+                    (Some(schema), name) => {
+                        quote! { postgres_type(name = #name, schema = #schema) }
+                    }
+                    (None, name) => quote! { postgres_type(name = #name) },
+                },
+            },
+            // the oids of `text` (unless given, e.g. `bpchar` for `DbCharWidth`), `int4`
+            // and `int8`, and of their array types
+            Storage::Text => {
+                let (oid, array_oid) = pg_oids.unwrap_or((25, 1009));
+                let oid = proc_macro2::Literal::u32_unsuffixed(oid);
+                let array_oid = proc_macro2::Literal::u32_unsuffixed(array_oid);
+                quote! { postgres_type(oid = #oid, array_oid = #array_oid) }
+            }
+            Storage::Integer => quote! { postgres_type(oid = 23, array_oid = 1007) },
+            Storage::BigInt => quote! { postgres_type(oid = 20, array_oid = 1016) },
+            Storage::UnsignedInteger | Storage::UnsignedSmallInt => {
+                unreachable!("unsigned storage is only accepted for mysql")
+            }
+        };
+        quote! { #[diesel(#postgres_type)] }
+    });
+    let mysql_type = backends.mysql.then(|| {
+        let mysql_type = match storage.mysql() {
+            Storage::Enum => "Enum",
+            Storage::Text => "String",
+            Storage::Integer => "Long",
+            Storage::BigInt => "LongLong",
+            Storage::UnsignedInteger => "UnsignedLong",
+            Storage::UnsignedSmallInt => "UnsignedShort",
+        };
+        quote! { #[diesel(mysql_type(name = #mysql_type))] }
+    });
+    // `max()`, `min()` and the like only follow the enum where the database compares in
+    // declaration order
+    let sql_ord = storage.sorts_in_declaration_order(backends).then(|| {
+        quote! {
+            impl diesel::sql_types::SqlOrd for #new_diesel_mapping {}
+        }
+    });
+    let sqlite_type = backends.sqlite.then(|| {
+        let sqlite_type = match storage.sqlite() {
+            Storage::Enum | Storage::Text => "Text",
+            Storage::Integer => "Integer",
+            Storage::BigInt => "Long",
+            Storage::UnsignedInteger | Storage::UnsignedSmallInt => {
+                unreachable!("unsigned storage is only accepted for mysql")
+            }
+        };
+        quote! { #[diesel(sqlite_type(name = #sqlite_type))] }
+    });
+    quote! {
+        #[doc = #doc]
+        #[derive(
+            Debug,
+            Clone,
+            Copy,
+            Default,
+            diesel::sql_types::SqlType,
+            diesel::query_builder::QueryId,
+        )]
+        #mysql_type
+        #sqlite_type
+        #postgres_type
+        pub struct #new_diesel_mapping;
+
+        #sql_ord
+    }
+}
+
+/// The `SQL_TYPE_NAME` (of a postgres enum) and `LABELS` constants of a diesel type, for
+/// code which only has the type in scope
+fn generate_mapping_consts(
+    mapping: &proc_macro2::TokenStream,
+    enum_ty: &Ident,
+    sql_type_name: Option<&str>,
+    labels: Option<&[proc_macro2::TokenStream]>,
+) -> proc_macro2::TokenStream {
+    let sql_type_name = sql_type_name.map(|sql_type_name| {
+        let doc = format!(
+            "The name of the database type of `{}`, as written in SQL, e.g. `CAST($1 AS {})`",
+            enum_ty, sql_type_name
+        );
+        quote! {
+            #[doc = #doc]
+            pub const SQL_TYPE_NAME: &'static str = #sql_type_name;
+        }
+    });
+    let labels = labels.map(|labels| {
+        let doc = format!(
+            "The db values of `{}` (except a `db_null_variant`), in declaration order",
+            enum_ty
+        );
+        quote! {
+            #[doc = #doc]
+            pub const LABELS: &'static [&'static str] = &[#(#labels),*];
+        }
+    });
+    quote! {
+        impl #mapping {
+            #sql_type_name
+            #labels
+        }
+    }
+}
+
+/// The diesel 1.x form of `generate_new_diesel_mapping`: diesel 1 has no `SqlType` derive
+/// which looks up postgres types by name, so the impls are written out
+fn generate_diesel1_mapping(
+    new_diesel_mapping: &Ident,
+    doc: &str,
+    pg_internal_type: &str,
+    pg_oids: Option<(u32, u32)>,
+    storage: &BackendStorage,
+    backends: MappingBackends,
+) -> proc_macro2::TokenStream {
+    let postgres_type = (cfg!(feature = "postgres") && backends.postgres).then(|| {
+        let metadata = match (storage.postgres(), pg_oids) {
+            (Storage::Enum, Some((oid, array_oid))) => quote! {
+                diesel::pg::PgTypeMetadata {
+                    oid: #oid,
+                    array_oid: #array_oid,
+                }
+            },
+            (Storage::Enum, None) => quote! { lookup.lookup_type(#pg_internal_type) },
+            // the oids of `text` (unless given, e.g. `bpchar` for `DbCharWidth`) and its
+            // array type
+            (_, pg_oids) => {
+                let (oid, array_oid) = pg_oids.unwrap_or((25, 1009));
+                quote! {
+                    diesel::pg::PgTypeMetadata {
+                        oid: #oid,
+                        array_oid: #array_oid,
+                    }
+                }
+            }
+        };
+        quote! {
+            impl diesel::sql_types::HasSqlType<#new_diesel_mapping> for diesel::pg::Pg {
+                #[allow(unused_variables)]
+                fn metadata(lookup: &diesel::pg::PgMetadataLookup) -> diesel::pg::PgTypeMetadata {
+                    #metadata
+                }
+            }
+        }
+    });
+    // diesel 1 binds mysql enums as strings
+    let mysql_type = (cfg!(feature = "mysql") && backends.mysql).then(|| {
+        quote! {
+            impl diesel::sql_types::HasSqlType<#new_diesel_mapping> for diesel::mysql::Mysql {
+                fn metadata(_: &()) -> diesel::mysql::MysqlType {
+                    diesel::mysql::MysqlType::String
+                }
+            }
+        }
+    });
+    let sql_ord = storage.sorts_in_declaration_order(backends).then(|| {
+        quote! {
+            impl diesel::sql_types::SqlOrd for #new_diesel_mapping {}
+        }
+    });
+    let sqlite_type = (cfg!(feature = "sqlite") && backends.sqlite).then(|| {
+        quote! {
+            impl diesel::sql_types::HasSqlType<#new_diesel_mapping> for diesel::sqlite::Sqlite {
+                fn metadata(_: &()) -> diesel::sqlite::SqliteType {
+                    diesel::sqlite::SqliteType::Text
+                }
+            }
+        }
+    });
+    quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, Copy, Default)]
+        pub struct #new_diesel_mapping;
+
+        impl diesel::query_builder::QueryId for #new_diesel_mapping {
+            type QueryId = #new_diesel_mapping;
+            const HAS_STATIC_QUERY_ID: bool = true;
+        }
+
+        impl diesel::sql_types::NotNull for #new_diesel_mapping {}
+
+        impl diesel::sql_types::SingleValue for #new_diesel_mapping {}
+
+        #sql_ord
+
+        #postgres_type
+        #mysql_type
+        #sqlite_type
+    }
+}
+
+/// A second diesel type which stores the enum as text on every backend, alongside the
+/// main (e.g. native enum) one
+fn generate_text_mapping(
+    text_mapping: &Ident,
+    enum_ty: &Ident,
+    null_variant: &Option<proc_macro2::TokenStream>,
+    skip: SkipImpls,
+) -> proc_macro2::TokenStream {
+    let text = Some((Storage::Text, Span::call_site()));
+    let storage = BackendStorage {
+        postgres: text,
+        mysql: text,
+        sqlite: text,
+    };
+    let backends = MappingBackends {
+        postgres: true,
+        mysql: true,
+        sqlite: true,
+    };
+    let mapping = quote! { #text_mapping };
+    let doc = format!("The diesel SQL type of `{}`, stored as text", enum_ty);
+    let mapping_def = generate_new_diesel_mapping(text_mapping, &doc, "", None, &storage, backends);
+    let common_impls = generate_common_impls(&mapping, enum_ty, null_variant, skip);
+    let pg_impl = cfg!(feature = "postgres").then(|| {
+        generate_postgres_impl(
+            &mapping,
+            enum_ty,
+            false,
+            Storage::Text,
+            skip,
+            SqlHooks::default(),
+        )
+    });
+    let mysql_impl = cfg!(feature = "mysql").then(|| {
+        generate_mysql_impl(
+            text_mapping,
+            enum_ty,
+            Storage::Text,
+            skip,
+            SqlHooks::default(),
+        )
+    });
+    let sqlite_impl = cfg!(feature = "sqlite").then(|| {
+        generate_sqlite_impl(
+            text_mapping,
+            enum_ty,
+            Storage::Text,
+            skip,
+            SqlHooks::default(),
+        )
+    });
+
+    quote! {
+        pub use self::text_mapping::#text_mapping;
+
+        mod text_mapping {
+            use super::*;
+
+            #mapping_def
+            #common_impls
+            #pg_impl
+            #mysql_impl
+            #sqlite_impl
+        }
+    }
+}
+
+fn generate_str_type(str_type: &Ident, enum_ty: &Ident) -> proc_macro2::TokenStream {
+    let doc = format!(
+        "A db value of `{}`, which may also be a value that this version of the enum doesn't know",
+        enum_ty
+    );
+    quote! {
+        #[doc = #doc]
+        #[derive(std::fmt::Debug, Clone, PartialEq, Eq, std::hash::Hash, PartialOrd, Ord)]
+        pub struct #str_type(String);
+    }
+}
+
+fn generate_rank_type(rank_type: &Ident, enum_ty: &Ident) -> proc_macro2::TokenStream {
+    let doc = format!(
+        "The expression of `{}::db_rank`, ranking the values of a column in declaration order",
+        enum_ty
+    );
+    quote! {
+        #[doc = #doc]
+        #[derive(std::fmt::Debug, Clone, Copy, diesel::query_builder::QueryId)]
+        pub struct #rank_type<C>(C);
+    }
+}
+
+fn generate_drift_report(drift_report: &Ident, enum_ty: &Ident) -> proc_macro2::TokenStream {
+    let doc = format!(
+        "How the values of the database type differ from the variants of `{}`",
+        enum_ty
+    );
+    quote! {
+        #[doc = #doc]
+        #[derive(std::fmt::Debug, Clone, PartialEq, Eq, Default)]
+        pub struct #drift_report {
+            /// The db values of the variants which the database lacks, in declaration order
+            pub missing: Vec<String>,
+            /// The values of the database which are not variants, in database order
+            pub extra: Vec<String>,
+            /// The values on both sides which are not in the same place relative to the
+            /// others, in declaration order
+            pub order_mismatches: Vec<String>,
+        }
+    }
+}
+
+/// Construction checks the value against the enum, but the diesel impls accept any
+/// string, so that rows with values added to the database later can still be loaded
+fn generate_str_type_impls(
+    str_type: &Ident,
+    enum_ty: &Ident,
+    mappings: &[proc_macro2::TokenStream],
+) -> proc_macro2::TokenStream {
+    let to_sql_signature = to_sql_signature(&quote! { DB });
+    let mapping_impls = mappings.iter().map(|mapping| {
+        quote! {
+            impl AsExpression<#mapping> for #str_type {
+                type Expression = Bound<#mapping, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    Bound::new(self)
+                }
+            }
+
+            impl AsExpression<Nullable<#mapping>> for #str_type {
+                type Expression = Bound<Nullable<#mapping>, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    Bound::new(self)
+                }
+            }
+
+            impl<'a> AsExpression<#mapping> for &'a #str_type {
+                type Expression = Bound<#mapping, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    Bound::new(self)
+                }
+            }
+
+            impl<'a> AsExpression<Nullable<#mapping>> for &'a #str_type {
+                type Expression = Bound<Nullable<#mapping>, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    Bound::new(self)
+                }
+            }
+
+            impl<DB> ToSql<#mapping, DB> for #str_type
+            where
+                DB: Backend,
+                str: ToSql<diesel::sql_types::Text, DB>,
+            {
+                #to_sql_signature {
+                    <str as ToSql<diesel::sql_types::Text, DB>>::to_sql(self.0.as_str(), out)
+                }
+            }
+
+            impl<DB> ToSql<Nullable<#mapping>, DB> for #str_type
+            where
+                DB: Backend,
+                Self: ToSql<#mapping, DB>,
+            {
+                #to_sql_signature {
+                    ToSql::<#mapping, DB>::to_sql(self, out)
+                }
+            }
+
+            impl<DB> FromSql<#mapping, DB> for #str_type
+            where
+                DB: Backend,
+                String: FromSql<diesel::sql_types::Text, DB>,
+            {
+                fn from_sql(raw: DB::RawValue<'_>) -> deserialize::Result<Self> {
+                    <String as FromSql<diesel::sql_types::Text, DB>>::from_sql(raw).map(#str_type)
+                }
+            }
+
+            impl<DB> Queryable<#mapping, DB> for #str_type
+            where
+                DB: Backend,
+                Self: FromSql<#mapping, DB>,
+            {
+                type Row = Self;
+
+                fn build(row: Self::Row) -> deserialize::Result<Self> {
+                    Ok(row)
+                }
+            }
+        }
+    });
+    quote! {
+        impl #str_type {
+            /// Wrap `value` if it is the db value of a known variant, or give it back
+            pub fn new(value: impl Into<String>) -> Result<Self, String> {
+                let value = value.into();
+                match from_db_binary_representation(value.as_bytes()) {
+                    Ok(_) => Ok(Self(value)),
+                    Err(_) => Err(value),
+                }
+            }
+
+            /// Wrap `value` without checking it, e.g. to write a value which was added
+            /// to the database type before the enum
+            pub fn new_unchecked(value: impl Into<String>) -> Self {
+                Self(value.into())
+            }
+
+            /// The db value
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// The db value
+            pub fn into_inner(self) -> String {
+                self.0
+            }
+
+            /// The variant with this db value, if the enum knows it
+            pub fn known(&self) -> Option<#enum_ty> {
+                from_db_binary_representation(self.0.as_bytes()).ok()
+            }
+        }
+
+        impl From<#enum_ty> for #str_type {
+            fn from(value: #enum_ty) -> Self {
+                Self(db_str_representation(&value).to_string())
+            }
+        }
+
+        impl From<#str_type> for String {
+            fn from(value: #str_type) -> Self {
+                value.0
+            }
+        }
+
+        impl AsRef<str> for #str_type {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl std::fmt::Display for #str_type {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        #(#mapping_impls)*
+    }
+}
+
+fn generate_as_text_impls(
+    enum_ty: &Ident,
+    null_variant: &Option<proc_macro2::TokenStream>,
+    skip: SkipImpls,
+) -> proc_macro2::TokenStream {
+    let text = Ident::new("Text", Span::call_site());
+    let mapping = quote! { #text };
+    let common_impls = generate_common_impls(&mapping, enum_ty, null_variant, skip);
+    let pg_impl = cfg!(feature = "postgres").then(|| {
+        generate_postgres_impl(
+            &mapping,
+            enum_ty,
+            false,
+            Storage::Text,
+            skip,
+            SqlHooks::default(),
+        )
+    });
+    let mysql_impl = cfg!(feature = "mysql")
+        .then(|| generate_mysql_impl(&text, enum_ty, Storage::Text, skip, SqlHooks::default()));
+    let sqlite_impl = cfg!(feature = "sqlite")
+        .then(|| generate_sqlite_impl(&text, enum_ty, Storage::Text, skip, SqlHooks::default()));
+
+    quote! {
+        const _: () = {
+
+            #common_impls
+            #pg_impl
+            #mysql_impl
+            #sqlite_impl
+        };
+    }
+}
+
+fn generate_common_impls(
+    diesel_mapping: &proc_macro2::TokenStream,
+    enum_ty: &Ident,
+    null_variant: &Option<proc_macro2::TokenStream>,
+    skip: SkipImpls,
+) -> proc_macro2::TokenStream {
+    let (write_null, null_variant_impls) = match null_variant {
+        Some(null_variant) => (
+            Some(quote! {
+                if let #null_variant = *self {
+                    return Ok(IsNull::Yes);
+                }
+            }),
+            Some(generate_null_variant_impls(
+                diesel_mapping,
+                enum_ty,
+                null_variant,
+                skip,
+            )),
+        ),
+        None => (None, None),
+    };
+
+    let to_sql_signature = to_sql_signature(&quote! { DB });
+    // e.g. for `.eq(&&value)`, which is rare enough to leave out with `DbEnumCodegen(minimal)`
+    let double_ref = (!skip.double_ref).then(|| {
+        quote! {
+            impl<'a, 'b> AsExpression<#diesel_mapping> for &'a &'b #enum_ty {
+                type Expression = Bound<#diesel_mapping, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    Bound::new(self)
+                }
+            }
+
+            impl<'a, 'b> AsExpression<Nullable<#diesel_mapping>> for &'a &'b #enum_ty {
+                type Expression = Bound<Nullable<#diesel_mapping>, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    Bound::new(self)
+                }
+            }
+        }
+    });
+    let as_expression = (!skip.as_expression).then(|| {
+        quote! {
+            impl AsExpression<#diesel_mapping> for #enum_ty {
+                type Expression = Bound<#diesel_mapping, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    Bound::new(self)
+                }
+            }
+
+            impl AsExpression<Nullable<#diesel_mapping>> for #enum_ty {
+                type Expression = Bound<Nullable<#diesel_mapping>, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    Bound::new(self)
+                }
+            }
+
+            impl<'a> AsExpression<#diesel_mapping> for &'a #enum_ty {
+                type Expression = Bound<#diesel_mapping, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    Bound::new(self)
+                }
+            }
+
+            impl<'a> AsExpression<Nullable<#diesel_mapping>> for &'a #enum_ty {
+                type Expression = Bound<Nullable<#diesel_mapping>, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    Bound::new(self)
+                }
+            }
+
+            #double_ref
+
+            // `#[derive(AsExpression)]` emits this impl too, so it is skipped along with it
+            impl<DB> ToSql<Nullable<#diesel_mapping>, DB> for #enum_ty
+            where
+                DB: Backend,
+                Self: ToSql<#diesel_mapping, DB>,
+            {
+                #to_sql_signature {
+                    #write_null
+                    ToSql::<#diesel_mapping, DB>::to_sql(self, out)
+                }
+            }
+        }
+    });
+
+    quote! {
+        #as_expression
+        #null_variant_impls
+    }
+}
+
+fn generate_null_variant_impls(
+    diesel_mapping: &proc_macro2::TokenStream,
+    enum_ty: &Ident,
+    null_variant: &proc_macro2::TokenStream,
+    skip: SkipImpls,
+) -> proc_macro2::TokenStream {
+    let from_sql = (!skip.from_sql).then(|| {
+        quote! {
+            impl<DB> FromSql<Nullable<#diesel_mapping>, DB> for #enum_ty
+            where
+                DB: Backend,
+                Self: FromSql<#diesel_mapping, DB>,
+            {
+                fn from_sql(raw: DB::RawValue<'_>) -> deserialize::Result<Self> {
+                    FromSql::<#diesel_mapping, DB>::from_sql(raw)
+                }
+
+                fn from_nullable_sql(raw: Option<DB::RawValue<'_>>) -> deserialize::Result<Self> {
+                    match raw {
+                        Some(raw) => FromSql::<#diesel_mapping, DB>::from_sql(raw),
+                        None => Ok(#null_variant),
+                    }
+                }
+            }
+        }
+    });
+    let queryable = (!skip.queryable).then(|| {
+        quote! {
+            impl<DB> Queryable<Nullable<#diesel_mapping>, DB> for #enum_ty
+            where
+                DB: Backend,
+                Self: FromSql<Nullable<#diesel_mapping>, DB>,
+            {
+                type Row = Self;
+
+                fn build(row: Self::Row) -> deserialize::Result<Self> {
+                    Ok(row)
+                }
+            }
+        }
+    });
+
+    quote! {
+        #from_sql
+        #queryable
+    }
+}
+
+/// Minimal JSON string (de)serialization, shared by the JSON array storage and
+/// the `Json`/`Jsonb` impls, so users don't need a dependency on `serde_json`
+fn generate_json_string_codec() -> proc_macro2::TokenStream {
+    quote! {
+        fn push_json_string(out: &mut String, value: &str) {
+            out.push('"');
+            for c in value.chars() {
+                match c {
+                    '"' => out.push_str("\\\""),
+                    '\\' => out.push_str("\\\\"),
+                    c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+                    c => out.push(c),
+                }
+            }
+            out.push('"');
+        }
+
+        // expects the opening quote to have been consumed already
+        fn parse_json_string(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> Option<String> {
+            let mut value = String::new();
+            loop {
+                match chars.next()? {
+                    '"' => return Some(value),
+                    '\\' => match chars.next()? {
+                        '"' => value.push('"'),
+                        '\\' => value.push('\\'),
+                        '/' => value.push('/'),
+                        'b' => value.push('\u{8}'),
+                        'f' => value.push('\u{c}'),
+                        'n' => value.push('\n'),
+                        'r' => value.push('\r'),
+                        't' => value.push('\t'),
+                        'u' => {
+                            let hex: String = chars.by_ref().take(4).collect();
+                            let c = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32)?;
+                            value.push(c);
+                        }
+                        _ => return None,
+                    },
+                    c => value.push(c),
+                }
+            }
+        }
+    }
+}
+
+fn generate_json_impls(
+    enum_ty: &Ident,
+    null_variant: &Option<proc_macro2::TokenStream>,
+    skip: SkipImpls,
+) -> proc_macro2::TokenStream {
+    let json = quote! { Json };
+    let jsonb = quote! { Jsonb };
+    let queryable = |backend: &proc_macro2::TokenStream, sql_type: &proc_macro2::TokenStream| {
+        (!skip.queryable).then(|| {
+            quote! {
+                impl Queryable<#sql_type, #backend> for #enum_ty {
+                    type Row = Self;
+
+                    fn build(row: Self::Row) -> deserialize::Result<Self> {
+                        Ok(row)
+                    }
+                }
+            }
+        })
+    };
+
+    let json_common = generate_common_impls(&json, enum_ty, null_variant, skip);
+    let pg_impl = cfg!(feature = "postgres").then(|| {
+        let jsonb_common = generate_common_impls(&jsonb, enum_ty, null_variant, skip);
+        let from_sql = (!skip.from_sql).then(|| {
+            quote! {
+                impl FromSql<Json, diesel::pg::Pg> for #enum_ty {
+                    fn from_sql(raw: diesel::pg::PgValue) -> deserialize::Result<Self> {
+                        from_db_json_representation(raw.as_bytes())
+                    }
+                }
+
+                impl FromSql<Jsonb, diesel::pg::Pg> for #enum_ty {
+                    fn from_sql(raw: diesel::pg::PgValue) -> deserialize::Result<Self> {
+                        match raw.as_bytes().split_first() {
+                            Some((1, bytes)) => from_db_json_representation(bytes),
+                            _ => Err("Unsupported JSONB encoding version".into()),
+                        }
+                    }
+                }
+            }
+        });
+        let to_sql = (!skip.to_sql).then(|| {
+            quote! {
+                impl ToSql<Json, diesel::pg::Pg> for #enum_ty {
+                    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, diesel::pg::Pg>) -> serialize::Result {
+                        out.write_all(db_json_representation(self).as_bytes())?;
+                        Ok(IsNull::No)
+                    }
+                }
+
+                impl ToSql<Jsonb, diesel::pg::Pg> for #enum_ty {
+                    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, diesel::pg::Pg>) -> serialize::Result {
+                        out.write_all(&[1])?;
+                        out.write_all(db_json_representation(self).as_bytes())?;
+                        Ok(IsNull::No)
+                    }
+                }
+            }
+        });
+        let pg = quote! { diesel::pg::Pg };
+        let queryable_json = queryable(&pg, &json);
+        let queryable_jsonb = queryable(&pg, &jsonb);
+        quote! {
+            #jsonb_common
+            #from_sql
+            #to_sql
+            #queryable_json
+            #queryable_jsonb
+        }
+    });
+    let mysql_impl = cfg!(feature = "mysql").then(|| {
+        let from_sql = (!skip.from_sql).then(|| {
+            quote! {
+                impl FromSql<Json, diesel::mysql::Mysql> for #enum_ty {
+                    fn from_sql(raw: diesel::mysql::MysqlValue) -> deserialize::Result<Self> {
+                        from_db_json_representation(raw.as_bytes())
+                    }
+                }
+            }
+        });
+        let to_sql = (!skip.to_sql).then(|| {
+            quote! {
+                impl ToSql<Json, diesel::mysql::Mysql> for #enum_ty {
+                    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, diesel::mysql::Mysql>) -> serialize::Result {
+                        out.write_all(db_json_representation(self).as_bytes())?;
+                        Ok(IsNull::No)
+                    }
+                }
+            }
+        });
+        let queryable_json = queryable(&quote! { diesel::mysql::Mysql }, &json);
+        quote! {
+            #from_sql
+            #to_sql
+            #queryable_json
+        }
+    });
+
+    quote! {
+        const _: () = {
+
+            fn db_json_representation(e: &#enum_ty) -> String {
+                let mut out = String::new();
+                push_json_string(&mut out, db_str_representation(e));
+                out
+            }
+
+            fn from_db_json_representation(bytes: &[u8]) -> deserialize::Result<#enum_ty> {
+                let text = std::str::from_utf8(bytes)?;
+                let invalid = || format!("Expected a JSON string, got '{}'", text);
+                let mut chars = text.trim().chars().peekable();
+                if chars.next() != Some('"') {
+                    return Err(invalid().into());
+                }
+                let value = parse_json_string(&mut chars).ok_or_else(invalid)?;
+                if chars.next().is_some() {
+                    return Err(invalid().into());
+                }
+                from_db_binary_representation(value.as_bytes())
+            }
+
+            #json_common
+            #pg_impl
+            #mysql_impl
+        };
+    }
+}
+
+/// The diesel type of the array storage, and the newtype for writing a `Vec` of the enum
+fn generate_array_storage_types(
+    array_mapping: &Ident,
+    array_ty: &Ident,
+    enum_ty: &Ident,
 ) -> proc_macro2::TokenStream {
-    // Note - we only generate a new mapping for mysql and sqlite, postgres
-    // should already have one
+    // `Vec<enum>` can be read directly, but due to the orphan rules it cannot be
+    // written directly, so we also generate a newtype for the write path
+    let mapping_doc = format!(
+        "The diesel SQL type of a list of `{}`, stored as text",
+        enum_ty
+    );
+    let ty_doc = format!(
+        "A list of `{}`, for writing to a `{}` column",
+        enum_ty, array_mapping
+    );
     quote! {
-        #[derive(Clone, SqlType, diesel::query_builder::QueryId)]
-        #[diesel(mysql_type(name = "Enum"))]
+        #[doc = #mapping_doc]
+        #[derive(
+            Debug,
+            Clone,
+            Copy,
+            Default,
+            diesel::sql_types::SqlType,
+            diesel::query_builder::QueryId,
+        )]
+        #[diesel(mysql_type(name = "String"))]
         #[diesel(sqlite_type(name = "Text"))]
-        #[diesel(postgres_type(name = #pg_internal_type))]
-        pub struct #new_diesel_mapping;
+        pub struct #array_mapping;
+
+        #[doc = #ty_doc]
+        #[derive(Debug)]
+        pub struct #array_ty(pub Vec<#enum_ty>);
     }
 }
 
-fn generate_common_impls(
-    diesel_mapping: &proc_macro2::TokenStream,
+fn generate_array_storage_impls(
+    array_mapping: &Ident,
+    array_ty: &Ident,
     enum_ty: &Ident,
+    storage: &ArrayStorage,
 ) -> proc_macro2::TokenStream {
-    quote! {
-        impl AsExpression<#diesel_mapping> for #enum_ty {
-            type Expression = Bound<#diesel_mapping, Self>;
+    let codec = match storage {
+        ArrayStorage::Json => quote! {
+            fn encode_db_array(values: &[#enum_ty]) -> String {
+                let mut out = String::from("[");
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    push_json_string(&mut out, db_str_representation(value));
+                }
+                out.push(']');
+                out
+            }
 
-            fn as_expression(self) -> Self::Expression {
-                Bound::new(self)
+            fn decode_db_array(bytes: &[u8]) -> deserialize::Result<Vec<#enum_ty>> {
+                let text = std::str::from_utf8(bytes)?;
+                let invalid = || format!("Invalid JSON array of enum variants: '{}'", text);
+                let mut chars = text.trim().chars().peekable();
+                if chars.next() != Some('[') {
+                    return Err(invalid().into());
+                }
+                let mut values = Vec::new();
+                loop {
+                    while chars.peek().map_or(false, |c| c.is_whitespace()) {
+                        chars.next();
+                    }
+                    match chars.next() {
+                        Some(']') if values.is_empty() => break,
+                        Some('"') => {}
+                        _ => return Err(invalid().into()),
+                    }
+                    let value = parse_json_string(&mut chars).ok_or_else(invalid)?;
+                    values.push(from_db_binary_representation(value.as_bytes())?);
+                    while chars.peek().map_or(false, |c| c.is_whitespace()) {
+                        chars.next();
+                    }
+                    match chars.next() {
+                        Some(',') => continue,
+                        Some(']') => break,
+                        _ => return Err(invalid().into()),
+                    }
+                }
+                Ok(values)
+            }
+        },
+        ArrayStorage::Delimited(delimiter) => {
+            let delimiter_bytes = LitByteStr::new(delimiter.as_bytes(), Span::call_site());
+            quote! {
+                fn encode_db_array(values: &[#enum_ty]) -> String {
+                    values
+                        .iter()
+                        .map(db_str_representation)
+                        .collect::<Vec<_>>()
+                        .join(#delimiter)
+                }
+
+                fn decode_db_array(bytes: &[u8]) -> deserialize::Result<Vec<#enum_ty>> {
+                    let delimiter: &[u8] = #delimiter_bytes;
+                    let mut values = Vec::new();
+                    if bytes.is_empty() {
+                        return Ok(values);
+                    }
+                    let mut rest = bytes;
+                    while let Some(pos) = rest
+                        .windows(delimiter.len())
+                        .position(|window| window == delimiter)
+                    {
+                        values.push(from_db_binary_representation(&rest[..pos])?);
+                        rest = &rest[pos + delimiter.len()..];
+                    }
+                    values.push(from_db_binary_representation(rest)?);
+                    Ok(values)
+                }
             }
         }
+    };
 
-        impl AsExpression<Nullable<#diesel_mapping>> for #enum_ty {
-            type Expression = Bound<Nullable<#diesel_mapping>, Self>;
+    let mysql_impl = if cfg!(feature = "mysql") {
+        Some(quote! {
+            impl ToSql<#array_mapping, diesel::mysql::Mysql> for #array_ty {
+                fn to_sql<'b>(
+                    &'b self,
+                    out: &mut Output<'b, '_, diesel::mysql::Mysql>,
+                ) -> serialize::Result {
+                    out.write_all(encode_db_array(&self.0).as_bytes())?;
+                    Ok(IsNull::No)
+                }
+            }
 
-            fn as_expression(self) -> Self::Expression {
-                Bound::new(self)
+            impl FromSql<#array_mapping, diesel::mysql::Mysql> for Vec<#enum_ty> {
+                fn from_sql(raw: diesel::mysql::MysqlValue) -> deserialize::Result<Self> {
+                    decode_db_array(raw.as_bytes())
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    let sqlite_impl = if cfg!(feature = "sqlite") {
+        Some(quote! {
+            impl ToSql<#array_mapping, diesel::sqlite::Sqlite> for #array_ty {
+                fn to_sql<'b>(
+                    &'b self,
+                    out: &mut Output<'b, '_, diesel::sqlite::Sqlite>,
+                ) -> serialize::Result {
+                    out.set_value(encode_db_array(&self.0));
+                    Ok(IsNull::No)
+                }
+            }
+
+            impl FromSql<#array_mapping, diesel::sqlite::Sqlite> for Vec<#enum_ty> {
+                fn from_sql(
+                    value: <diesel::sqlite::Sqlite as Backend>::RawValue<'_>,
+                ) -> deserialize::Result<Self> {
+                    let bytes = <Vec<u8> as FromSql<Binary, diesel::sqlite::Sqlite>>::from_sql(value)?;
+                    decode_db_array(bytes.as_slice())
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    quote! {
+        impl From<Vec<#enum_ty>> for #array_ty {
+            fn from(values: Vec<#enum_ty>) -> Self {
+                #array_ty(values)
+            }
+        }
+
+        impl From<#array_ty> for Vec<#enum_ty> {
+            fn from(values: #array_ty) -> Self {
+                values.0
             }
         }
 
-        impl<'a> AsExpression<#diesel_mapping> for &'a #enum_ty {
-            type Expression = Bound<#diesel_mapping, Self>;
+        #codec
+
+        impl AsExpression<#array_mapping> for #array_ty {
+            type Expression = Bound<#array_mapping, Self>;
 
             fn as_expression(self) -> Self::Expression {
                 Bound::new(self)
             }
         }
 
-        impl<'a> AsExpression<Nullable<#diesel_mapping>> for &'a #enum_ty {
-            type Expression = Bound<Nullable<#diesel_mapping>, Self>;
+        impl AsExpression<Nullable<#array_mapping>> for #array_ty {
+            type Expression = Bound<Nullable<#array_mapping>, Self>;
 
             fn as_expression(self) -> Self::Expression {
                 Bound::new(self)
             }
         }
 
-        impl<'a, 'b> AsExpression<#diesel_mapping> for &'a &'b #enum_ty {
-            type Expression = Bound<#diesel_mapping, Self>;
+        impl<'a> AsExpression<#array_mapping> for &'a #array_ty {
+            type Expression = Bound<#array_mapping, Self>;
 
             fn as_expression(self) -> Self::Expression {
                 Bound::new(self)
             }
         }
 
-        impl<'a, 'b> AsExpression<Nullable<#diesel_mapping>> for &'a &'b #enum_ty {
-            type Expression = Bound<Nullable<#diesel_mapping>, Self>;
+        impl<'a> AsExpression<Nullable<#array_mapping>> for &'a #array_ty {
+            type Expression = Bound<Nullable<#array_mapping>, Self>;
 
             fn as_expression(self) -> Self::Expression {
                 Bound::new(self)
             }
         }
 
-        impl<DB> ToSql<Nullable<#diesel_mapping>, DB> for #enum_ty
+        impl<DB> ToSql<Nullable<#array_mapping>, DB> for #array_ty
         where
             DB: Backend,
-            Self: ToSql<#diesel_mapping, DB>,
+            Self: ToSql<#array_mapping, DB>,
         {
             fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
-                ToSql::<#diesel_mapping, DB>::to_sql(self, out)
+                ToSql::<#array_mapping, DB>::to_sql(self, out)
+            }
+        }
+
+        impl<DB> FromSql<#array_mapping, DB> for #array_ty
+        where
+            DB: Backend,
+            Vec<#enum_ty>: FromSql<#array_mapping, DB>,
+        {
+            fn from_sql(raw: DB::RawValue<'_>) -> deserialize::Result<Self> {
+                FromSql::<#array_mapping, DB>::from_sql(raw).map(#array_ty)
             }
         }
+
+        #mysql_impl
+        #sqlite_impl
+    }
+}
+
+/// The functions set with `DbSerializeWith` and `DbDeserializeWith`, which replace the
+/// conversion of text and enum values in the `ToSql` and `FromSql` impls
+#[derive(Clone, Copy, Default)]
+struct SqlHooks<'a> {
+    serialize_with: Option<&'a Path>,
+    deserialize_with: Option<&'a Path>,
+}
+
+impl SqlHooks<'_> {
+    /// Read the enum from `bytes`
+    fn read_bytes(&self, bytes: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        match self.deserialize_with {
+            Some(path) => quote! { #path(#bytes).map_err(Into::into) },
+            None => quote! { from_db_binary_representation(#bytes) },
+        }
+    }
+}
+
+/// The bodies of `FromSql::from_sql` (reading `raw`) and `ToSql::to_sql` (writing `out`)
+/// for a backend which binds raw bytes, i.e. postgres and mysql
+fn raw_bytes_sql_bodies(
+    backend: &proc_macro2::TokenStream,
+    storage: Storage,
+    hooks: SqlHooks,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    match storage {
+        Storage::Enum | Storage::Text => (
+            hooks.read_bytes(quote! { raw.as_bytes() }),
+            match hooks.serialize_with {
+                Some(path) => quote! {
+                    out.write_all(AsRef::<[u8]>::as_ref(&#path(self)))?;
+                    Ok(IsNull::No)
+                },
+                None => quote! {
+                    out.write_all(db_str_representation(self).as_bytes())?;
+                    Ok(IsNull::No)
+                },
+            },
+        ),
+        Storage::Integer => (
+            quote! {
+                from_db_int_representation(
+                    <i32 as FromSql<sql_types::Integer, #backend>>::from_sql(raw)?.into()
+                )
+            },
+            quote! {
+                ToSql::<sql_types::Integer, #backend>::to_sql(
+                    &db_int_representation(self),
+                    &mut out.reborrow(),
+                )
+            },
+        ),
+        Storage::BigInt => (
+            quote! {
+                from_db_int_representation(
+                    <i64 as FromSql<sql_types::BigInt, #backend>>::from_sql(raw)?
+                )
+            },
+            quote! {
+                ToSql::<sql_types::BigInt, #backend>::to_sql(
+                    &i64::from(db_int_representation(self)),
+                    &mut out.reborrow(),
+                )
+            },
+        ),
+        // the stored numbers are checked to fit the column when deriving
+        Storage::UnsignedInteger => (
+            quote! {
+                from_db_int_representation(
+                    <u32 as FromSql<sql_types::Unsigned<sql_types::Integer>, #backend>>::from_sql(raw)?.into()
+                )
+            },
+            quote! {
+                ToSql::<sql_types::Unsigned<sql_types::Integer>, #backend>::to_sql(
+                    &(db_int_representation(self) as u32),
+                    &mut out.reborrow(),
+                )
+            },
+        ),
+        Storage::UnsignedSmallInt => (
+            quote! {
+                from_db_int_representation(
+                    <u16 as FromSql<sql_types::Unsigned<sql_types::SmallInt>, #backend>>::from_sql(raw)?.into()
+                )
+            },
+            quote! {
+                ToSql::<sql_types::Unsigned<sql_types::SmallInt>, #backend>::to_sql(
+                    &(db_int_representation(self) as u16),
+                    &mut out.reborrow(),
+                )
+            },
+        ),
     }
 }
 
@@ -381,42 +7579,66 @@ fn generate_postgres_impl(
     diesel_mapping: &proc_macro2::TokenStream,
     enum_ty: &Ident,
     with_clone: bool,
+    storage: Storage,
+    skip: SkipImpls,
+    hooks: SqlHooks,
 ) -> proc_macro2::TokenStream {
-    // If the type was generated by postgres, we have to manually add a clone impl,
-    // if generated by 'us' it has already been done
+    // If the type was generated by postgres, we have to manually add clone and debug
+    // impls, if generated by 'us' it has already been done
     let clone_impl = if with_clone {
+        let path = diesel_mapping.to_string().replace(' ', "");
+        let name = path.rsplit("::").next().unwrap_or_default();
         Some(quote! {
             impl Clone for #diesel_mapping {
                 fn clone(&self) -> Self {
                     #diesel_mapping
                 }
             }
+
+            impl std::fmt::Debug for #diesel_mapping {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.write_str(#name)
+                }
+            }
         })
     } else {
         None
     };
 
-    quote! {
-        mod pg_impl {
-            use super::*;
-            use diesel::pg::{Pg, PgValue};
-
-            #clone_impl
+    if cfg!(feature = "diesel-1") {
+        let impls =
+            generate_diesel1_impls(diesel_mapping, enum_ty, &quote! { Pg }, None, skip, hooks);
+        return quote! {
+            const _: () = {
+                use diesel::pg::Pg;
 
+                #clone_impl
+                #impls
+            };
+        };
+    }
+    let (from_sql_body, to_sql_body) = raw_bytes_sql_bodies(&quote! { Pg }, storage, hooks);
+    let from_sql = (!skip.from_sql).then(|| {
+        quote! {
             impl FromSql<#diesel_mapping, Pg> for #enum_ty {
                 fn from_sql(raw: PgValue) -> deserialize::Result<Self> {
-                    from_db_binary_representation(raw.as_bytes())
+                    #from_sql_body
                 }
             }
-
+        }
+    });
+    let to_sql = (!skip.to_sql).then(|| {
+        quote! {
             impl ToSql<#diesel_mapping, Pg> for #enum_ty
             {
                 fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
-                    out.write_all(db_str_representation(self).as_bytes())?;
-                    Ok(IsNull::No)
+                    #to_sql_body
                 }
             }
-
+        }
+    });
+    let queryable = (!skip.queryable).then(|| {
+        quote! {
             impl Queryable<#diesel_mapping, Pg> for #enum_ty {
                 type Row = Self;
 
@@ -425,30 +7647,68 @@ fn generate_postgres_impl(
                 }
             }
         }
+    });
+
+    quote! {
+        const _: () = {
+            use diesel::pg::{Pg, PgValue};
+            use diesel::sql_types;
+
+            #clone_impl
+
+            #from_sql
+            #to_sql
+            #queryable
+        };
     }
 }
 
-fn generate_mysql_impl(diesel_mapping: &Ident, enum_ty: &Ident) -> proc_macro2::TokenStream {
-    quote! {
-        mod mysql_impl {
-            use super::*;
-            use diesel;
-            use diesel::mysql::{Mysql, MysqlValue};
+fn generate_mysql_impl(
+    diesel_mapping: &Ident,
+    enum_ty: &Ident,
+    storage: Storage,
+    skip: SkipImpls,
+    hooks: SqlHooks,
+) -> proc_macro2::TokenStream {
+    if cfg!(feature = "diesel-1") {
+        let impls = generate_diesel1_impls(
+            &quote! { #diesel_mapping },
+            enum_ty,
+            &quote! { Mysql },
+            None,
+            skip,
+            hooks,
+        );
+        return quote! {
+            const _: () = {
+                use diesel::mysql::Mysql;
 
+                #impls
+            };
+        };
+    }
+    let (from_sql_body, to_sql_body) = raw_bytes_sql_bodies(&quote! { Mysql }, storage, hooks);
+    let from_sql = (!skip.from_sql).then(|| {
+        quote! {
             impl FromSql<#diesel_mapping, Mysql> for #enum_ty {
                 fn from_sql(raw: MysqlValue) -> deserialize::Result<Self> {
-                    from_db_binary_representation(raw.as_bytes())
+                    #from_sql_body
                 }
             }
-
+        }
+    });
+    let to_sql = (!skip.to_sql).then(|| {
+        quote! {
             impl ToSql<#diesel_mapping, Mysql> for #enum_ty
             {
                 fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Mysql>) -> serialize::Result {
-                    out.write_all(db_str_representation(self).as_bytes())?;
-                    Ok(IsNull::No)
+                    #to_sql_body
                 }
             }
-
+        }
+    });
+    let queryable = (!skip.queryable).then(|| {
+        quote! {
             impl Queryable<#diesel_mapping, Mysql> for #enum_ty {
                 type Row = Self;
 
@@ -457,31 +7717,229 @@ fn generate_mysql_impl(diesel_mapping: &Ident, enum_ty: &Ident) -> proc_macro2::
                 }
             }
         }
+    });
+
+    quote! {
+        const _: () = {
+            use diesel;
+            use diesel::mysql::{Mysql, MysqlValue};
+            use diesel::sql_types;
+
+            #from_sql
+            #to_sql
+            #queryable
+        };
     }
 }
 
-fn generate_sqlite_impl(diesel_mapping: &Ident, enum_ty: &Ident) -> proc_macro2::TokenStream {
+fn generate_sqlite_impl(
+    diesel_mapping: &Ident,
+    enum_ty: &Ident,
+    storage: Storage,
+    skip: SkipImpls,
+    hooks: SqlHooks,
+) -> proc_macro2::TokenStream {
+    if cfg!(feature = "diesel-1") {
+        let impls = generate_diesel1_impls(
+            &quote! { #diesel_mapping },
+            enum_ty,
+            &quote! { Sqlite },
+            Some(quote! { raw.read_blob() }),
+            skip,
+            hooks,
+        );
+        return quote! {
+            const _: () = {
+                use diesel::sqlite::Sqlite;
+
+                #impls
+            };
+        };
+    }
+    let (from_sql_body, to_sql_body) = match storage {
+        Storage::Enum | Storage::Text => {
+            let from_bytes = hooks.read_bytes(quote! { bytes.as_slice() });
+            (
+                quote! {
+                    let bytes = <Vec<u8> as FromSql<sql_types::Binary, Sqlite>>::from_sql(value)?;
+                    #from_bytes
+                },
+                match hooks.serialize_with {
+                    Some(path) => quote! {
+                        out.set_value(#path(self));
+                        Ok(IsNull::No)
+                    },
+                    None => quote! {
+                        <str as ToSql<sql_types::Text, Sqlite>>::to_sql(db_str_representation(self), out)
+                    },
+                },
+            )
+        }
+        Storage::Integer => (
+            quote! {
+                from_db_int_representation(<i32 as FromSql<sql_types::Integer, Sqlite>>::from_sql(value)?.into())
+            },
+            quote! {
+                out.set_value(db_int_representation(self));
+                Ok(IsNull::No)
+            },
+        ),
+        Storage::BigInt => (
+            quote! {
+                from_db_int_representation(<i64 as FromSql<sql_types::BigInt, Sqlite>>::from_sql(value)?)
+            },
+            quote! {
+                out.set_value(i64::from(db_int_representation(self)));
+                Ok(IsNull::No)
+            },
+        ),
+        Storage::UnsignedInteger | Storage::UnsignedSmallInt => {
+            unreachable!("unsigned storage is only accepted for mysql")
+        }
+    };
+    let from_sql = (!skip.from_sql).then(|| {
+        quote! {
+            impl FromSql<#diesel_mapping, Sqlite> for #enum_ty {
+                fn from_sql(value: <Sqlite as Backend>::RawValue<'_>) -> deserialize::Result<Self> {
+                    #from_sql_body
+                }
+            }
+        }
+    });
+    let to_sql = (!skip.to_sql).then(|| {
+        quote! {
+            impl ToSql<#diesel_mapping, Sqlite> for #enum_ty {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
+                    #to_sql_body
+                }
+            }
+        }
+    });
+    let queryable = (!skip.queryable).then(|| {
+        quote! {
+            impl Queryable<#diesel_mapping, Sqlite> for #enum_ty {
+                type Row = Self;
+
+                fn build(row: Self::Row) -> deserialize::Result<Self> {
+                    Ok(row)
+                }
+            }
+        }
+    });
+
     quote! {
-        mod sqlite_impl {
-            use super::*;
+        const _: () = {
             use diesel;
             use diesel::sql_types;
             use diesel::sqlite::Sqlite;
 
-            impl FromSql<#diesel_mapping, Sqlite> for #enum_ty {
-                fn from_sql(value: backend::RawValue<Sqlite>) -> deserialize::Result<Self> {
-                    let bytes = <Vec<u8> as FromSql<sql_types::Binary, Sqlite>>::from_sql(value)?;
-                    from_db_binary_representation(bytes.as_slice())
+            #from_sql
+            #to_sql
+            #queryable
+        };
+    }
+}
+
+/// The signature of `ToSql::to_sql` for the diesel version in use
+fn to_sql_signature(backend: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    if cfg!(feature = "diesel-1") {
+        quote! { fn to_sql<W: Write>(&self, out: &mut Output<W, #backend>) -> serialize::Result }
+    } else {
+        quote! { fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, #backend>) -> serialize::Result }
+    }
+}
+
+/// The diesel 1.x `FromSql`, `ToSql`, `FromSqlRow` and `Queryable` impls for a backend,
+/// which store the db value as text. `read_raw` turns the backend's raw value into
+/// bytes, if it isn't bytes already.
+fn generate_diesel1_impls(
+    diesel_mapping: &proc_macro2::TokenStream,
+    enum_ty: &Ident,
+    backend: &proc_macro2::TokenStream,
+    read_raw: Option<proc_macro2::TokenStream>,
+    skip: SkipImpls,
+    hooks: SqlHooks,
+) -> proc_macro2::TokenStream {
+    let read_raw = read_raw.unwrap_or_else(|| quote! { raw });
+    let from_bytes = hooks.read_bytes(quote! { bytes });
+    let value = match hooks.serialize_with {
+        Some(path) => quote! { AsRef::<[u8]>::as_ref(&#path(self)) },
+        None => quote! { db_str_representation(self).as_bytes() },
+    };
+    let to_sql_signature = to_sql_signature(backend);
+    let from_sql = (!skip.from_sql).then(|| {
+        quote! {
+            impl FromSql<#diesel_mapping, #backend> for #enum_ty {
+                fn from_sql(
+                    raw: Option<&<#backend as Backend>::RawValue>,
+                ) -> deserialize::Result<Self> {
+                    let raw = raw.ok_or("Unexpected null for non-null column")?;
+                    let bytes = #read_raw;
+                    #from_bytes
+                }
+            }
+        }
+    });
+    let to_sql = (!skip.to_sql).then(|| {
+        quote! {
+            impl ToSql<#diesel_mapping, #backend> for #enum_ty {
+                #to_sql_signature {
+                    out.write_all(#value)?;
+                    Ok(IsNull::No)
+                }
+            }
+        }
+    });
+    // diesel 1 reads rows through `FromSqlRow`, which `#[derive(Queryable)]` doesn't cover
+    let queryable = (!skip.queryable).then(|| {
+        quote! {
+            impl FromSqlRow<#diesel_mapping, #backend> for #enum_ty {
+                fn build_from_row<T: Row<#backend>>(row: &mut T) -> deserialize::Result<Self> {
+                    FromSql::<#diesel_mapping, #backend>::from_sql(row.take())
                 }
             }
 
-            impl ToSql<#diesel_mapping, Sqlite> for #enum_ty {
-                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
-                    <str as ToSql<sql_types::Text, Sqlite>>::to_sql(db_str_representation(self), out)
+            impl Queryable<#diesel_mapping, #backend> for #enum_ty {
+                type Row = Self;
+
+                fn build(row: Self::Row) -> Self {
+                    row
                 }
             }
+        }
+    });
 
-            impl Queryable<#diesel_mapping, Sqlite> for #enum_ty {
+    quote! {
+        #from_sql
+        #to_sql
+        #queryable
+    }
+}
+
+fn generate_custom_backend_impl(
+    diesel_mapping: &proc_macro2::TokenStream,
+    enum_ty: &Ident,
+    backend: &Path,
+    skip: SkipImpls,
+) -> proc_macro2::TokenStream {
+    let from_sql = (!skip.from_sql).then(|| quote! {
+        impl FromSql<#diesel_mapping, #backend> for #enum_ty {
+            fn from_sql(value: <#backend as Backend>::RawValue<'_>) -> deserialize::Result<Self> {
+                let value = <String as FromSql<sql_types::Text, #backend>>::from_sql(value)?;
+                from_db_binary_representation(value.as_bytes())
+            }
+        }
+    });
+    let to_sql = (!skip.to_sql).then(|| quote! {
+        impl ToSql<#diesel_mapping, #backend> for #enum_ty {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, #backend>) -> serialize::Result {
+                <str as ToSql<sql_types::Text, #backend>>::to_sql(db_str_representation(self), out)
+            }
+        }
+    });
+    let queryable = (!skip.queryable).then(|| {
+        quote! {
+            impl Queryable<#diesel_mapping, #backend> for #enum_ty {
                 type Row = Self;
 
                 fn build(row: Self::Row) -> deserialize::Result<Self> {
@@ -489,5 +7947,350 @@ fn generate_sqlite_impl(diesel_mapping: &Ident, enum_ty: &Ident) -> proc_macro2:
                 }
             }
         }
+    });
+
+    quote! {
+        const _: () = {
+            use diesel::sql_types::{self, HasSqlType};
+
+            impl HasSqlType<#diesel_mapping> for #backend {
+                fn metadata(lookup: &mut Self::MetadataLookup) -> Self::TypeMetadata {
+                    <#backend as HasSqlType<sql_types::Text>>::metadata(lookup)
+                }
+            }
+
+            #from_sql
+            #to_sql
+            #queryable
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh directory for the files of one test
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "diesel-derive-enum-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn snapshot_round_trip() {
+        let mut snapshot = Snapshot::new();
+        snapshot.insert(
+            "app.mood".to_string(),
+            vec!["happy".into(), "so \"so\"".into(), "a\\b\n\u{1}".into()],
+        );
+        snapshot.insert("empty".to_string(), vec![]);
+        let path = temp_dir("snapshot_round_trip").join("db_enums.json");
+        write_snapshot(&path, &snapshot).unwrap();
+        assert_eq!(read_snapshot(&path).unwrap(), snapshot);
+    }
+
+    #[test]
+    fn snapshot_escapes() {
+        let snapshot =
+            parse_snapshot(r#" { "mood" : [ "a\"b", "c\\d\/e", "\b\f\n\r\t", "éé", "😀" ] } "#)
+                .unwrap();
+        assert_eq!(
+            snapshot["mood"],
+            vec!["a\"b", "c\\d/e", "\u{8}\u{c}\n\r\t", "éé", "😀"]
+        );
+        assert_eq!(parse_snapshot("{}").unwrap(), Snapshot::new());
+    }
+
+    #[test]
+    fn snapshot_rejects_malformed_input() {
+        for text in [
+            "",
+            "[]",
+            "{",
+            r#"{"mood": ["a"]"#,
+            r#"{"mood": ["a",]}"#,
+            r#"{"mood": ["a"],}"#,
+            r#"{"mood": "a"}"#,
+            r#"{"mood" ["a"]}"#,
+            r#"{mood: ["a"]}"#,
+            r#"{"mood": ["a"]} {}"#,
+            r#"{"mood": ["a"], "mood": ["b"]}"#,
+            // escapes
+            r#"{"mood": ["\x"]}"#,
+            r#"{"mood": ["\u12"]}"#,
+            r#"{"mood": ["\u+123"]}"#,
+            r#"{"mood": ["\ud83d"]}"#,
+            r#"{"mood": ["\ud83dA"]}"#,
+            r#"{"mood": ["\ude00"]}"#,
+            "{\"mood\": [\"a\nb\"]}",
+            r#"{"mood": ["a]}"#,
+        ] {
+            assert_eq!(parse_snapshot(text), None, "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn snapshot_lock_waits_for_the_holder() {
+        let path = temp_dir("snapshot_lock_waits").join("db_enums.json.lock");
+        let lock = SnapshotLock::acquire(path.clone(), std::time::Duration::from_secs(30)).unwrap();
+        let holder = std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            drop(lock);
+        });
+        let started = std::time::Instant::now();
+        let lock = SnapshotLock::acquire(path.clone(), std::time::Duration::from_secs(30)).unwrap();
+        assert!(started.elapsed() >= std::time::Duration::from_millis(40));
+        holder.join().unwrap();
+        assert!(path.exists());
+        drop(lock);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn snapshot_lock_takes_over_a_stale_lock() {
+        let path = temp_dir("snapshot_lock_stale").join("db_enums.json.lock");
+        // left behind by a killed build
+        std::fs::write(&path, "").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        let lock =
+            SnapshotLock::acquire(path.clone(), std::time::Duration::from_millis(20)).unwrap();
+        drop(lock);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn config_accepted_subset() {
+        let config = ConfigDefaults::parse(
+            "# project-wide defaults\n\
+             \n\
+             value_style = \"camelCase\"  # trailing comment\n\
+             pg_type_style='PascalCase'\n\
+             \tschema_root = \"crate::schema::sql_types\"\r\n\
+             [storage_by_backend] # the table\n\
+             sqlite = \"integer\"\n",
+            "db-enum.toml",
+        )
+        .unwrap();
+        assert_eq!(config.value_style, Some(CaseStyle::Camel));
+        assert_eq!(config.pg_type_style, Some(CaseStyle::Pascal));
+        let root = config.schema_root.unwrap();
+        assert_eq!(quote!(#root).to_string(), "crate :: schema :: sql_types");
+        assert_eq!(config.storage.sqlite(), Storage::Integer);
+        assert_eq!(config.storage.postgres(), Storage::Enum);
+
+        let empty = ConfigDefaults::parse("", "db-enum.toml").unwrap();
+        assert_eq!(empty.value_style, None);
+    }
+
+    #[test]
+    fn config_rejects_everything_else() {
+        for (text, message) in [
+            // keys
+            (
+                "\"value_style\" = \"camelCase\"",
+                "expected `key = \"value\"`",
+            ),
+            (
+                "storage_by_backend.sqlite = \"integer\"",
+                "expected `key = \"value\"`",
+            ),
+            ("value style = \"camelCase\"", "expected `key = \"value\"`"),
+            ("= \"camelCase\"", "expected `key = \"value\"`"),
+            ("value_styles = \"camelCase\"", "unknown key `value_styles`"),
+            (
+                "value_style = \"camelCase\"\nvalue_style = \"camelCase\"",
+                "duplicate key",
+            ),
+            // values
+            ("value_style = camelCase", "expected `key = \"value\"`"),
+            ("value_style = \"camelCase", "expected `key = \"value\"`"),
+            (
+                "value_style = \"camel\\u0043ase\"",
+                "expected `key = \"value\"`",
+            ),
+            (
+                "value_style = \"camel\\\"Case\"",
+                "expected `key = \"value\"`",
+            ),
+            (
+                "value_style = \"\"\"camelCase\"\"\"",
+                "expected `key = \"value\"`",
+            ),
+            (
+                "value_style = \"camelCase\" x",
+                "expected `key = \"value\"`",
+            ),
+            (
+                "value_style = [\"camelCase\"]",
+                "expected `key = \"value\"`",
+            ),
+            ("value_style = \"camel\"", "unsupported casing"),
+            ("schema_root = \"not a path\"", "is not a module path"),
+            // tables
+            ("[storage]", "unknown table `[storage]`"),
+            ("[ storage_by_backend ]", "unknown table"),
+            ("[[storage_by_backend]]", "unknown table"),
+            ("[storage_by_backend.sqlite]", "unknown table"),
+            (
+                "storage_by_backend = { sqlite = \"integer\" }",
+                "expected `key = \"value\"`",
+            ),
+            (
+                "[storage_by_backend]\n[storage_by_backend]",
+                "duplicate table",
+            ),
+            (
+                "[storage_by_backend]\nvalue_style = \"camelCase\"",
+                "unknown backend",
+            ),
+            (
+                "[storage_by_backend]\nsqlite = \"enum\"",
+                "sqlite has no enum type",
+            ),
+            // a `#` in a value is part of it
+            (
+                "[storage_by_backend]\nmysql = \"text # x\"",
+                "unsupported storage",
+            ),
+            (
+                "[storage_by_backend]\nsqlite = \"text\"\nsqlite = \"text\"",
+                "duplicate key",
+            ),
+        ] {
+            let error = match ConfigDefaults::parse(text, "db-enum.toml") {
+                Ok(_) => panic!("accepted {:?}", text),
+                Err(error) => error.to_string(),
+            };
+            assert!(error.contains(message), "{:?}: {}", text, error);
+        }
+    }
+
+    #[test]
+    fn sql_tokens_of_strings_and_identifiers() {
+        use SqlToken::*;
+        assert_eq!(
+            sql_tokens(r#"CREATE TYPE "App"."My ""Type""" AS ENUM ('it''s', '');"#).unwrap(),
+            vec![
+                Word("create".into()),
+                Word("type".into()),
+                Ident("App".into()),
+                Punct('.'),
+                Ident("My \"Type\"".into()),
+                Word("as".into()),
+                Word("enum".into()),
+                Punct('('),
+                Str("it's".into()),
+                Punct(','),
+                Str("".into()),
+                Punct(')'),
+                Punct(';'),
+            ]
+        );
+        assert_eq!(
+            sql_tokens(r"SELECT E'a\'b''c', e'\\', $1, x$y").unwrap(),
+            vec![
+                Word("select".into()),
+                EscapeStr(r"a\'b''c".into()),
+                Punct(','),
+                EscapeStr(r"\\".into()),
+                Punct(','),
+                Punct('$'),
+                Word("1".into()),
+                Punct(','),
+                Word("x$y".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn sql_tokens_of_dollar_quotes() {
+        use SqlToken::*;
+        assert_eq!(
+            sql_tokens("$$it's -- not a comment$$ $fn$ $$ 'x $fn$ $_a1$$_a1$ $t$$$t$").unwrap(),
+            vec![
+                DollarStr("it's -- not a comment".into()),
+                DollarStr(" $$ 'x ".into()),
+                DollarStr("".into()),
+                // the `$` before the closing tag is part of the string
+                DollarStr("$".into()),
+            ]
+        );
+        assert!(sql_tokens("$$never closed").is_err());
+        assert!(sql_tokens("$fn$ closed by another tag $other$").is_err());
+    }
+
+    #[test]
+    fn sql_tokens_of_comments() {
+        use SqlToken::*;
+        assert_eq!(
+            sql_tokens(
+                "a -- 'not a string\n\
+                 b /* outer /* nested 'x */ still a comment */ c --"
+            )
+            .unwrap(),
+            vec![Word("a".into()), Word("b".into()), Word("c".into())]
+        );
+        assert!(sql_tokens("/* /* */").is_err());
+        assert!(sql_tokens("'unterminated").is_err());
+        assert!(sql_tokens("\"unterminated").is_err());
+        assert!(sql_tokens(r"E'unterminated\'").is_err());
+    }
+
+    #[test]
+    fn create_enum_type_labels() {
+        let sql = r#"
+            -- CREATE TYPE status AS ENUM ('commented');
+            CREATE FUNCTION f() RETURNS void AS $$
+                CREATE TYPE status AS ENUM ('in a function');
+            $$ LANGUAGE sql;
+            CREATE TYPE app.status AS ENUM ('draft', 'it''s', 'Done');
+            CREATE TYPE "Other"."Kind" AS ENUM ('a');
+            CREATE TYPE point AS (x int, y int);
+        "#;
+        let labels = vec!["draft".to_string(), "it's".into(), "Done".into()];
+        assert_eq!(parse_create_enum_type(sql, "status"), Ok(labels.clone()));
+        assert_eq!(parse_create_enum_type(sql, "app.status"), Ok(labels));
+        assert_eq!(
+            parse_create_enum_type(sql, "Other.Kind"),
+            Ok(vec!["a".to_string()])
+        );
+        // unquoted names are lowercased
+        assert!(parse_create_enum_type(sql, "kind").is_err());
+        assert!(parse_create_enum_type(sql, "other.status").is_err());
+        assert_eq!(
+            parse_create_enum_type(sql, "point"),
+            Err("type `point` is not an enum".to_string())
+        );
+    }
+
+    #[test]
+    fn create_enum_type_rejects_what_it_cannot_read() {
+        for (sql, message) in [
+            ("CREATE TYPE s AS ENUM ();", "has no labels"),
+            ("CREATE TYPE s AS ENUM ('a',);", "invalid labels"),
+            ("CREATE TYPE s AS ENUM ('a' 'b');", "invalid labels"),
+            ("CREATE TYPE s AS ENUM (a);", "invalid labels"),
+            ("CREATE TYPE s AS ENUM 'a';", "expected `(`"),
+            (r"CREATE TYPE s AS ENUM (E'a\'b');", "must be plain"),
+            ("CREATE TYPE s AS ENUM ($$a$$);", "must be plain"),
+            (
+                "CREATE TYPE s AS ENUM ('a'); CREATE TYPE x.s AS ENUM ('b');",
+                "more than once",
+            ),
+            (
+                "CREATE TYPE t AS ENUM ('a');",
+                "no `CREATE TYPE s AS ENUM` statement",
+            ),
+            ("CREATE TYPE s AS ENUM ('a);", "unterminated quote"),
+        ] {
+            let error = parse_create_enum_type(sql, "s").unwrap_err();
+            assert!(error.contains(message), "{:?}: {}", sql, error);
+        }
     }
 }
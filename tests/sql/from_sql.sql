@@ -0,0 +1,9 @@
+-- the types read by `db_enum_from_sql!` in src/from_sql.rs
+CREATE TYPE from_sql_priority AS ENUM ('low', 'high');
+
+/* labels with spaces and quotes */
+CREATE TYPE "from_sql_status" AS ENUM (
+    'todo',
+    'in progress', -- being worked on
+    'won''t do'
+);
@@ -0,0 +1,47 @@
+use arbitrary::{Arbitrary, Unstructured};
+
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[DbEnumArbitrary]
+pub enum Status {
+    Active,
+    Suspended,
+    Closed,
+}
+
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[DbEnumArbitrary]
+pub enum Unreleased {
+    #[cfg(any())]
+    Beta,
+}
+
+#[derive(Debug, Arbitrary)]
+pub struct Row {
+    id: i32,
+    status: Status,
+}
+
+#[test]
+fn every_variant_is_reachable() {
+    let mut seen = Vec::new();
+    for byte in 0..=u8::MAX {
+        let status = Status::arbitrary(&mut Unstructured::new(&[byte])).unwrap();
+        if !seen.contains(&status) {
+            seen.push(status);
+        }
+    }
+    assert_eq!(seen.len(), Status::all().len());
+}
+
+#[test]
+fn rows_from_fuzzer_input() {
+    let data = [7u8; 16];
+    let row = Row::arbitrary(&mut Unstructured::new(&data)).unwrap();
+    assert!(Status::all().contains(&row.status));
+}
+
+#[test]
+fn no_variants_is_an_error() {
+    let result = Unreleased::arbitrary(&mut Unstructured::new(&[7u8; 16]));
+    assert!(matches!(result, Err(arbitrary::Error::EmptyChoose)));
+}
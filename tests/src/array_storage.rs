@@ -0,0 +1,106 @@
+use diesel::insert_into;
+use diesel::prelude::*;
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[DbArrayStorage = "json"]
+pub enum JsonTag {
+    Red,
+    Green,
+    #[db_rename = "with \"quotes\""]
+    Quoted,
+}
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[DbArrayStorage = "delimited"]
+#[DbArrayDelimiter = ";"]
+pub enum DelimitedTag {
+    Red,
+    Green,
+    BlueGreen,
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::{JsonTagArrayMapping, DelimitedTagArrayMapping};
+    test_array_storage {
+        id -> Integer,
+        json_tags -> JsonTagArrayMapping,
+        delimited_tags -> DelimitedTagArrayMapping,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, Clone, PartialEq)]
+#[diesel(table_name = test_array_storage)]
+struct TestArrayStorage {
+    id: i32,
+    #[diesel(serialize_as = JsonTagArray)]
+    json_tags: Vec<JsonTag>,
+    #[diesel(serialize_as = DelimitedTagArray)]
+    delimited_tags: Vec<DelimitedTag>,
+}
+
+#[cfg(feature = "mysql")]
+pub fn create_table(conn: &mut MysqlConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TEMPORARY TABLE IF NOT EXISTS test_array_storage (
+            id SERIAL PRIMARY KEY,
+            json_tags JSON NOT NULL,
+            delimited_tags TEXT NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+pub fn create_table(conn: &mut SqliteConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TABLE test_array_storage (
+            id SERIAL PRIMARY KEY,
+            json_tags TEXT NOT NULL,
+            delimited_tags TEXT NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn array_storage_round_trip() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    let data = vec![
+        TestArrayStorage {
+            id: 1,
+            json_tags: vec![JsonTag::Red, JsonTag::Quoted],
+            delimited_tags: vec![DelimitedTag::BlueGreen, DelimitedTag::Red],
+        },
+        TestArrayStorage {
+            id: 2,
+            json_tags: vec![],
+            delimited_tags: vec![],
+        },
+    ];
+    let ct = insert_into(test_array_storage::table)
+        .values(data.clone())
+        .execute(connection)
+        .unwrap();
+    assert_eq!(data.len(), ct);
+    let items = test_array_storage::table
+        .load::<TestArrayStorage>(connection)
+        .unwrap();
+    assert_eq!(data, items);
+
+    let filtered = test_array_storage::table
+        .filter(test_array_storage::json_tags.eq(JsonTagArray(vec![JsonTag::Red, JsonTag::Quoted])))
+        .select(test_array_storage::id)
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(filtered, vec![1]);
+}
@@ -0,0 +1,196 @@
+use diesel::insert_into;
+use diesel::prelude::*;
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[DbEnumAsText]
+pub enum Colour {
+    Red,
+    LightBlue,
+}
+
+// a hand-written schema, declaring the column as plain `Text`
+table! {
+    test_as_text {
+        id -> Integer,
+        colour -> Text,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, Clone, PartialEq)]
+#[diesel(table_name = test_as_text)]
+struct Paint {
+    id: i32,
+    colour: Colour,
+}
+
+#[cfg(feature = "postgres")]
+pub fn create_table(conn: &mut PgConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TABLE test_as_text (
+            id SERIAL PRIMARY KEY,
+            colour TEXT NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "mysql")]
+pub fn create_table(conn: &mut MysqlConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TEMPORARY TABLE IF NOT EXISTS test_as_text (
+            id SERIAL PRIMARY KEY,
+            colour TEXT NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+pub fn create_table(conn: &mut SqliteConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TABLE test_as_text (
+            id SERIAL PRIMARY KEY,
+            colour TEXT NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn text_column_round_trip() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    let data = vec![
+        Paint {
+            id: 1,
+            colour: Colour::LightBlue,
+        },
+        Paint {
+            id: 2,
+            colour: Colour::Red,
+        },
+    ];
+    let ct = insert_into(test_as_text::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    assert_eq!(data.len(), ct);
+    let items = test_as_text::table
+        .order(test_as_text::id)
+        .load::<Paint>(connection)
+        .unwrap();
+    assert_eq!(data, items);
+    let reds = test_as_text::table
+        .filter(test_as_text::colour.eq(Colour::Red))
+        .select(test_as_text::id)
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(reds, vec![2]);
+    let raw: String = test_as_text::table
+        .find(1)
+        .select(test_as_text::colour)
+        .get_result(connection)
+        .unwrap();
+    assert_eq!(raw, "light_blue");
+}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn text_expression_comparison() {
+    use diesel::dsl::sql;
+    use diesel::sql_types::Text;
+    let connection = &mut get_connection();
+    create_table(connection);
+    insert_into(test_as_text::table)
+        .values(&Paint {
+            id: 1,
+            colour: Colour::LightBlue,
+        })
+        .execute(connection)
+        .unwrap();
+    let colour: Colour = test_as_text::table
+        .select(sql::<Text>("lower(upper(colour))"))
+        .filter(sql::<Text>("lower(colour)").eq(Colour::LightBlue))
+        .get_result(connection)
+        .unwrap();
+    assert_eq!(colour, Colour::LightBlue);
+}
+
+#[cfg(feature = "postgres")]
+table! {
+    test_as_text_array {
+        id -> Integer,
+        colours -> Array<Text>,
+        accents -> Nullable<Array<Text>>,
+    }
+}
+
+#[cfg(feature = "postgres")]
+#[derive(Insertable, Queryable, Debug, PartialEq)]
+#[diesel(table_name = test_as_text_array)]
+struct Palette {
+    id: i32,
+    colours: Vec<Colour>,
+    accents: Option<Vec<Colour>>,
+}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn text_array_round_trip() {
+    use diesel::connection::SimpleConnection;
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(
+            "CREATE TABLE test_as_text_array (
+                id SERIAL PRIMARY KEY,
+                colours TEXT[] NOT NULL,
+                accents TEXT[]
+            );",
+        )
+        .unwrap();
+    let data = vec![
+        Palette {
+            id: 1,
+            colours: vec![Colour::Red, Colour::LightBlue],
+            accents: None,
+        },
+        Palette {
+            id: 2,
+            colours: vec![],
+            accents: Some(vec![Colour::LightBlue]),
+        },
+    ];
+    insert_into(test_as_text_array::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let items = test_as_text_array::table
+        .order(test_as_text_array::id)
+        .load::<Palette>(connection)
+        .unwrap();
+    assert_eq!(data, items);
+    let with_red = test_as_text_array::table
+        .filter(test_as_text_array::colours.contains(vec![Colour::Red]))
+        .select(test_as_text_array::id)
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(with_red, vec![1]);
+    let raw: Vec<String> = test_as_text_array::table
+        .find(1)
+        .select(test_as_text_array::colours)
+        .get_result(connection)
+        .unwrap();
+    assert_eq!(raw, vec!["red", "light_blue"]);
+}
@@ -0,0 +1,95 @@
+use diesel::prelude::*;
+use diesel_async::{AsyncConnection, RunQueryDsl, SimpleAsyncConnection};
+
+use crate::common::{sample_data, test_simple, MyEnum, Simple};
+use crate::ord::Severity;
+
+#[cfg(feature = "async-postgres")]
+type Conn = diesel_async::AsyncPgConnection;
+#[cfg(feature = "async-mysql")]
+type Conn = diesel_async::AsyncMysqlConnection;
+
+#[cfg(feature = "async-postgres")]
+async fn get_connection() -> Conn {
+    let database_url =
+        ::std::env::var("PG_TEST_DATABASE_URL").expect("Env var PG_TEST_DATABASE_URL not set");
+    let mut conn = Conn::establish(&database_url)
+        .await
+        .expect(&format!("Failed to connect to {}", database_url));
+    conn.batch_execute("SET search_path TO pg_temp;")
+        .await
+        .unwrap();
+    conn.batch_execute(
+        r#"
+        CREATE TYPE my_enum AS ENUM ('foo', 'bar', 'baz_quxx');
+        CREATE TABLE test_simple (
+            id SERIAL PRIMARY KEY,
+            my_enum my_enum NOT NULL
+        );
+    "#,
+    )
+    .await
+    .unwrap();
+    conn
+}
+
+#[cfg(feature = "async-mysql")]
+async fn get_connection() -> Conn {
+    let database_url = ::std::env::var("MYSQL_TEST_DATABASE_URL")
+        .expect("Env var MYSQL_TEST_DATABASE_URL not set");
+    let mut conn = Conn::establish(&database_url)
+        .await
+        .expect(&format!("Failed to connect to {}", database_url));
+    conn.batch_execute(
+        r#"
+        CREATE TEMPORARY TABLE IF NOT EXISTS test_simple (
+            id SERIAL PRIMARY KEY,
+            my_enum enum('foo', 'bar', 'baz_quxx') NOT NULL
+        );
+    "#,
+    )
+    .await
+    .unwrap();
+    conn
+}
+
+#[tokio::test]
+async fn async_enum_round_trip() {
+    // spawning requires the query futures to be `Send`
+    tokio::spawn(async {
+        let connection = &mut get_connection().await;
+        let data = sample_data();
+        let ct = diesel::insert_into(test_simple::table)
+            .values(&data)
+            .execute(connection)
+            .await
+            .unwrap();
+        assert_eq!(data.len(), ct);
+        let items = test_simple::table.load::<Simple>(connection).await.unwrap();
+        assert_eq!(data, items);
+        let foos: i64 = test_simple::table
+            .filter(test_simple::my_enum.eq(MyEnum::Foo))
+            .count()
+            .get_result(connection)
+            .await
+            .unwrap();
+        assert_eq!(foos, 3);
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+#[cfg(feature = "async-postgres")]
+async fn async_order_matches_db() {
+    let connection = &mut get_connection().await;
+    connection
+        .batch_execute("CREATE TYPE severity AS ENUM ('low', 'high', 'medium');")
+        .await
+        .unwrap();
+    let db_order: Vec<String> = Severity::db_order_query()
+        .get_result(connection)
+        .await
+        .unwrap();
+    Severity::check_db_order(&db_order).unwrap();
+}
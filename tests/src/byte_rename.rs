@@ -0,0 +1,76 @@
+use diesel::connection::SimpleConnection;
+use diesel::insert_into;
+use diesel::prelude::*;
+
+use crate::common::get_connection;
+
+// codes from a latin-1 system, which aren't valid UTF-8
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[DbStorageByBackend(mysql = "text")]
+pub enum Season {
+    #[db_rename = b"\xE9t\xE9"]
+    Summer,
+    #[db_rename = b"hiver"]
+    Winter,
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::SeasonMapping;
+    test_byte_rename {
+        id -> Integer,
+        season -> SeasonMapping,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_byte_rename)]
+struct Holiday {
+    id: i32,
+    season: Season,
+}
+
+#[test]
+fn byte_rename_str_helpers() {
+    assert!(Season::Summer.matches_db_str("\\xE9t\\xE9"));
+    assert!(Season::Winter.matches_db_str("hiver"));
+}
+
+#[test]
+fn byte_rename_round_trip() {
+    let connection = &mut get_connection();
+    // a binary column, so the connection's character set doesn't convert the bytes
+    connection
+        .batch_execute(
+            "CREATE TEMPORARY TABLE IF NOT EXISTS test_byte_rename (
+                id SERIAL PRIMARY KEY,
+                season VARBINARY(10) NOT NULL
+            );",
+        )
+        .unwrap();
+    let data = vec![
+        Holiday {
+            id: 1,
+            season: Season::Summer,
+        },
+        Holiday {
+            id: 2,
+            season: Season::Winter,
+        },
+    ];
+    insert_into(test_byte_rename::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let stored = test_byte_rename::table
+        .order(test_byte_rename::id)
+        .select(diesel::dsl::sql::<diesel::sql_types::Binary>("season"))
+        .load::<Vec<u8>>(connection)
+        .unwrap();
+    assert_eq!(stored, vec![b"\xE9t\xE9".to_vec(), b"hiver".to_vec()]);
+    let items = test_byte_rename::table
+        .order(test_byte_rename::id)
+        .load::<Holiday>(connection)
+        .unwrap();
+    assert_eq!(data, items);
+}
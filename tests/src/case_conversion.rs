@@ -0,0 +1,181 @@
+#![allow(clippy::upper_case_acronyms)]
+
+// The db values of each `DbValueStyle`, for variant names which split into words in
+// different ways. These must never change, as they are stored in existing databases.
+
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[DbValueStyle = "snake_case"]
+pub enum SnakeWords {
+    HTTPServer,
+    fooBar,
+    _Leading,
+    ABC,
+    Foo2Bar,
+    Version2,
+    XMLHttpRequest,
+    already_snake,
+    SCREAMING_CASE,
+    a__b,
+}
+
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[DbValueStyle = "kebab-case"]
+pub enum KebabWords {
+    HTTPServer,
+    fooBar,
+    _Leading,
+    ABC,
+    Foo2Bar,
+    Version2,
+    XMLHttpRequest,
+    already_snake,
+    SCREAMING_CASE,
+    a__b,
+}
+
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum ScreamingSnakeWords {
+    HTTPServer,
+    fooBar,
+    _Leading,
+    ABC,
+    Foo2Bar,
+    Version2,
+    XMLHttpRequest,
+    already_snake,
+    SCREAMING_CASE,
+    a__b,
+}
+
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[DbValueStyle = "camelCase"]
+pub enum CamelWords {
+    HTTPServer,
+    fooBar,
+    _Leading,
+    ABC,
+    Foo2Bar,
+    Version2,
+    XMLHttpRequest,
+    already_snake,
+    SCREAMING_CASE,
+    a__b,
+}
+
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[DbValueStyle = "PascalCase"]
+pub enum PascalWords {
+    HTTPServer,
+    fooBar,
+    _Leading,
+    ABC,
+    Foo2Bar,
+    Version2,
+    XMLHttpRequest,
+    already_snake,
+    SCREAMING_CASE,
+    a__b,
+}
+
+fn db_values<T: 'static>(all: &[T], to_sql_literal: fn(&T) -> String) -> Vec<String> {
+    all.iter().map(to_sql_literal).collect()
+}
+
+#[test]
+fn snake_words() {
+    assert_eq!(
+        db_values(SnakeWords::all(), SnakeWords::to_sql_literal),
+        [
+            "'http_server'",
+            "'foo_bar'",
+            "'leading'",
+            "'abc'",
+            "'foo2_bar'",
+            "'version2'",
+            "'xml_http_request'",
+            "'already_snake'",
+            "'screaming_case'",
+            "'a_b'",
+        ]
+    );
+}
+
+#[test]
+fn kebab_words() {
+    assert_eq!(
+        db_values(KebabWords::all(), KebabWords::to_sql_literal),
+        [
+            "'http-server'",
+            "'foo-bar'",
+            "'leading'",
+            "'abc'",
+            "'foo2-bar'",
+            "'version2'",
+            "'xml-http-request'",
+            "'already-snake'",
+            "'screaming-case'",
+            "'a-b'",
+        ]
+    );
+}
+
+#[test]
+fn screaming_snake_words() {
+    assert_eq!(
+        db_values(
+            ScreamingSnakeWords::all(),
+            ScreamingSnakeWords::to_sql_literal
+        ),
+        [
+            "'HTTP_SERVER'",
+            "'FOO_BAR'",
+            "'LEADING'",
+            "'ABC'",
+            "'FOO2_BAR'",
+            "'VERSION2'",
+            "'XML_HTTP_REQUEST'",
+            "'ALREADY_SNAKE'",
+            "'SCREAMING_CASE'",
+            "'A_B'",
+        ]
+    );
+}
+
+#[test]
+fn camel_words() {
+    assert_eq!(
+        db_values(CamelWords::all(), CamelWords::to_sql_literal),
+        [
+            "'httpServer'",
+            "'fooBar'",
+            "'leading'",
+            "'abc'",
+            "'foo2Bar'",
+            "'version2'",
+            "'xmlHttpRequest'",
+            "'alreadySnake'",
+            "'screamingCase'",
+            "'aB'",
+        ]
+    );
+}
+
+#[test]
+fn pascal_words() {
+    assert_eq!(
+        db_values(PascalWords::all(), PascalWords::to_sql_literal),
+        [
+            "'HttpServer'",
+            "'FooBar'",
+            "'Leading'",
+            "'Abc'",
+            "'Foo2Bar'",
+            "'Version2'",
+            "'XmlHttpRequest'",
+            "'AlreadySnake'",
+            "'ScreamingCase'",
+            "'AB'",
+        ]
+    );
+}
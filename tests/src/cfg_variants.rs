@@ -0,0 +1,49 @@
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::Text;
+
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Eq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(dsl, ord, index, str_eq, as_text, generate_tests)]
+pub enum Feature {
+    Basic,
+    // never compiled, e.g. behind a disabled cargo feature
+    #[cfg(any())]
+    Experimental,
+    #[cfg(all())]
+    Stable,
+}
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[DbStorageByBackend(postgres = "integer", mysql = "integer", sqlite = "integer")]
+pub enum Level {
+    Low,
+    #[cfg(any())]
+    Medium,
+    High,
+}
+
+#[test]
+fn cfg_disabled_variants_are_skipped() {
+    assert_eq!(Feature::all(), &[Feature::Basic, Feature::Stable]);
+    assert_eq!(Feature::Stable.to_sql_literal(), "'stable'");
+    assert!(Feature::Basic < Feature::Stable);
+    assert_eq!(Feature::Stable.index(), 1);
+    assert_eq!(Feature::try_from(2), Err(2));
+    assert_eq!(Level::all(), &[Level::Low, Level::High]);
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn cfg_disabled_values_are_not_read() {
+    let connection = &mut get_connection();
+    let read = diesel::select(sql::<Text>("'stable'"))
+        .get_result::<Feature>(connection)
+        .unwrap();
+    assert_eq!(read, Feature::Stable);
+    assert!(diesel::select(sql::<Text>("'experimental'"))
+        .get_result::<Feature>(connection)
+        .is_err());
+}
@@ -0,0 +1,125 @@
+use diesel::insert_into;
+use diesel::prelude::*;
+
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[DbCharWidth = 4]
+pub enum Region {
+    Us,
+    Eu,
+    Apac,
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::RegionMapping;
+    test_char_width {
+        id -> Integer,
+        region -> RegionMapping,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, Clone, PartialEq)]
+#[diesel(table_name = test_char_width)]
+struct Office {
+    id: i32,
+    region: Region,
+}
+
+#[cfg(feature = "postgres")]
+pub fn create_table(conn: &mut PgConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TABLE test_char_width (
+            id SERIAL PRIMARY KEY,
+            region CHAR(4) NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "mysql")]
+pub fn create_table(conn: &mut MysqlConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(&format!(
+        "CREATE TEMPORARY TABLE IF NOT EXISTS test_char_width (
+            id SERIAL PRIMARY KEY,
+            region {} NOT NULL
+        );",
+        Region::MYSQL_COLUMN_TYPE
+    ))
+    .unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+pub fn create_table(conn: &mut SqliteConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TABLE test_char_width (
+            id SERIAL PRIMARY KEY,
+            region CHAR(4) NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn char_width_pads_and_trims() {
+    use diesel::dsl::sql;
+    use diesel::sql_types::Integer;
+    let connection = &mut get_connection();
+    create_table(connection);
+    let data = vec![
+        Office {
+            id: 1,
+            region: Region::Us,
+        },
+        Office {
+            id: 2,
+            region: Region::Apac,
+        },
+    ];
+    insert_into(test_char_width::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let items = test_char_width::table
+        .order(test_char_width::id)
+        .load::<Office>(connection)
+        .unwrap();
+    assert_eq!(data, items);
+    let us = test_char_width::table
+        .filter(test_char_width::region.eq(Region::Us))
+        .select(test_char_width::id)
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(us, vec![1]);
+    // other conversions keep the unpadded value
+    assert_eq!(Region::Us.to_sql_literal(), "'us'");
+    // mysql strips the padding of `CHAR` columns when reading, and postgres when
+    // converting them to text, so only the stored length shows it
+    let stored_len = if cfg!(feature = "postgres") {
+        "octet_length(region)"
+    } else {
+        "length(region)"
+    };
+    let len = test_char_width::table
+        .find(1)
+        .select(sql::<Integer>(stored_len))
+        .get_result::<i32>(connection)
+        .unwrap();
+    assert_eq!(len, 4);
+}
+
+#[test]
+#[cfg(feature = "mysql")]
+fn char_width_column_type() {
+    assert_eq!(Region::MYSQL_COLUMN_TYPE, "CHAR(4)");
+}
@@ -0,0 +1,34 @@
+use clap::ValueEnum;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[DbEnumClap]
+#[DbValueStyle = "kebab-case"]
+pub enum Environment {
+    Staging,
+    Production,
+    #[db_rename = "dev"]
+    Development,
+    #[db_null_variant]
+    Unset,
+}
+
+#[test]
+fn value_names_are_db_values() {
+    let names: Vec<String> = Environment::value_variants()
+        .iter()
+        .filter_map(|variant| variant.to_possible_value())
+        .map(|value| value.get_name().to_string())
+        .collect();
+    assert_eq!(names, vec!["staging", "production", "dev"]);
+    assert_eq!(
+        Environment::from_str("dev", false),
+        Ok(Environment::Development)
+    );
+    assert!(Environment::from_str("Development", false).is_err());
+}
+
+#[test]
+fn null_variant_is_not_a_value() {
+    assert!(!Environment::value_variants().contains(&Environment::Unset));
+    assert!(Environment::from_str("unset", false).is_err());
+}
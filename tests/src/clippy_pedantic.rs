@@ -0,0 +1,30 @@
+//! The generated code is checked with `cargo clippy` in CI, which fails on any pedantic
+//! lint firing inside the expansion
+#![deny(clippy::pedantic)]
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, diesel_derive_enum::DbEnum)]
+#[DbEnumDsl]
+#[DbEnumOrd]
+#[DbEnumStrEq]
+#[DbEnumAsText]
+#[db_enum(text_mapping, str, index, generate_tests)]
+#[cfg_attr(any(feature = "postgres", feature = "mysql"), DbEnumJson)]
+#[cfg_attr(any(feature = "mysql", feature = "sqlite"), DbArrayStorage = "json")]
+#[cfg_attr(feature = "clap", DbEnumClap)]
+#[cfg_attr(feature = "rocket", DbEnumRocket)]
+#[cfg_attr(feature = "postgres-types", DbEnumPostgresTypes)]
+pub enum Pedantic {
+    Foo,
+    BarBaz,
+    #[db_null_variant]
+    Unknown,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, diesel_derive_enum::DbEnum)]
+#[DbStorageByBackend(postgres = "integer", mysql = "bigint", sqlite = "integer")]
+#[DbValueStyleFn = "str::to_owned"]
+pub enum PedanticNumbered {
+    One,
+    #[db_value = 5]
+    Five,
+}
@@ -0,0 +1,111 @@
+use diesel::insert_into;
+use diesel::prelude::*;
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[DbEnumCodegen(inline, minimal)]
+pub enum Lean {
+    Small,
+    Tiny,
+}
+
+table! {
+    use diesel::sql_types::{Integer, Nullable};
+    use super::LeanMapping;
+    test_codegen {
+        id -> Integer,
+        lean -> LeanMapping,
+        maybe_lean -> Nullable<LeanMapping>,
+    }
+}
+
+// inserting by reference and `Option` fields only need the minimal impls
+#[derive(Insertable, Queryable, Identifiable, Debug, Clone, PartialEq)]
+#[diesel(table_name = test_codegen)]
+struct LeanRow {
+    id: i32,
+    lean: Lean,
+    maybe_lean: Option<Lean>,
+}
+
+#[cfg(feature = "postgres")]
+pub fn create_table(conn: &mut PgConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TYPE lean AS ENUM ('small', 'tiny');
+        CREATE TABLE test_codegen (
+            id SERIAL PRIMARY KEY,
+            lean lean NOT NULL,
+            maybe_lean lean
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "mysql")]
+pub fn create_table(conn: &mut MysqlConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TEMPORARY TABLE IF NOT EXISTS test_codegen (
+            id SERIAL PRIMARY KEY,
+            lean enum('small', 'tiny') NOT NULL,
+            maybe_lean enum('small', 'tiny')
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+pub fn create_table(conn: &mut SqliteConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TABLE test_codegen (
+            id SERIAL PRIMARY KEY,
+            lean TEXT CHECK(lean IN ('small', 'tiny')) NOT NULL,
+            maybe_lean TEXT CHECK(maybe_lean IN ('small', 'tiny'))
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn minimal_codegen_round_trip() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    let data = vec![
+        LeanRow {
+            id: 1,
+            lean: Lean::Small,
+            maybe_lean: None,
+        },
+        LeanRow {
+            id: 2,
+            lean: Lean::Tiny,
+            maybe_lean: Some(Lean::Small),
+        },
+    ];
+    let ct = insert_into(test_codegen::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    assert_eq!(data.len(), ct);
+    let items = test_codegen::table
+        .order(test_codegen::id)
+        .load::<LeanRow>(connection)
+        .unwrap();
+    assert_eq!(data, items);
+    let tiny = test_codegen::table
+        .filter(test_codegen::lean.eq(Lean::Tiny))
+        .select(test_codegen::id)
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(tiny, vec![2]);
+}
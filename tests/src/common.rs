@@ -4,6 +4,7 @@ use diesel_derive_enum::DbEnum;
 use diesel::connection::SimpleConnection;
 
 #[derive(Debug, PartialEq, DbEnum, Clone)]
+#[DbEnumDsl]
 pub enum MyEnum {
     Foo,
     Bar,
@@ -0,0 +1,101 @@
+//! A minimal third-party backend, to check the impls generated by `DbCustomBackend`
+
+use diesel::backend::{sql_dialect, Backend, DieselReserveSpecialization, SqlDialect, TrustedBackend};
+use diesel::deserialize::{self, FromSql};
+use diesel::query_builder::bind_collector::RawBytesBindCollector;
+use diesel::query_builder::{BindCollector, QueryBuilder};
+use diesel::sql_types::{self, HasSqlType, TypeMetadata};
+use diesel::QueryResult;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TextBackend;
+
+#[derive(Default)]
+pub struct TextQueryBuilder(String);
+
+impl QueryBuilder<TextBackend> for TextQueryBuilder {
+    fn push_sql(&mut self, sql: &str) {
+        self.0.push_str(sql);
+    }
+
+    fn push_identifier(&mut self, identifier: &str) -> QueryResult<()> {
+        self.0.push_str(identifier);
+        Ok(())
+    }
+
+    fn push_bind_param(&mut self) {
+        self.0.push('?');
+    }
+
+    fn finish(self) -> String {
+        self.0
+    }
+}
+
+impl Backend for TextBackend {
+    type QueryBuilder = TextQueryBuilder;
+    type RawValue<'a> = &'a [u8];
+    type BindCollector<'a> = RawBytesBindCollector<TextBackend>;
+}
+
+impl TypeMetadata for TextBackend {
+    type TypeMetadata = ();
+    type MetadataLookup = ();
+}
+
+impl SqlDialect for TextBackend {
+    type ReturningClause = sql_dialect::returning_clause::DoesNotSupportReturningClause;
+    type OnConflictClause = sql_dialect::on_conflict_clause::DoesNotSupportOnConflictClause;
+    type InsertWithDefaultKeyword = sql_dialect::default_keyword_for_insert::IsoSqlDefaultKeyword;
+    type BatchInsertSupport = sql_dialect::batch_insert_support::PostgresLikeBatchInsertSupport;
+    type ConcatClause = sql_dialect::concat_clause::ConcatWithPipesClause;
+    type DefaultValueClauseForInsert = sql_dialect::default_value_clause::AnsiDefaultValueClause;
+    type EmptyFromClauseSyntax = sql_dialect::from_clause_syntax::AnsiSqlFromClauseSyntax;
+    type ExistsSyntax = sql_dialect::exists_syntax::AnsiSqlExistsSyntax;
+    type ArrayComparison = sql_dialect::array_comparison::AnsiSqlArrayComparison;
+    type SelectStatementSyntax = sql_dialect::select_statement_syntax::AnsiSqlSelectStatement;
+}
+
+impl TrustedBackend for TextBackend {}
+impl DieselReserveSpecialization for TextBackend {}
+
+macro_rules! has_sql_type {
+    ($($ty:ident),*) => {
+        $(
+            impl HasSqlType<sql_types::$ty> for TextBackend {
+                fn metadata(_: &mut ()) {}
+            }
+        )*
+    };
+}
+
+has_sql_type!(SmallInt, Integer, BigInt, Float, Double, Text, Binary, Date, Time, Timestamp);
+
+impl FromSql<sql_types::Text, TextBackend> for String {
+    fn from_sql(bytes: &[u8]) -> deserialize::Result<Self> {
+        Ok(String::from_utf8(bytes.to_vec())?)
+    }
+}
+
+// `str: ToSql<Text, _>` is provided by diesel for backends using `RawBytesBindCollector`
+
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[DbCustomBackend = "crate::custom_backend::TextBackend"]
+pub enum Fruit {
+    Apple,
+    #[db_rename = "pear!"]
+    Pear,
+}
+
+#[test]
+fn custom_backend_round_trip() {
+    let mut collector = RawBytesBindCollector::<TextBackend>::new();
+    collector
+        .push_bound_value::<FruitMapping, _>(&Fruit::Pear, &mut ())
+        .unwrap();
+    assert_eq!(collector.binds, vec![Some(b"pear!".to_vec())]);
+
+    let apple = <Fruit as FromSql<FruitMapping, TextBackend>>::from_sql(b"apple").unwrap();
+    assert_eq!(apple, Fruit::Apple);
+    assert!(<Fruit as FromSql<FruitMapping, TextBackend>>::from_sql(b"banana").is_err());
+}
@@ -0,0 +1,72 @@
+use diesel::prelude::*;
+
+// all of the type attributes can also be given as a single `db_enum` list
+
+#[derive(Debug, PartialEq, Eq, diesel_derive_enum::DbEnum)]
+#[db_enum(
+    pg_type = "combined_enum",
+    diesel_type = "CombinedMapping",
+    value_style(camelCase),
+    dsl,
+    ord,
+    strict_attrs,
+    generate_tests
+)]
+pub enum Combined {
+    FirstVariant,
+    SecondVariant,
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::CombinedMapping;
+    test_db_enum_attr {
+        id -> Integer,
+        combined -> CombinedMapping,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_db_enum_attr)]
+struct TestCombined {
+    id: i32,
+    combined: Combined,
+}
+
+#[test]
+fn db_enum_attr_options() {
+    assert!(Combined::FirstVariant < Combined::SecondVariant);
+    let _filter = test_db_enum_attr::table.filter(combined_dsl::is_second_variant(
+        test_db_enum_attr::combined,
+    ));
+}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn db_enum_attr_round_trip() {
+    use crate::common::get_connection;
+    use diesel::connection::SimpleConnection;
+    use diesel::insert_into;
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(
+            r#"
+        CREATE TYPE combined_enum AS ENUM ('firstVariant', 'secondVariant');
+        CREATE TABLE test_db_enum_attr (
+            id SERIAL PRIMARY KEY,
+            combined combined_enum NOT NULL
+        );
+    "#,
+        )
+        .unwrap();
+    let data = vec![TestCombined {
+        id: 1,
+        combined: Combined::SecondVariant,
+    }];
+    let inserted = insert_into(test_db_enum_attr::table)
+        .values(&data)
+        .get_results(connection)
+        .unwrap();
+    assert_eq!(data, inserted);
+    Combined::assert_order_matches_db(connection).unwrap();
+}
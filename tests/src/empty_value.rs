@@ -0,0 +1,54 @@
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::Text;
+
+use crate::common::get_connection;
+
+// mysql cannot store an empty enum value, see `db_empty_fallback` below
+#[cfg(not(feature = "mysql"))]
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[DbEnumAsText]
+pub enum Answer {
+    Yes,
+    No,
+    #[db_rename = ""]
+    Blank,
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres"))]
+fn empty_db_value_round_trip() {
+    let connection = &mut get_connection();
+    assert_eq!(Answer::Blank.to_sql_literal(), "''");
+    let read = diesel::select(sql::<Text>("''"))
+        .get_result::<Answer>(connection)
+        .unwrap();
+    assert_eq!(read, Answer::Blank);
+}
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[DbEnumAsText]
+pub enum Reply {
+    Yes,
+    No,
+    #[db_empty_fallback]
+    Invalid,
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn empty_string_is_read_as_fallback() {
+    let connection = &mut get_connection();
+    let read = diesel::select(sql::<Text>("''"))
+        .get_result::<Reply>(connection)
+        .unwrap();
+    assert_eq!(read, Reply::Invalid);
+    // the fallback variant still has its own db value
+    let read = diesel::select(sql::<Text>("'invalid'"))
+        .get_result::<Reply>(connection)
+        .unwrap();
+    assert_eq!(read, Reply::Invalid);
+    assert!(diesel::select(sql::<Text>("'maybe'"))
+        .get_result::<Reply>(connection)
+        .is_err());
+}
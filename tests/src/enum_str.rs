@@ -0,0 +1,131 @@
+use diesel::insert_into;
+use diesel::prelude::*;
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[DbEnumStr]
+pub enum Shipping {
+    Standard,
+    Express,
+}
+
+// the database already knows `same_day`, the enum doesn't yet
+table! {
+    use diesel::sql_types::{Integer, Nullable};
+    use super::ShippingMapping;
+    test_enum_str {
+        id -> Integer,
+        shipping -> ShippingMapping,
+        fallback -> Nullable<ShippingMapping>,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, Clone, PartialEq)]
+#[diesel(table_name = test_enum_str)]
+struct Order {
+    id: i32,
+    shipping: ShippingStr,
+    fallback: Option<ShippingStr>,
+}
+
+#[cfg(feature = "postgres")]
+pub fn create_table(conn: &mut PgConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TYPE shipping AS ENUM ('standard', 'express', 'same_day');
+        CREATE TABLE test_enum_str (
+            id SERIAL PRIMARY KEY,
+            shipping shipping NOT NULL,
+            fallback shipping
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "mysql")]
+pub fn create_table(conn: &mut MysqlConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TEMPORARY TABLE IF NOT EXISTS test_enum_str (
+            id SERIAL PRIMARY KEY,
+            shipping enum('standard', 'express', 'same_day') NOT NULL,
+            fallback enum('standard', 'express', 'same_day')
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+pub fn create_table(conn: &mut SqliteConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TABLE test_enum_str (
+            id SERIAL PRIMARY KEY,
+            shipping TEXT CHECK(shipping IN ('standard', 'express', 'same_day')) NOT NULL,
+            fallback TEXT CHECK(fallback IN ('standard', 'express', 'same_day'))
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn enum_str_validates_on_construction() {
+    let express = ShippingStr::new("express").unwrap();
+    assert_eq!(express.as_str(), "express");
+    assert_eq!(express.known(), Some(Shipping::Express));
+    assert_eq!(express, ShippingStr::from(Shipping::Express));
+    assert_eq!(ShippingStr::new("same_day"), Err("same_day".to_string()));
+    let same_day = ShippingStr::new_unchecked("same_day");
+    assert_eq!(same_day.known(), None);
+    assert_eq!(same_day.to_string(), "same_day");
+    assert_eq!(String::from(same_day), "same_day");
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn enum_str_round_trip_unknown_values() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    let data = vec![
+        Order {
+            id: 1,
+            shipping: Shipping::Standard.into(),
+            fallback: None,
+        },
+        Order {
+            id: 2,
+            shipping: ShippingStr::new_unchecked("same_day"),
+            fallback: Some(Shipping::Express.into()),
+        },
+    ];
+    let ct = insert_into(test_enum_str::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    assert_eq!(data.len(), ct);
+    let items = test_enum_str::table
+        .order(test_enum_str::id)
+        .load::<Order>(connection)
+        .unwrap();
+    assert_eq!(data, items);
+    // the strict enum can still be used for the same column
+    let standard = test_enum_str::table
+        .filter(test_enum_str::shipping.eq(Shipping::Standard))
+        .select(test_enum_str::shipping)
+        .first::<Shipping>(connection)
+        .unwrap();
+    assert_eq!(standard, Shipping::Standard);
+    let unknown = test_enum_str::table
+        .filter(test_enum_str::shipping.eq(ShippingStr::new_unchecked("same_day")))
+        .select(test_enum_str::id)
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(unknown, vec![2]);
+}
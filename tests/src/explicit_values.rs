@@ -0,0 +1,218 @@
+use diesel::connection::SimpleConnection;
+use diesel::insert_into;
+use diesel::prelude::*;
+
+use crate::common::get_connection;
+
+// codes from a legacy system, which skipped 1 and started errors at -1
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[DbStorageByBackend(postgres = "integer", mysql = "integer", sqlite = "integer")]
+pub enum Status {
+    #[db_value(-1)]
+    Failed,
+    Pending,
+    #[db_value = 2]
+    Running,
+    Done,
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::StatusMapping;
+    test_explicit_values {
+        id -> Integer,
+        status -> StatusMapping,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, Clone, PartialEq)]
+#[diesel(table_name = test_explicit_values)]
+struct Job {
+    id: i32,
+    status: Status,
+}
+
+fn create_table(conn: &mut impl SimpleConnection) {
+    conn.batch_execute(
+        r#"
+        CREATE TEMPORARY TABLE test_explicit_values (
+            id INTEGER PRIMARY KEY,
+            status INTEGER NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[test]
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+fn explicit_values_are_stored() {
+    use diesel::dsl::sql;
+    use diesel::sql_types::Integer;
+    let connection = &mut get_connection();
+    create_table(connection);
+    let data = vec![
+        Job {
+            id: 1,
+            status: Status::Failed,
+        },
+        Job {
+            id: 2,
+            status: Status::Pending,
+        },
+        Job {
+            id: 3,
+            status: Status::Running,
+        },
+        Job {
+            id: 4,
+            status: Status::Done,
+        },
+    ];
+    insert_into(test_explicit_values::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let raw = test_explicit_values::table
+        .select(sql::<Integer>("status"))
+        .order(test_explicit_values::id)
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(raw, vec![-1, 0, 2, 3]);
+    let items = test_explicit_values::table
+        .order(test_explicit_values::id)
+        .load::<Job>(connection)
+        .unwrap();
+    assert_eq!(data, items);
+}
+
+// the same codes, in a 64 bit column
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[DbStorageByBackend(postgres = "bigint", mysql = "bigint", sqlite = "bigint")]
+pub enum WideStatus {
+    #[db_value(-1)]
+    Failed,
+    Pending,
+    #[db_value = 2]
+    Running,
+    Done,
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::WideStatusMapping;
+    test_explicit_values_bigint {
+        id -> Integer,
+        status -> WideStatusMapping,
+    }
+}
+
+#[test]
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+fn bigint_storage_round_trip() {
+    use diesel::dsl::sql;
+    use diesel::sql_types::BigInt;
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(
+            r#"
+        CREATE TEMPORARY TABLE test_explicit_values_bigint (
+            id INTEGER PRIMARY KEY,
+            status BIGINT NOT NULL
+        );
+    "#,
+        )
+        .unwrap();
+    let data = vec![
+        (1, WideStatus::Failed),
+        (2, WideStatus::Running),
+        (3, WideStatus::Done),
+    ];
+    insert_into(test_explicit_values_bigint::table)
+        .values(
+            data.iter()
+                .map(|(id, status)| {
+                    (
+                        test_explicit_values_bigint::id.eq(*id),
+                        test_explicit_values_bigint::status.eq(status.clone()),
+                    )
+                })
+                .collect::<Vec<_>>(),
+        )
+        .execute(connection)
+        .unwrap();
+    let raw = test_explicit_values_bigint::table
+        .select(sql::<BigInt>("status"))
+        .order(test_explicit_values_bigint::id)
+        .load::<i64>(connection)
+        .unwrap();
+    assert_eq!(raw, vec![-1, 2, 3]);
+    let items = test_explicit_values_bigint::table
+        .order(test_explicit_values_bigint::id)
+        .load::<(i32, WideStatus)>(connection)
+        .unwrap();
+    assert_eq!(data, items);
+}
+
+// mysql schemas often use unsigned columns for such codes
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[DbStorageByBackend(mysql = "unsigned_smallint")]
+pub enum Shelf {
+    #[db_value = 100]
+    Top,
+    Middle,
+    #[db_value = 65535]
+    Floor,
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::ShelfMapping;
+    test_explicit_values_unsigned {
+        id -> Integer,
+        shelf -> ShelfMapping,
+    }
+}
+
+#[test]
+#[cfg(feature = "mysql")]
+fn mysql_unsigned_storage_round_trip() {
+    use diesel::dsl::sql;
+    use diesel::sql_types::{SmallInt, Unsigned};
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(
+            r#"
+        CREATE TEMPORARY TABLE test_explicit_values_unsigned (
+            id INTEGER PRIMARY KEY,
+            shelf SMALLINT UNSIGNED NOT NULL
+        );
+    "#,
+        )
+        .unwrap();
+    let data = vec![(1, Shelf::Middle), (2, Shelf::Floor)];
+    insert_into(test_explicit_values_unsigned::table)
+        .values(
+            data.iter()
+                .map(|(id, shelf)| {
+                    (
+                        test_explicit_values_unsigned::id.eq(*id),
+                        test_explicit_values_unsigned::shelf.eq(shelf.clone()),
+                    )
+                })
+                .collect::<Vec<_>>(),
+        )
+        .execute(connection)
+        .unwrap();
+    let raw = test_explicit_values_unsigned::table
+        .select(sql::<Unsigned<SmallInt>>("shelf"))
+        .order(test_explicit_values_unsigned::id)
+        .load::<u16>(connection)
+        .unwrap();
+    assert_eq!(raw, vec![101, 65535]);
+    let items = test_explicit_values_unsigned::table
+        .order(test_explicit_values_unsigned::id)
+        .load::<(i32, Shelf)>(connection)
+        .unwrap();
+    assert_eq!(data, items);
+}
@@ -0,0 +1,35 @@
+use fake::{Dummy, Fake, Faker};
+
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[DbEnumFake]
+pub enum Status {
+    Active,
+    Suspended,
+    Closed,
+}
+
+#[derive(Debug, Dummy)]
+pub struct Account {
+    #[dummy(faker = "1..1000")]
+    id: i32,
+    status: Status,
+}
+
+#[test]
+fn every_variant_is_generated() {
+    let mut seen = Vec::new();
+    for _ in 0..1000 {
+        let status: Status = Faker.fake();
+        if !seen.contains(&status) {
+            seen.push(status);
+        }
+    }
+    assert_eq!(seen.len(), Status::all().len());
+}
+
+#[test]
+fn fake_rows() {
+    let account: Account = Faker.fake();
+    assert!(Status::all().contains(&account.status));
+    assert!((1..1000).contains(&account.id));
+}
@@ -0,0 +1,46 @@
+use diesel_derive_enum::db_enum_from_sql;
+
+#[cfg(feature = "postgres")]
+use crate::common::get_connection;
+
+db_enum_from_sql!("sql/from_sql.sql", "from_sql_status");
+db_enum_from_sql!(
+    #[derive(Hash)]
+    "sql/from_sql.sql",
+    "from_sql_priority",
+);
+
+#[test]
+fn variants_from_labels() {
+    assert_eq!(
+        FromSqlStatus::all(),
+        [FromSqlStatus::Todo, FromSqlStatus::InProgress, FromSqlStatus::WonTDo]
+    );
+    assert_eq!(FromSqlStatus::InProgress.to_sql_literal(), "'in progress'");
+    assert_eq!(FromSqlStatus::WonTDo.to_sql_literal(), "'won''t do'");
+    assert_eq!(
+        FromSqlPriority::all(),
+        [FromSqlPriority::Low, FromSqlPriority::High]
+    );
+}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn round_trip_through_sql_type() {
+    use diesel::connection::SimpleConnection;
+    use diesel::dsl::sql;
+    use diesel::prelude::*;
+
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(include_str!("../sql/from_sql.sql"))
+        .unwrap();
+    let value = diesel::select(sql::<FromSqlStatusMapping>("'won''t do'::from_sql_status"))
+        .get_result::<FromSqlStatus>(connection)
+        .unwrap();
+    assert_eq!(value, FromSqlStatus::WonTDo);
+    let value = diesel::select(FromSqlStatus::InProgress.into_sql::<FromSqlStatusMapping>())
+        .get_result::<FromSqlStatus>(connection)
+        .unwrap();
+    assert_eq!(value, FromSqlStatus::InProgress);
+}
@@ -0,0 +1,22 @@
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[DbEnumI18n]
+pub enum UserStatus {
+    Active,
+    #[db_rename = "on_hold"]
+    Paused,
+}
+
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[db_enum(i18n = "labels.plan", value_style(camelCase))]
+pub enum Plan {
+    FreeTier,
+    Pro,
+}
+
+#[test]
+fn i18n_keys() {
+    assert_eq!(UserStatus::Active.i18n_key(), "enum.user_status.active");
+    assert_eq!(UserStatus::Paused.i18n_key(), "enum.user_status.on_hold");
+    assert_eq!(Plan::FreeTier.i18n_key(), "labels.plan.freeTier");
+    assert_eq!(Plan::Pro.i18n_key(), "labels.plan.pro");
+}
@@ -0,0 +1,114 @@
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::{Json, Nullable};
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[DbEnumJson]
+pub enum Status {
+    Active,
+    OnHold,
+}
+
+#[cfg(feature = "postgres")]
+pub fn create_table(conn: &mut PgConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TABLE test_json_string (
+            id SERIAL PRIMARY KEY,
+            data JSONB NOT NULL
+        );
+        INSERT INTO test_json_string (data) VALUES
+            ('{"status": "on_hold"}'),
+            ('{"status": "active", "note": "\"quoted\""}');
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "mysql")]
+pub fn create_table(conn: &mut MysqlConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TEMPORARY TABLE IF NOT EXISTS test_json_string (
+            id SERIAL PRIMARY KEY,
+            data JSON NOT NULL
+        );
+        INSERT INTO test_json_string (data) VALUES
+            ('{"status": "on_hold"}'),
+            ('{"status": "active", "note": "\\"quoted\\""}');
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "postgres")]
+const STATUS: &str = "(data->'status')::json";
+#[cfg(feature = "mysql")]
+const STATUS: &str = "JSON_EXTRACT(data, '$.status')";
+
+#[cfg(feature = "postgres")]
+const MISSING: &str = "(data->'missing')::json";
+#[cfg(feature = "mysql")]
+const MISSING: &str = "JSON_EXTRACT(data, '$.missing')";
+
+#[test]
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+fn load_json_string_projection() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    let items: Vec<Status> = test_json_string::table
+        .select(sql::<Json>(STATUS))
+        .order(test_json_string::id)
+        .load(connection)
+        .unwrap();
+    assert_eq!(items, vec![Status::OnHold, Status::Active]);
+    let missing: Vec<Option<Status>> = test_json_string::table
+        .select(sql::<Nullable<Json>>(MISSING))
+        .load(connection)
+        .unwrap();
+    assert_eq!(missing, vec![None, None]);
+}
+
+#[test]
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+fn json_string_round_trip() {
+    let connection = &mut get_connection();
+    let status: Status = diesel::select(Status::OnHold.into_sql::<Json>())
+        .get_result(connection)
+        .unwrap();
+    assert_eq!(status, Status::OnHold);
+}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn load_jsonb_string_projection() {
+    use diesel::sql_types::{Jsonb, Text};
+    let connection = &mut get_connection();
+    create_table(connection);
+    let items: Vec<Status> = test_json_string::table
+        .select(sql::<Jsonb>("data->'status'"))
+        .order(test_json_string::id)
+        .load(connection)
+        .unwrap();
+    assert_eq!(items, vec![Status::OnHold, Status::Active]);
+    let active: Vec<i32> = test_json_string::table
+        .filter(sql::<Jsonb>("data->'status'").eq(Status::Active))
+        .select(test_json_string::id)
+        .load(connection)
+        .unwrap();
+    assert_eq!(active, vec![2]);
+    let text: String = diesel::select(sql::<Text>("").bind::<Jsonb, _>(Status::OnHold).sql("::text"))
+        .get_result(connection)
+        .unwrap();
+    assert_eq!(text, "\"on_hold\"");
+}
+
+table! {
+    test_json_string {
+        id -> Integer,
+    }
+}
@@ -0,0 +1,52 @@
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+pub enum AccountStatus {
+    #[db_label = "Active user"]
+    Active,
+    #[db_rename = "on_hold"]
+    #[db_label = "Suspended"]
+    Paused,
+    Closed,
+    #[db_null_variant]
+    #[db_label = "Unknown"]
+    Unknown,
+}
+
+#[test]
+fn labels_and_choices() {
+    assert_eq!(AccountStatus::Active.label(), "Active user");
+    assert_eq!(AccountStatus::Paused.label(), "Suspended");
+    // without a `db_label`, the variant name
+    assert_eq!(AccountStatus::Closed.label(), "Closed");
+    assert_eq!(AccountStatus::Unknown.label(), "Unknown");
+    assert_eq!(
+        AccountStatus::choices(),
+        &[
+            (AccountStatus::Active, "active", "Active user"),
+            (AccountStatus::Paused, "on_hold", "Suspended"),
+            (AccountStatus::Closed, "closed", "Closed"),
+            // no `Unknown`, which is written as `NULL`
+        ]
+    );
+}
+
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[DbValueStyleFn = "crate::value_style_fn::prefixed_style"]
+pub enum StyledStatus {
+    #[db_label = "Active user"]
+    Active,
+    Closed,
+}
+
+#[test]
+fn choices_with_style_fn() {
+    // computed on first use, and the same slice afterwards
+    let choices = StyledStatus::choices();
+    assert_eq!(
+        choices,
+        &[
+            (StyledStatus::Active, "st_active", "Active user"),
+            (StyledStatus::Closed, "st_closed", "Closed"),
+        ]
+    );
+    assert!(std::ptr::eq(choices, StyledStatus::choices()));
+}
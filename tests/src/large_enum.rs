@@ -0,0 +1,318 @@
+// Generated code should stay flat as enums grow, so that a large enum expands and
+// type-checks without raising the `recursion_limit` of the crate using it
+#[derive(Debug, PartialEq, Eq, Clone, Copy, diesel_derive_enum::DbEnum)]
+#[DbEnumOrd]
+#[DbEnumDsl]
+#[DbEnumStrEq]
+#[DbTextMapping]
+pub enum StatusCode {
+    Code000,
+    Code001,
+    Code002,
+    Code003,
+    Code004,
+    Code005,
+    Code006,
+    Code007,
+    Code008,
+    Code009,
+    Code010,
+    Code011,
+    Code012,
+    Code013,
+    Code014,
+    Code015,
+    Code016,
+    Code017,
+    Code018,
+    Code019,
+    Code020,
+    Code021,
+    Code022,
+    Code023,
+    Code024,
+    Code025,
+    Code026,
+    Code027,
+    Code028,
+    Code029,
+    Code030,
+    Code031,
+    Code032,
+    Code033,
+    Code034,
+    Code035,
+    Code036,
+    Code037,
+    Code038,
+    Code039,
+    Code040,
+    Code041,
+    Code042,
+    Code043,
+    Code044,
+    Code045,
+    Code046,
+    Code047,
+    Code048,
+    Code049,
+    Code050,
+    Code051,
+    Code052,
+    Code053,
+    Code054,
+    Code055,
+    Code056,
+    Code057,
+    Code058,
+    Code059,
+    Code060,
+    Code061,
+    Code062,
+    Code063,
+    Code064,
+    Code065,
+    Code066,
+    Code067,
+    Code068,
+    Code069,
+    Code070,
+    Code071,
+    Code072,
+    Code073,
+    Code074,
+    Code075,
+    Code076,
+    Code077,
+    Code078,
+    Code079,
+    Code080,
+    Code081,
+    Code082,
+    Code083,
+    Code084,
+    Code085,
+    Code086,
+    Code087,
+    Code088,
+    Code089,
+    Code090,
+    Code091,
+    Code092,
+    Code093,
+    Code094,
+    Code095,
+    Code096,
+    Code097,
+    Code098,
+    Code099,
+    Code100,
+    Code101,
+    Code102,
+    Code103,
+    Code104,
+    Code105,
+    Code106,
+    Code107,
+    Code108,
+    Code109,
+    Code110,
+    Code111,
+    Code112,
+    Code113,
+    Code114,
+    Code115,
+    Code116,
+    Code117,
+    Code118,
+    Code119,
+    Code120,
+    Code121,
+    Code122,
+    Code123,
+    Code124,
+    Code125,
+    Code126,
+    Code127,
+    Code128,
+    Code129,
+    Code130,
+    Code131,
+    Code132,
+    Code133,
+    Code134,
+    Code135,
+    Code136,
+    Code137,
+    Code138,
+    Code139,
+    Code140,
+    Code141,
+    Code142,
+    Code143,
+    Code144,
+    Code145,
+    Code146,
+    Code147,
+    Code148,
+    Code149,
+    Code150,
+    Code151,
+    Code152,
+    Code153,
+    Code154,
+    Code155,
+    Code156,
+    Code157,
+    Code158,
+    Code159,
+    Code160,
+    Code161,
+    Code162,
+    Code163,
+    Code164,
+    Code165,
+    Code166,
+    Code167,
+    Code168,
+    Code169,
+    Code170,
+    Code171,
+    Code172,
+    Code173,
+    Code174,
+    Code175,
+    Code176,
+    Code177,
+    Code178,
+    Code179,
+    Code180,
+    Code181,
+    Code182,
+    Code183,
+    Code184,
+    Code185,
+    Code186,
+    Code187,
+    Code188,
+    Code189,
+    Code190,
+    Code191,
+    Code192,
+    Code193,
+    Code194,
+    Code195,
+    Code196,
+    Code197,
+    Code198,
+    Code199,
+    Code200,
+    Code201,
+    Code202,
+    Code203,
+    Code204,
+    Code205,
+    Code206,
+    Code207,
+    Code208,
+    Code209,
+    Code210,
+    Code211,
+    Code212,
+    Code213,
+    Code214,
+    Code215,
+    Code216,
+    Code217,
+    Code218,
+    Code219,
+    Code220,
+    Code221,
+    Code222,
+    Code223,
+    Code224,
+    Code225,
+    Code226,
+    Code227,
+    Code228,
+    Code229,
+    Code230,
+    Code231,
+    Code232,
+    Code233,
+    Code234,
+    Code235,
+    Code236,
+    Code237,
+    Code238,
+    Code239,
+    Code240,
+    Code241,
+    Code242,
+    Code243,
+    Code244,
+    Code245,
+    Code246,
+    Code247,
+    Code248,
+    Code249,
+    Code250,
+    Code251,
+    Code252,
+    Code253,
+    Code254,
+    Code255,
+    Code256,
+    Code257,
+    Code258,
+    Code259,
+    Code260,
+    Code261,
+    Code262,
+    Code263,
+    Code264,
+    Code265,
+    Code266,
+    Code267,
+    Code268,
+    Code269,
+    Code270,
+    Code271,
+    Code272,
+    Code273,
+    Code274,
+    Code275,
+    Code276,
+    Code277,
+    Code278,
+    Code279,
+    Code280,
+    Code281,
+    Code282,
+    Code283,
+    Code284,
+    Code285,
+    Code286,
+    Code287,
+    Code288,
+    Code289,
+    Code290,
+    Code291,
+    Code292,
+    Code293,
+    Code294,
+    Code295,
+    Code296,
+    Code297,
+    Code298,
+    Code299,
+}
+
+#[test]
+fn large_enum_round_trips_db_values() {
+    assert_eq!(StatusCode::all().len(), 300);
+    for (i, code) in StatusCode::all().iter().enumerate() {
+        assert_eq!(*code, format!("code{:03}", i).as_str());
+    }
+    assert!(StatusCode::Code000 < StatusCode::Code299);
+}
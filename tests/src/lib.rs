@@ -3,12 +3,95 @@
 #![allow(dead_code)]
 #![allow(unused_imports)]
 
+#[cfg(any(feature = "mysql", feature = "sqlite"))]
+mod array_storage;
+#[cfg(feature = "arbitrary")]
+mod arbitrary_input;
+mod as_text;
+#[cfg(any(feature = "async-postgres", feature = "async-mysql"))]
+mod async_conn;
+#[cfg(feature = "mysql")]
+mod byte_rename;
+#[cfg(feature = "clap")]
+mod clap_value_enum;
+mod case_conversion;
+mod cfg_variants;
+mod char_width;
+mod clippy_pedantic;
+mod codegen;
 mod common;
 mod complex_join;
+#[cfg(feature = "custom-backend")]
+mod custom_backend;
+mod db_enum_attr;
+mod empty_value;
+mod enum_str;
+mod explicit_values;
+#[cfg(feature = "fake")]
+mod fake_dummy;
+mod from_sql;
+mod i18n_keys;
+mod labels;
+mod large_enum;
+mod local_enum;
+mod markdown_table;
+mod mapping_consts;
+mod mapping_traits;
+// public, as `missing_docs` only applies to exported items
+pub mod missing_docs;
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+mod json_string;
+mod no_implicit_prelude;
+mod no_reexport;
+mod non_ascii;
+mod null_variant;
 mod nullable;
+mod ord;
 #[cfg(feature = "postgres")]
 mod pg_array;
 #[cfg(feature = "postgres")]
+mod pg_comment;
+#[cfg(feature = "postgres")]
+mod pg_composite;
+#[cfg(feature = "postgres")]
+mod pg_find_columns;
+#[cfg(feature = "postgres")]
+mod pg_migration;
+#[cfg(feature = "postgres")]
+mod pg_oid;
+#[cfg(feature = "postgres-types")]
+mod pg_postgres_types;
+#[cfg(feature = "refinery")]
+mod pg_refinery;
+#[cfg(feature = "postgres")]
 mod pg_remote_type;
+#[cfg(feature = "postgres")]
+mod pg_remove_values;
+#[cfg(feature = "postgres")]
+mod pg_rename_values;
+#[cfg(feature = "postgres")]
+mod pg_sort_order;
+#[cfg(feature = "postgres")]
+mod pg_sync_variants;
+#[cfg(feature = "postgres")]
+mod pg_type_names;
+mod read_write_only;
+#[cfg(feature = "serde")]
+mod serde_as_db_string;
+#[cfg(feature = "rocket")]
+mod rocket_params;
 mod simple;
+mod skip_impls;
+mod sql_hooks;
+mod sql_literal;
+#[cfg(feature = "sqlite")]
+mod sqlite_validation;
+#[cfg(feature = "sqlx")]
+mod sqlx_interop;
+mod storage_by_backend;
+mod text_mapping;
+mod trusted_read;
+mod value_normalize;
 mod value_style;
+mod value_style_fn;
+mod variant_index;
@@ -0,0 +1,41 @@
+use diesel::prelude::*;
+
+#[test]
+fn enum_in_function_body() {
+    #[derive(Debug, PartialEq, Clone, Copy, diesel_derive_enum::DbEnum)]
+    #[DbEnumLocal]
+    #[DbStorageByBackend(postgres = "text", mysql = "text")]
+    #[cfg_attr(any(feature = "mysql", feature = "sqlite"), DbArrayStorage = "json")]
+    enum Shape {
+        Circle,
+        Square,
+    }
+
+    assert_eq!(Shape::all(), &[Shape::Circle, Shape::Square]);
+    assert!(Shape::Square.matches_db_str("square"));
+
+    #[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+    {
+        let connection = &mut crate::common::get_connection();
+        let shape: Shape = diesel::select(Shape::Circle.into_sql::<ShapeMapping>())
+            .get_result(connection)
+            .unwrap();
+        assert_eq!(shape, Shape::Circle);
+    }
+}
+
+mod tests {
+    // also in a test module, which doesn't need a re-export of the mapping
+    #[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+    #[db_enum(local)]
+    enum Color {
+        Red,
+    }
+
+    #[test]
+    fn enum_in_module() {
+        let _: Option<ColorMapping> = None;
+        let _: Option<ColorRank<()>> = None;
+        assert_eq!(Color::all(), &[Color::Red]);
+    }
+}
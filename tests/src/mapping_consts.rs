@@ -0,0 +1,36 @@
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[PgType = "ticket_priority"]
+pub enum Priority {
+    Low,
+    #[db_rename = "urgent"]
+    High,
+    #[db_null_variant]
+    Unset,
+}
+
+#[test]
+fn mapping_consts() {
+    assert_eq!(PriorityMapping::SQL_TYPE_NAME, "ticket_priority");
+    // the null variant is written as `NULL`, not as a value of the type
+    assert_eq!(PriorityMapping::LABELS, &["low", "urgent"]);
+}
+
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[DbStorageByBackend(postgres = "text")]
+pub enum Channel {
+    Email,
+    Sms,
+}
+
+// an inherent `SQL_TYPE_NAME` would take precedence over this one
+trait NoSqlTypeName {
+    const SQL_TYPE_NAME: &'static str = "<none>";
+}
+
+impl<T> NoSqlTypeName for T {}
+
+#[test]
+fn text_mapping_has_no_type_name() {
+    assert_eq!(ChannelMapping::SQL_TYPE_NAME, "<none>");
+    assert_eq!(ChannelMapping::LABELS, &["email", "sms"]);
+}
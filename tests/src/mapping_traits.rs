@@ -0,0 +1,20 @@
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(text_mapping)]
+#[cfg_attr(any(feature = "mysql", feature = "sqlite"), DbArrayStorage = "json")]
+pub enum Marker {
+    Foo,
+}
+
+fn assert_copy<T: Copy>(value: T) -> (T, T) {
+    (value, value)
+}
+
+#[test]
+fn mapping_traits() {
+    let mapping: MarkerMapping = Default::default();
+    let (mapping, _) = assert_copy(mapping);
+    assert_eq!(format!("{:?}", mapping), "MarkerMapping");
+    assert_eq!(format!("{:?}", MarkerTextMapping::default()), "MarkerTextMapping");
+    #[cfg(any(feature = "mysql", feature = "sqlite"))]
+    assert_eq!(format!("{:?}", MarkerArrayMapping), "MarkerArrayMapping");
+}
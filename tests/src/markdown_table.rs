@@ -0,0 +1,27 @@
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+pub enum Delivery {
+    /// Left at the door
+    Doorstep,
+    /// Held at the depot,
+    /// for pickup | collection
+    #[db_rename = "depot|hold"]
+    Depot,
+    #[db_rename = "`drone`"]
+    Drone,
+    /// Not chosen yet
+    #[db_null_variant]
+    Undecided,
+}
+
+#[test]
+fn markdown_table() {
+    assert_eq!(
+        Delivery::markdown_table(),
+        "| Variant | DB value | Description |\n\
+         | --- | --- | --- |\n\
+         | Doorstep | `doorstep` | Left at the door |\n\
+         | Depot | `depot\\|hold` | Held at the depot, for pickup \\| collection |\n\
+         | Drone | `` `drone` `` |  |\n\
+         | Undecided | NULL | Not chosen yet |\n"
+    );
+}
@@ -0,0 +1,24 @@
+//! The generated items are documented, for crates which deny `missing_docs`
+#![deny(missing_docs)]
+
+/// A documented enum
+#[derive(Debug, PartialEq, Eq, Clone, Copy, diesel_derive_enum::DbEnum)]
+#[DbEnumDsl]
+#[DbEnumOrd]
+#[DbEnumStrEq]
+#[DbEnumAsText]
+#[db_enum(text_mapping, str, index)]
+#[cfg_attr(any(feature = "postgres", feature = "mysql"), DbEnumJson)]
+#[cfg_attr(any(feature = "mysql", feature = "sqlite"), DbArrayStorage = "delimited")]
+#[cfg_attr(feature = "clap", DbEnumClap)]
+#[cfg_attr(feature = "rocket", DbEnumRocket)]
+#[cfg_attr(feature = "postgres-types", DbEnumPostgresTypes)]
+pub enum Documented {
+    /// The first variant
+    Foo,
+    /// The second variant
+    Bar,
+    /// No value
+    #[db_null_variant]
+    Unknown,
+}
@@ -0,0 +1,45 @@
+#[no_implicit_prelude]
+mod isolated {
+    // names which the generated code must not pick up instead of the std ones
+    pub type Result<T> = ::std::result::Result<T, ()>;
+    pub struct String;
+    pub struct Vec;
+    pub struct Ok;
+
+    #[derive(
+        ::std::fmt::Debug,
+        ::std::cmp::PartialEq,
+        ::std::cmp::Eq,
+        ::std::clone::Clone,
+        ::std::marker::Copy,
+        ::diesel_derive_enum::DbEnum,
+    )]
+    #[DbEnumDsl]
+    #[DbEnumOrd]
+    #[DbEnumStrEq]
+    #[DbEnumAsText]
+    #[db_enum(generate_tests, text_mapping, str, index)]
+    #[cfg_attr(any(feature = "postgres", feature = "mysql"), DbEnumJson)]
+    #[cfg_attr(any(feature = "mysql", feature = "sqlite"), DbArrayStorage = "delimited")]
+    #[cfg_attr(feature = "clap", DbEnumClap)]
+    #[cfg_attr(feature = "rocket", DbEnumRocket)]
+    #[cfg_attr(feature = "postgres-types", DbEnumPostgresTypes)]
+    pub enum Hygienic {
+        Foo,
+        BarBaz,
+        #[db_null_variant]
+        Unknown,
+    }
+}
+
+use isolated::Hygienic;
+
+#[test]
+fn no_implicit_prelude() {
+    assert_eq!(
+        Hygienic::all(),
+        &[Hygienic::Foo, Hygienic::BarBaz, Hygienic::Unknown]
+    );
+    assert!(Hygienic::BarBaz.matches_db_str("bar_baz"));
+    assert_eq!(Hygienic::Foo.to_sql_literal(), "'foo'");
+}
@@ -0,0 +1,25 @@
+use diesel::prelude::*;
+
+#[derive(Debug, PartialEq, Clone, Copy, diesel_derive_enum::DbEnum)]
+#[DbEnumNoReexport]
+#[DbStorageByBackend(postgres = "text", mysql = "text")]
+pub enum Quiet {
+    Hush,
+    Whisper,
+}
+
+// the name of the mapping is free for other uses
+pub struct QuietMapping;
+
+// and the mapping can be re-exported under another name
+pub use self::db_enum_impl_Quiet::QuietMapping as QuietSqlType;
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn no_reexport() {
+    let connection = &mut crate::common::get_connection();
+    let quiet: Quiet = diesel::select(Quiet::Whisper.into_sql::<QuietSqlType>())
+        .get_result(connection)
+        .unwrap();
+    assert_eq!(quiet, Quiet::Whisper);
+}
@@ -0,0 +1,107 @@
+use diesel::insert_into;
+use diesel::prelude::*;
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+pub enum Weather {
+    #[db_rename = "ensoleillé"]
+    Sunny,
+    #[db_rename = "雨"]
+    Rainy,
+    #[db_rename = "🌩️"]
+    Stormy,
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::WeatherMapping;
+    test_non_ascii {
+        id -> Integer,
+        weather -> WeatherMapping,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, Clone, PartialEq)]
+#[diesel(table_name = test_non_ascii)]
+struct Forecast {
+    id: i32,
+    weather: Weather,
+}
+
+#[cfg(feature = "postgres")]
+pub fn create_table(conn: &mut PgConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TYPE weather AS ENUM ('ensoleillé', '雨', '🌩️');
+        CREATE TABLE test_non_ascii (
+            id SERIAL PRIMARY KEY,
+            weather weather NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "mysql")]
+pub fn create_table(conn: &mut MysqlConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TEMPORARY TABLE IF NOT EXISTS test_non_ascii (
+            id SERIAL PRIMARY KEY,
+            weather enum('ensoleillé', '雨', '🌩️') CHARACTER SET utf8mb4 NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+pub fn create_table(conn: &mut SqliteConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TABLE test_non_ascii (
+            id SERIAL PRIMARY KEY,
+            weather TEXT CHECK(weather IN ('ensoleillé', '雨', '🌩️')) NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn non_ascii_round_trip() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    let data = vec![
+        Forecast {
+            id: 1,
+            weather: Weather::Sunny,
+        },
+        Forecast {
+            id: 2,
+            weather: Weather::Rainy,
+        },
+        Forecast {
+            id: 3,
+            weather: Weather::Stormy,
+        },
+    ];
+    let ct = insert_into(test_non_ascii::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    assert_eq!(data.len(), ct);
+    let items = test_non_ascii::table.load::<Forecast>(connection).unwrap();
+    assert_eq!(data, items);
+    let stormy = test_non_ascii::table
+        .filter(test_non_ascii::weather.eq(Weather::Stormy))
+        .select(test_non_ascii::id)
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(stormy, vec![3]);
+}
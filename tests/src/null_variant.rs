@@ -0,0 +1,126 @@
+use diesel::insert_into;
+use diesel::prelude::*;
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+pub enum LegacyStatus {
+    Active,
+    Retired,
+    #[db_null_variant]
+    Unknown,
+}
+
+table! {
+    use diesel::sql_types::{Integer, Nullable};
+    use super::LegacyStatusMapping;
+    test_null_variant {
+        id -> Integer,
+        status -> Nullable<LegacyStatusMapping>,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_null_variant)]
+struct Legacy {
+    id: i32,
+    status: LegacyStatus,
+}
+
+#[cfg(feature = "postgres")]
+pub fn create_null_variant_table(conn: &mut PgConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TYPE legacy_status AS ENUM ('active', 'retired');
+        CREATE TABLE test_null_variant (
+            id SERIAL PRIMARY KEY,
+            status legacy_status
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "mysql")]
+pub fn create_null_variant_table(conn: &mut MysqlConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TEMPORARY TABLE IF NOT EXISTS test_null_variant (
+            id SERIAL PRIMARY KEY,
+            status enum('active', 'retired')
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+pub fn create_null_variant_table(conn: &mut SqliteConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TABLE test_null_variant (
+            id SERIAL PRIMARY KEY,
+            status TEXT CHECK(status IN ('active', 'retired'))
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn null_variant_round_trip() {
+    let connection = &mut get_connection();
+    create_null_variant_table(connection);
+    let data = vec![
+        Legacy {
+            id: 1,
+            status: LegacyStatus::Unknown,
+        },
+        Legacy {
+            id: 2,
+            status: LegacyStatus::Retired,
+        },
+    ];
+    let ct = insert_into(test_null_variant::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    assert_eq!(data.len(), ct);
+    let nulls: i64 = test_null_variant::table
+        .filter(test_null_variant::status.is_null())
+        .count()
+        .get_result(connection)
+        .unwrap();
+    assert_eq!(nulls, 1);
+    let items = test_null_variant::table.load::<Legacy>(connection).unwrap();
+    assert_eq!(data, items);
+}
+
+#[test]
+fn null_variant_ranks_as_null() {
+    let connection = &mut get_connection();
+    create_null_variant_table(connection);
+    let data = vec![
+        Legacy {
+            id: 1,
+            status: LegacyStatus::Unknown,
+        },
+        Legacy {
+            id: 2,
+            status: LegacyStatus::Retired,
+        },
+    ];
+    insert_into(test_null_variant::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let ranks = test_null_variant::table
+        .select(LegacyStatus::db_rank(test_null_variant::status))
+        .order_by(test_null_variant::id)
+        .load::<Option<i32>>(connection)
+        .unwrap();
+    assert_eq!(ranks, vec![None, Some(1)]);
+}
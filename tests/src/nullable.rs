@@ -111,3 +111,46 @@ fn not_nullable_enum_round_trip() {
         .unwrap();
     assert_eq!(data.len(), ct);
 }
+
+#[derive(Insertable, Debug, PartialEq)]
+#[diesel(table_name = test_nullable)]
+struct BorrowedNullable<'a> {
+    id: i32,
+    my_enum: Option<&'a MyEnum>,
+}
+
+#[test]
+fn borrowed_nullable_batch_insert() {
+    let connection = &mut get_connection();
+    create_null_table(connection);
+    let bar = MyEnum::Bar;
+    let data = vec![
+        BorrowedNullable {
+            id: 1,
+            my_enum: None,
+        },
+        BorrowedNullable {
+            id: 2,
+            my_enum: Some(&bar),
+        },
+    ];
+    let ct = insert_into(test_nullable::table)
+        .values(data.as_slice())
+        .execute(connection)
+        .unwrap();
+    assert_eq!(data.len(), ct);
+    let items = test_nullable::table.load::<Nullable>(connection).unwrap();
+    assert_eq!(
+        items,
+        vec![
+            Nullable {
+                id: 1,
+                my_enum: None,
+            },
+            Nullable {
+                id: 2,
+                my_enum: Some(MyEnum::Bar),
+            },
+        ]
+    );
+}
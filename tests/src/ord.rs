@@ -0,0 +1,325 @@
+use diesel::prelude::*;
+
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Eq, Clone, diesel_derive_enum::DbEnum)]
+#[DbEnumOrd]
+pub enum Severity {
+    Low,
+    High,
+    Medium,
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::SeverityMapping;
+    test_ord {
+        id -> Integer,
+        severity -> SeverityMapping,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_ord)]
+struct Ticket {
+    id: i32,
+    severity: Severity,
+}
+
+#[cfg(feature = "postgres")]
+pub fn create_table(conn: &mut PgConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TYPE severity AS ENUM ('low', 'high', 'medium');
+        CREATE TABLE test_ord (
+            id SERIAL PRIMARY KEY,
+            severity severity NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "mysql")]
+pub fn create_table(conn: &mut MysqlConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TEMPORARY TABLE IF NOT EXISTS test_ord (
+            id SERIAL PRIMARY KEY,
+            severity VARCHAR(10) NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+pub fn create_table(conn: &mut SqliteConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TABLE test_ord (
+            id SERIAL PRIMARY KEY,
+            severity TEXT NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn ord_follows_declaration_order() {
+    assert!(Severity::Low < Severity::High);
+    assert!(Severity::High < Severity::Medium);
+    let mut values = vec![Severity::Medium, Severity::Low, Severity::High];
+    values.sort();
+    assert_eq!(values, Severity::all());
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn order_by_db_rank() {
+    use diesel::insert_into;
+    let connection = &mut get_connection();
+    create_table(connection);
+    let data = vec![
+        Ticket {
+            id: 1,
+            severity: Severity::Medium,
+        },
+        Ticket {
+            id: 2,
+            severity: Severity::Low,
+        },
+        Ticket {
+            id: 3,
+            severity: Severity::High,
+        },
+    ];
+    insert_into(test_ord::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let ids = test_ord::table
+        .select(test_ord::id)
+        .order_by(severity_rank())
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(ids, vec![2, 3, 1]);
+}
+
+// the rank expression can be named, e.g. to return it from a helper
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn severity_rank() -> SeverityRank<test_ord::severity> {
+    Severity::db_rank(test_ord::severity)
+}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn order_matches_db() {
+    use diesel::connection::SimpleConnection;
+    let connection = &mut get_connection();
+    connection
+        .batch_execute("CREATE TYPE severity AS ENUM ('low', 'high', 'medium');")
+        .unwrap();
+    Severity::assert_order_matches_db(connection).unwrap();
+}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn order_mismatch_db() {
+    use diesel::connection::SimpleConnection;
+    let connection = &mut get_connection();
+    connection
+        .batch_execute("CREATE TYPE severity AS ENUM ('low', 'medium', 'high');")
+        .unwrap();
+    assert!(Severity::assert_order_matches_db(connection).is_err());
+}
+
+#[test]
+#[cfg(feature = "mysql")]
+fn mysql_column_type_order() {
+    assert!(Severity::check_mysql_column_type("enum('low','high','medium')").is_ok());
+    assert!(Severity::check_mysql_column_type("ENUM('low','medium','high')").is_err());
+    assert!(Severity::check_mysql_column_type("varchar(10)").is_err());
+}
+
+#[test]
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+fn drift_report() {
+    let values = |values: &[&str]| values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+    let report = Severity::drift_report(&values(&["low", "high", "medium"]));
+    assert!(report.is_in_sync());
+    assert_eq!(report.status(), "in_sync");
+    assert_eq!(report, SeverityDriftReport::default());
+
+    let report = Severity::drift_report(&values(&["medium", "low", "urgent"]));
+    assert_eq!(report.status(), "drifted");
+    assert_eq!(report.missing, values(&["high"]));
+    assert_eq!(report.extra, values(&["urgent"]));
+    assert_eq!(report.order_mismatches, values(&["low", "medium"]));
+    assert_eq!(SeverityDriftReport::ENUM_NAME, "Severity");
+}
+
+#[test]
+#[cfg(all(feature = "serde", any(feature = "postgres", feature = "mysql")))]
+fn drift_report_json() {
+    let report = Severity::drift_report(&["low".to_string(), "high".to_string()]);
+    assert_eq!(
+        serde_json::to_string(&report).unwrap(),
+        r#"{"enum":"Severity","status":"drifted","missing":["medium"],"extra":[],"order_mismatches":[]}"#
+    );
+}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn pg_drift_report() {
+    use diesel::connection::SimpleConnection;
+    let connection = &mut get_connection();
+    connection
+        .batch_execute("CREATE TYPE severity AS ENUM ('low', 'medium', 'high');")
+        .unwrap();
+    let report = Severity::pg_drift_report(connection).unwrap();
+    assert!(report.missing.is_empty() && report.extra.is_empty());
+    assert_eq!(report.order_mismatches, vec!["high", "medium"]);
+}
+
+#[test]
+#[cfg(feature = "mysql")]
+fn mysql_column_drift_report() {
+    let report = Severity::mysql_column_drift_report("enum('low','high')").unwrap();
+    assert_eq!(report.missing, vec!["medium"]);
+    assert!(Severity::mysql_column_drift_report("varchar(10)").is_err());
+}
+
+// the drift report doesn't need `DbEnumOrd`
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+pub enum Stage {
+    Draft,
+    Published,
+}
+
+#[test]
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+fn drift_report_without_ord() {
+    let report = Stage::drift_report(&["draft".to_string(), "archived".to_string()]);
+    assert_eq!(report.missing, vec!["published"]);
+    assert_eq!(report.extra, vec!["archived"]);
+    assert_eq!(StageDriftReport::ENUM_NAME, "Stage");
+}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn pg_drift_report_without_ord() {
+    use diesel::connection::SimpleConnection;
+    let connection = &mut get_connection();
+    connection
+        .batch_execute("CREATE TYPE stage AS ENUM ('draft', 'published');")
+        .unwrap();
+    assert!(Stage::pg_drift_report(connection).unwrap().is_in_sync());
+}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn range_filters_follow_db_order() {
+    use diesel::insert_into;
+    let connection = &mut get_connection();
+    create_table(connection);
+    let data = vec![
+        Ticket {
+            id: 1,
+            severity: Severity::Medium,
+        },
+        Ticket {
+            id: 2,
+            severity: Severity::Low,
+        },
+        Ticket {
+            id: 3,
+            severity: Severity::High,
+        },
+    ];
+    insert_into(test_ord::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let above_low = test_ord::table
+        .select(test_ord::id)
+        .filter(test_ord::severity.gt(Severity::Low))
+        .order(test_ord::id)
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(above_low, vec![1, 3]);
+    let up_to_high = test_ord::table
+        .select(test_ord::id)
+        .filter(test_ord::severity.between(Severity::Low, Severity::High))
+        .order(test_ord::id)
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(up_to_high, vec![2, 3]);
+}
+
+#[test]
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+fn mapping_is_sql_ord() {
+    fn assert_sql_ord<T: diesel::sql_types::SqlOrd>() {}
+    assert_sql_ord::<SeverityMapping>();
+    assert_sql_ord::<diesel::sql_types::Nullable<SeverityMapping>>();
+}
+
+// sqlite stores the enum as text, which `max()` would compare lexically, so this only
+// compiles as long as the mapping doesn't implement `SqlOrd`
+#[test]
+#[cfg(feature = "sqlite")]
+fn text_mapping_is_not_sql_ord() {
+    trait AmbiguousIfSqlOrd<A> {
+        fn check() {}
+    }
+    impl<T: ?Sized> AmbiguousIfSqlOrd<()> for T {}
+    struct IsSqlOrd;
+    impl<T: ?Sized + diesel::sql_types::SqlOrd> AmbiguousIfSqlOrd<IsSqlOrd> for T {}
+    <SeverityMapping as AmbiguousIfSqlOrd<_>>::check();
+}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn max_and_min() {
+    use diesel::insert_into;
+    let connection = &mut get_connection();
+    create_table(connection);
+    let empty = test_ord::table
+        .select(diesel::dsl::max(test_ord::severity))
+        .get_result::<Option<Severity>>(connection)
+        .unwrap();
+    assert_eq!(empty, None);
+    let data = vec![
+        Ticket {
+            id: 1,
+            severity: Severity::High,
+        },
+        Ticket {
+            id: 2,
+            severity: Severity::Low,
+        },
+        Ticket {
+            id: 3,
+            severity: Severity::Medium,
+        },
+    ];
+    insert_into(test_ord::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let (highest, lowest) = test_ord::table
+        .select((
+            diesel::dsl::max(test_ord::severity),
+            diesel::dsl::min(test_ord::severity),
+        ))
+        .get_result::<(Option<Severity>, Option<Severity>)>(connection)
+        .unwrap();
+    // postgres compares in declaration order
+    assert_eq!((highest, lowest), (Some(Severity::Medium), Some(Severity::Low)));
+}
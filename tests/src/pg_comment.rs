@@ -0,0 +1,51 @@
+use diesel::connection::SimpleConnection;
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::{Nullable, Text};
+
+use crate::common::get_connection;
+
+/// Where a shipment currently is.
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+pub enum ShipmentState {
+    /// Waiting for the courier's
+    /// pickup.
+    Pending,
+    InTransit,
+    /// Signed for by the recipient.
+    Delivered,
+}
+
+#[test]
+fn pg_comment_sql() {
+    assert_eq!(
+        ShipmentState::PG_COMMENT_SQL,
+        "COMMENT ON TYPE shipment_state IS 'Where a shipment currently is.\n\
+         - pending: Waiting for the courier''s pickup.\n\
+         - delivered: Signed for by the recipient.';"
+    );
+}
+
+#[test]
+fn pg_comment_applies() {
+    let connection = &mut get_connection();
+    connection
+        .batch_execute("CREATE TYPE shipment_state AS ENUM ('pending', 'in_transit', 'delivered');")
+        .unwrap();
+    connection
+        .batch_execute(ShipmentState::PG_COMMENT_SQL)
+        .unwrap();
+    let comment: Option<String> = diesel::select(sql::<Nullable<Text>>(
+        "obj_description('shipment_state'::regtype, 'pg_type')",
+    ))
+    .get_result(connection)
+    .unwrap();
+    assert_eq!(
+        comment.as_deref(),
+        Some(
+            "Where a shipment currently is.\n\
+             - pending: Waiting for the courier's pickup.\n\
+             - delivered: Signed for by the recipient."
+        )
+    );
+}
@@ -0,0 +1,85 @@
+use diesel::connection::SimpleConnection;
+use diesel::deserialize::{self, FromSql, FromSqlRow};
+use diesel::dsl::sql;
+use diesel::expression::AsExpression;
+use diesel::pg::{Pg, PgValue};
+use diesel::prelude::*;
+use diesel::query_builder::QueryId;
+use diesel::serialize::{self, Output, ToSql, WriteTuple};
+use diesel::sql_types::{Integer, Nullable, Record, SqlType};
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, Copy, diesel_derive_enum::DbEnum)]
+#[PgType = "composite_mood"]
+pub enum Mood {
+    Happy,
+    Sad,
+    #[db_null_variant]
+    Unknown,
+}
+
+#[derive(SqlType, QueryId)]
+#[diesel(postgres_type(name = "composite_pet"))]
+pub struct PetType;
+
+#[derive(Debug, PartialEq, AsExpression, FromSqlRow)]
+#[diesel(sql_type = PetType)]
+pub struct Pet {
+    age: i32,
+    mood: Mood,
+}
+
+impl ToSql<PetType, Pg> for Pet {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        WriteTuple::<(Integer, Nullable<MoodMapping>)>::write_tuple(&(self.age, self.mood), out)
+    }
+}
+
+impl FromSql<PetType, Pg> for Pet {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        let (age, mood) = FromSql::<Record<(Integer, Nullable<MoodMapping>)>, Pg>::from_sql(bytes)?;
+        Ok(Pet { age, mood })
+    }
+}
+
+fn create_types(conn: &mut PgConnection) {
+    conn.batch_execute(
+        "CREATE TYPE composite_mood AS ENUM ('happy', 'sad');
+         CREATE TYPE composite_pet AS (age integer, mood composite_mood);",
+    )
+    .unwrap();
+}
+
+#[test]
+fn read_record() {
+    let connection = &mut get_connection();
+    create_types(connection);
+    let row: (i32, Mood) = diesel::select(sql::<Record<(Integer, MoodMapping)>>(
+        "ROW(3, 'sad'::composite_mood)",
+    ))
+    .get_result(connection)
+    .unwrap();
+    assert_eq!(row, (3, Mood::Sad));
+}
+
+#[test]
+fn composite_round_trip() {
+    let connection = &mut get_connection();
+    create_types(connection);
+    for pet in [
+        Pet {
+            age: 3,
+            mood: Mood::Happy,
+        },
+        Pet {
+            age: 5,
+            mood: Mood::Unknown,
+        },
+    ] {
+        let read: Pet = diesel::select((&pet).into_sql::<PetType>())
+            .get_result(connection)
+            .unwrap();
+        assert_eq!(read, pet);
+    }
+}
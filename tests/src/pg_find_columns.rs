@@ -0,0 +1,47 @@
+use diesel::connection::SimpleConnection;
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+pub enum Tier {
+    Free,
+    Paid,
+}
+
+#[test]
+fn find_columns_using_type() {
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(
+            "CREATE TYPE tier AS ENUM ('free', 'paid');
+             CREATE TABLE test_find_accounts (
+                 id SERIAL PRIMARY KEY,
+                 tier tier NOT NULL,
+                 previous_tiers tier[] NOT NULL
+             );
+             CREATE TABLE test_find_invoices (
+                 id SERIAL PRIMARY KEY,
+                 name TEXT NOT NULL,
+                 tier tier
+             );
+             CREATE VIEW test_find_paid AS
+             SELECT id, tier FROM test_find_accounts WHERE tier = 'paid';",
+        )
+        .unwrap();
+    let columns = Tier::find_columns_using_type(connection).unwrap();
+    // the test connection creates its tables in a temporary schema
+    assert!(columns.iter().all(|(schema, _, _)| *schema == columns[0].0));
+    let columns = columns
+        .iter()
+        .map(|(_, table, column)| (table.as_str(), column.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        columns,
+        vec![
+            ("test_find_accounts", "tier"),
+            ("test_find_accounts", "previous_tiers"),
+            ("test_find_invoices", "tier"),
+            ("test_find_paid", "tier"),
+        ]
+    );
+}
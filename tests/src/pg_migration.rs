@@ -0,0 +1,85 @@
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::{Array, Bool, Text};
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[PgType = "migrated_fruit"]
+pub enum Fruit {
+    Apple,
+    #[db_rename = "blood orange"]
+    BloodOrange,
+}
+
+fn type_exists(conn: &mut PgConnection) -> bool {
+    diesel::select(sql::<Bool>("to_regtype('migrated_fruit') IS NOT NULL"))
+        .get_result(conn)
+        .unwrap()
+}
+
+#[test]
+fn create_type_migration() {
+    let connection = &mut get_connection();
+    let migration = Fruit::create_type_migration("2024-01-01-000000");
+    assert_eq!(
+        migration.name().to_string(),
+        "2024-01-01-000000_create_migrated_fruit"
+    );
+    assert_eq!(
+        migration.name().version().to_string(),
+        "2024-01-01-000000"
+    );
+    assert!(migration.metadata().run_in_transaction());
+
+    migration.run(connection).unwrap();
+    let values: Vec<String> =
+        diesel::select(sql::<Array<Text>>("enum_range(NULL::migrated_fruit)::text[]"))
+            .get_result(connection)
+            .unwrap();
+    assert_eq!(values, vec!["apple", "blood orange"]);
+
+    migration.revert(connection).unwrap();
+    assert!(!type_exists(connection));
+}
+
+// a type name which is a keyword, and one which isn't lowercase, are quoted in the SQL
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[PgType = "user"]
+pub enum UserKind {
+    /// A person
+    Human,
+    Bot,
+}
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[PgType = "MigratedColor"]
+pub enum Color {
+    Red,
+    Green,
+}
+
+#[test]
+fn quoted_type_names() {
+    assert_eq!(
+        UserKind::PG_COMMENT_SQL,
+        "COMMENT ON TYPE \"user\" IS '- human: A person';"
+    );
+    let connection = &mut get_connection();
+    UserKind::create_type_migration("2024-01-01-000000")
+        .run(connection)
+        .unwrap();
+    Color::create_type_migration("2024-01-01-000001")
+        .run(connection)
+        .unwrap();
+    assert!(UserKind::sync_variants(connection, true).unwrap().is_empty());
+    assert!(Color::sync_variants(connection, true).unwrap().is_empty());
+
+    let (kind, color): (UserKind, Color) = diesel::select((
+        UserKind::Bot.into_sql::<UserKindMapping>(),
+        Color::Green.into_sql::<ColorMapping>(),
+    ))
+    .get_result(connection)
+    .unwrap();
+    assert_eq!((kind, color), (UserKind::Bot, Color::Green));
+}
@@ -0,0 +1,65 @@
+use diesel::connection::SimpleConnection;
+use diesel::insert_into;
+use diesel::prelude::*;
+
+use crate::common::get_connection;
+
+// A real deployment gives the oids of its enum type, which differ per database. The oids
+// of `text` and `text[]` are the same everywhere, so they stand in for them here
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[PgOid = 25]
+#[PgArrayOid = 1009]
+pub enum Fixed {
+    Left,
+    Right,
+}
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(pg_oid = 25, pg_array_oid = 1009)]
+pub enum FixedByKey {
+    Up,
+    Down,
+}
+
+table! {
+    use diesel::sql_types::{Array, Integer};
+    use super::FixedMapping;
+    test_pg_oid {
+        id -> Integer,
+        side -> FixedMapping,
+        sides -> Array<FixedMapping>,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_pg_oid)]
+struct Wing {
+    id: i32,
+    side: Fixed,
+    sides: Vec<Fixed>,
+}
+
+#[test]
+fn fixed_oids_skip_lookup() {
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(
+            "CREATE TABLE test_pg_oid (
+                id SERIAL PRIMARY KEY,
+                side TEXT NOT NULL,
+                sides TEXT[] NOT NULL
+            );",
+        )
+        .unwrap();
+    let data = vec![Wing {
+        id: 1,
+        side: Fixed::Right,
+        sides: vec![Fixed::Left, Fixed::Right],
+    }];
+    insert_into(test_pg_oid::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let items = test_pg_oid::table.load::<Wing>(connection).unwrap();
+    assert_eq!(data, items);
+}
@@ -0,0 +1,134 @@
+use tokio_postgres::NoTls;
+
+#[derive(Debug, PartialEq, Clone, Copy, diesel_derive_enum::DbEnum)]
+#[DbEnumPostgresTypes]
+#[ExistingTypePath = "crate::pg_postgres_types::LightMapping"]
+pub enum Light {
+    Red,
+    Amber,
+    Green,
+    #[db_null_variant]
+    Off,
+}
+
+#[derive(diesel::sql_types::SqlType)]
+#[diesel(postgres_type(name = "light"))]
+pub struct LightMapping;
+
+async fn get_client() -> tokio_postgres::Client {
+    let database_url =
+        ::std::env::var("PG_TEST_DATABASE_URL").expect("Env var PG_TEST_DATABASE_URL not set");
+    let (client, connection) = tokio_postgres::connect(&database_url, NoTls)
+        .await
+        .expect(&format!("Failed to connect to {}", database_url));
+    tokio::spawn(connection);
+    client
+        .batch_execute(
+            r#"
+        SET search_path TO pg_temp;
+        CREATE TYPE light AS ENUM ('red', 'amber', 'green');
+        CREATE TABLE test_lights (
+            id SERIAL PRIMARY KEY,
+            light light,
+            light_text TEXT
+        );
+    "#,
+        )
+        .await
+        .unwrap();
+    client
+}
+
+#[tokio::test]
+async fn tokio_postgres_round_trip() {
+    let client = get_client().await;
+    for light in [Light::Red, Light::Amber, Light::Green, Light::Off] {
+        client
+            .execute(
+                "INSERT INTO test_lights (light, light_text) VALUES ($1, $2)",
+                &[&light, &light],
+            )
+            .await
+            .unwrap();
+    }
+    let rows = client
+        .query("SELECT light, light_text FROM test_lights ORDER BY id", &[])
+        .await
+        .unwrap();
+    let lights: Vec<(Light, Light)> = rows.iter().map(|row| (row.get(0), row.get(1))).collect();
+    assert_eq!(
+        lights,
+        vec![
+            (Light::Red, Light::Red),
+            (Light::Amber, Light::Amber),
+            (Light::Green, Light::Green),
+            (Light::Off, Light::Off),
+        ]
+    );
+    let text: Option<String> = client
+        .query_one("SELECT light_text FROM test_lights WHERE id = 2", &[])
+        .await
+        .unwrap()
+        .get(0);
+    assert_eq!(text.as_deref(), Some("amber"));
+}
+
+#[tokio::test]
+async fn tokio_postgres_rejects_other_enums() {
+    let client = get_client().await;
+    client
+        .batch_execute("CREATE TYPE colour AS ENUM ('red', 'blue');")
+        .await
+        .unwrap();
+    let err = client
+        .query_one("SELECT 'red'::colour", &[])
+        .await
+        .unwrap()
+        .try_get::<_, Light>(0)
+        .unwrap_err();
+    assert!(err.to_string().contains("error deserializing column 0"));
+}
+
+pub fn shout(level: &Level) -> String {
+    match level {
+        Level::Low => "LOW!".to_string(),
+        Level::High => "HIGH!".to_string(),
+    }
+}
+
+pub fn hush(bytes: &[u8]) -> Result<Level, String> {
+    match bytes {
+        b"LOW!" => Ok(Level::Low),
+        b"HIGH!" => Ok(Level::High),
+        _ => Err(format!("unknown level: {}", String::from_utf8_lossy(bytes))),
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, diesel_derive_enum::DbEnum)]
+#[DbEnumPostgresTypes]
+#[DbStorageByBackend(postgres = "text")]
+#[DbSerializeWith = "crate::pg_postgres_types::shout"]
+#[DbDeserializeWith = "crate::pg_postgres_types::hush"]
+pub enum Level {
+    Low,
+    High,
+}
+
+#[tokio::test]
+async fn tokio_postgres_applies_sql_hooks() {
+    let client = get_client().await;
+    client
+        .execute(
+            "INSERT INTO test_lights (light_text) VALUES ($1)",
+            &[&Level::High],
+        )
+        .await
+        .unwrap();
+    let row = client
+        .query_one("SELECT light_text FROM test_lights", &[])
+        .await
+        .unwrap();
+    // written by the hook, like diesel and sqlx write it
+    assert_eq!(row.get::<_, String>(0), "HIGH!");
+    assert_eq!(row.get::<_, Level>(0), Level::High);
+}
@@ -0,0 +1,44 @@
+use tokio_postgres::NoTls;
+
+#[derive(Debug, PartialEq, Clone, Copy, diesel_derive_enum::DbEnum)]
+pub enum Mood {
+    Happy,
+    NotSure,
+}
+
+#[test]
+fn create_type_refinery_migration() {
+    let migration = Mood::create_type_refinery_migration(7).unwrap();
+    assert_eq!(migration.version(), 7);
+    assert_eq!(migration.name(), "create_mood");
+    assert_eq!(
+        migration.sql(),
+        Some("CREATE TYPE mood AS ENUM ('happy', 'not_sure');")
+    );
+}
+
+#[tokio::test]
+async fn run_refinery_migration() {
+    let database_url =
+        ::std::env::var("PG_TEST_DATABASE_URL").expect("Env var PG_TEST_DATABASE_URL not set");
+    let (mut client, connection) = tokio_postgres::connect(&database_url, NoTls)
+        .await
+        .expect(&format!("Failed to connect to {}", database_url));
+    tokio::spawn(connection);
+    client
+        .batch_execute("SET search_path TO pg_temp;")
+        .await
+        .unwrap();
+    let migrations = [Mood::create_type_refinery_migration(1).unwrap()];
+    let report = refinery::Runner::new(&migrations)
+        .run_async(&mut client)
+        .await
+        .unwrap();
+    assert_eq!(report.applied_migrations().len(), 1);
+    let labels: Vec<String> = client
+        .query_one("SELECT enum_range(NULL::mood)::text[]", &[])
+        .await
+        .unwrap()
+        .get(0);
+    assert_eq!(labels, vec!["happy", "not_sure"]);
+}
@@ -54,3 +54,137 @@ fn enum_round_trip() {
         .unwrap();
     assert_eq!(data, res);
 }
+
+// the path may also be given unquoted - should compile
+
+#[derive(diesel::sql_types::SqlType)]
+#[diesel(postgres_type(name = "my_other_remote_enum"))]
+pub struct MyOtherRemoteEnumMapping;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[ExistingTypePath(MyOtherRemoteEnumMapping)]
+pub enum MyOtherRemoteEnum {
+    This,
+    That
+}
+
+// the path may also be inferred from a schema root and the enum name
+
+pub mod sql_types {
+    #[derive(diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "my_rooted_enum"))]
+    pub struct MyRootedEnum;
+
+    #[derive(diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "my_aliased_enum"))]
+    pub struct MyAliasedEnum;
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::sql_types::MyRootedEnum;
+    test_rooted {
+        id -> Integer,
+        my_enum -> MyRootedEnum,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, Clone, PartialEq)]
+#[diesel(table_name = test_rooted)]
+struct Rooted {
+    id: i32,
+    my_enum: MyRootedEnum
+}
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[DbSchemaRoot = "crate::pg_remote_type::sql_types"]
+pub enum MyRootedEnum {
+    This,
+    That
+}
+
+#[test]
+fn schema_root_round_trip() {
+    let connection = &mut get_connection();
+    use diesel::connection::SimpleConnection;
+
+    connection.batch_execute(
+        r#"
+        CREATE TYPE my_rooted_enum AS ENUM ('this', 'that');
+        CREATE TABLE test_rooted (
+            id SERIAL PRIMARY KEY,
+            my_enum my_rooted_enum NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+
+    let data = Rooted { id: 1, my_enum: MyRootedEnum::That };
+    let res = diesel::insert_into(test_rooted::table)
+        .values(&data)
+        .get_result(connection)
+        .unwrap();
+    assert_eq!(data, res);
+}
+
+// code written against the generated `MyAliasedEnumMapping` keeps compiling with the alias
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::MyAliasedEnumMapping;
+    test_aliased {
+        id -> Integer,
+        my_enum -> MyAliasedEnumMapping,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, Clone, PartialEq)]
+#[diesel(table_name = test_aliased)]
+struct Aliased {
+    id: i32,
+    my_enum: MyAliasedEnum
+}
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[DbSchemaRoot = "crate::pg_remote_type::sql_types"]
+#[ExistingTypeAlias]
+pub enum MyAliasedEnum {
+    This,
+    That
+}
+
+#[test]
+fn existing_type_alias_round_trip() {
+    let connection = &mut get_connection();
+    use diesel::connection::SimpleConnection;
+
+    connection.batch_execute(
+        r#"
+        CREATE TYPE my_aliased_enum AS ENUM ('this', 'that');
+        CREATE TABLE test_aliased (
+            id SERIAL PRIMARY KEY,
+            my_enum my_aliased_enum NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+
+    let data = Aliased { id: 1, my_enum: MyAliasedEnum::That };
+    let res = diesel::insert_into(test_aliased::table)
+        .values(&data)
+        .get_result(connection)
+        .unwrap();
+    assert_eq!(data, res);
+}
+
+#[test]
+fn existing_type_alias_consts() {
+    assert_eq!(MyAliasedEnumMapping::SQL_TYPE_NAME, "my_aliased_enum");
+    assert_eq!(MyAliasedEnumMapping::LABELS, &["this", "that"]);
+}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn existing_mapping_debug() {
+    assert_eq!(format!("{:?}", MyRemoteEnumMapping), "MyRemoteEnumMapping");
+}
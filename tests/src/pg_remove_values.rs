@@ -0,0 +1,180 @@
+use diesel::connection::SimpleConnection;
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::{Array, Text};
+
+use crate::common::get_connection;
+
+// `archived` was a variant before, and is still a label of the postgres type
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+pub enum DocState {
+    Draft,
+    Published,
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::DocStateMapping;
+    test_remove_values {
+        id -> Integer,
+        state -> DocStateMapping,
+    }
+}
+
+// in a schema of its own, whose name needs quoting
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[PgType = "RemoveApp.ticket_state"]
+pub enum TicketState {
+    Open,
+    Closed,
+}
+
+#[test]
+fn pg_remove_values_sql() {
+    assert_eq!(
+        DocState::pg_remove_values_sql(
+            &["draft", "archived", "published"],
+            &[("app", "docs", "state")],
+            &[("app", "docs", "history")],
+            &[("app", "docs", "state", "'draft'::doc_state")],
+        ),
+        "DO $guard$ BEGIN IF EXISTS (SELECT 1 FROM \"app\".\"docs\" WHERE \"state\"::text IN ('archived')) \
+         THEN RAISE EXCEPTION '%', 'app.docs.state holds removed values'; END IF; END $guard$;\n\
+         DO $guard$ BEGIN IF EXISTS (SELECT 1 FROM \"app\".\"docs\" WHERE \"history\"::text[] && ARRAY['archived']::text[]) \
+         THEN RAISE EXCEPTION '%', 'app.docs.history holds removed values'; END IF; END $guard$;\n\
+         ALTER TYPE doc_state RENAME TO doc_state_old;\n\
+         CREATE TYPE doc_state AS ENUM ('draft', 'published');\n\
+         ALTER TABLE \"app\".\"docs\" ALTER COLUMN \"state\" DROP DEFAULT;\n\
+         ALTER TABLE \"app\".\"docs\" ALTER COLUMN \"state\" TYPE doc_state USING \"state\"::text::doc_state;\n\
+         ALTER TABLE \"app\".\"docs\" ALTER COLUMN \"state\" SET DEFAULT 'draft'::doc_state;\n\
+         ALTER TABLE \"app\".\"docs\" ALTER COLUMN \"history\" TYPE doc_state[] USING \"history\"::text[]::doc_state[];\n\
+         DROP TYPE doc_state_old;\n"
+    );
+}
+
+#[test]
+fn pg_remove_values_sql_without_removed_values() {
+    // nothing to do when every previous value is still a variant
+    assert_eq!(
+        DocState::pg_remove_values_sql(
+            &["draft", "published"],
+            &[("app", "docs", "state")],
+            &[],
+            &[]
+        ),
+        ""
+    );
+}
+
+#[test]
+fn pg_remove_values_round_trip() {
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(
+            "CREATE TYPE doc_state AS ENUM ('draft', 'archived', 'published');
+             CREATE TABLE test_remove_values (
+                 id SERIAL PRIMARY KEY,
+                 state doc_state NOT NULL DEFAULT 'draft',
+                 history doc_state[] NOT NULL DEFAULT '{}'
+             );
+             INSERT INTO test_remove_values (id, state, history)
+             VALUES (1, 'published', '{draft,published}'), (2, 'archived', '{archived}'), (3, 'draft', '{}');",
+        )
+        .unwrap();
+    let previous = ["draft", "archived", "published"];
+    let migration = DocState::pg_remove_values_migration(connection, &previous).unwrap();
+    // rows still holding a removed value stop the migration
+    let err = connection.batch_execute(&migration).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("test_remove_values.state holds removed values"));
+    connection
+        .batch_execute(
+            "UPDATE test_remove_values SET state = 'draft' WHERE state = 'archived';
+             UPDATE test_remove_values SET history = array_remove(history, 'archived');",
+        )
+        .unwrap();
+    connection.batch_execute(&migration).unwrap();
+    let labels = diesel::select(sql::<Array<Text>>("enum_range(NULL::doc_state)::text[]"))
+        .get_result::<Vec<String>>(connection)
+        .unwrap();
+    assert_eq!(labels, vec!["draft", "published"]);
+    let states = test_remove_values::table
+        .order(test_remove_values::id)
+        .select(test_remove_values::state)
+        .load::<DocState>(connection)
+        .unwrap();
+    assert_eq!(
+        states,
+        vec![DocState::Published, DocState::Draft, DocState::Draft]
+    );
+    let histories = diesel::select(sql::<Array<Text>>(
+        "ARRAY(SELECT array_to_string(history, ',') FROM test_remove_values ORDER BY id)",
+    ))
+    .get_result::<Vec<String>>(connection)
+    .unwrap();
+    assert_eq!(histories, vec!["draft,published", "", ""]);
+    // the defaults are set again, on the new type
+    connection
+        .batch_execute("INSERT INTO test_remove_values (id) VALUES (4);")
+        .unwrap();
+    let state = test_remove_values::table
+        .find(4)
+        .select(test_remove_values::state)
+        .get_result::<DocState>(connection)
+        .unwrap();
+    assert_eq!(state, DocState::Draft);
+}
+
+#[test]
+fn pg_remove_values_sql_qualified() {
+    assert_eq!(
+        TicketState::pg_remove_values_sql(&["open", "stale", "closed"], &[("app", "tickets", "state")], &[], &[]),
+        "DO $guard$ BEGIN IF EXISTS (SELECT 1 FROM \"app\".\"tickets\" WHERE \"state\"::text IN ('stale')) \
+         THEN RAISE EXCEPTION '%', 'app.tickets.state holds removed values'; END IF; END $guard$;\n\
+         ALTER TYPE \"RemoveApp\".ticket_state RENAME TO ticket_state_old;\n\
+         CREATE TYPE \"RemoveApp\".ticket_state AS ENUM ('open', 'closed');\n\
+         ALTER TABLE \"app\".\"tickets\" ALTER COLUMN \"state\" TYPE \"RemoveApp\".ticket_state \
+         USING \"state\"::text::\"RemoveApp\".ticket_state;\n\
+         DROP TYPE \"RemoveApp\".ticket_state_old;\n"
+    );
+}
+
+#[test]
+fn pg_remove_values_round_trip_qualified() {
+    let connection = &mut get_connection();
+    // the schema isn't temporary, so leave nothing behind
+    connection.begin_test_transaction().unwrap();
+    connection
+        .batch_execute(
+            r#"CREATE SCHEMA "RemoveApp";
+             CREATE TYPE "RemoveApp".ticket_state AS ENUM ('open', 'stale', 'closed');
+             CREATE TABLE "RemoveApp".test_remove_values_qualified (
+                 id SERIAL PRIMARY KEY,
+                 state "RemoveApp".ticket_state NOT NULL
+             );
+             INSERT INTO "RemoveApp".test_remove_values_qualified (id, state)
+             VALUES (1, 'closed'), (2, 'open');"#,
+        )
+        .unwrap();
+    connection
+        .batch_execute(&TicketState::pg_remove_values_sql(
+            &["open", "stale", "closed"],
+            &[("RemoveApp", "test_remove_values_qualified", "state")],
+            &[],
+            &[],
+        ))
+        .unwrap();
+    let labels = diesel::select(sql::<Array<Text>>(
+        r#"enum_range(NULL::"RemoveApp".ticket_state)::text[]"#,
+    ))
+    .get_result::<Vec<String>>(connection)
+    .unwrap();
+    assert_eq!(labels, vec!["open", "closed"]);
+    let states = diesel::select(sql::<Array<Text>>(
+        r#"ARRAY(SELECT state::text FROM "RemoveApp".test_remove_values_qualified ORDER BY id)"#,
+    ))
+    .get_result::<Vec<String>>(connection)
+    .unwrap();
+    assert_eq!(states, vec!["closed", "open"]);
+}
@@ -0,0 +1,90 @@
+use diesel::connection::SimpleConnection;
+use diesel::insert_into;
+use diesel::prelude::*;
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+pub enum Plan {
+    Free,
+    #[db_renamed_from = "premium"]
+    Pro,
+    #[db_rename = "team's"]
+    #[db_renamed_from = "group"]
+    Team,
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::PlanMapping;
+    test_rename_values {
+        id -> Integer,
+        plan -> PlanMapping,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, Clone, PartialEq)]
+#[diesel(table_name = test_rename_values)]
+struct Account {
+    id: i32,
+    plan: Plan,
+}
+
+#[test]
+fn pg_rename_values_sql() {
+    assert_eq!(
+        Plan::PG_RENAME_VALUES_SQL,
+        &[
+            "ALTER TYPE plan RENAME VALUE 'premium' TO 'pro';",
+            "ALTER TYPE plan RENAME VALUE 'group' TO 'team''s';",
+        ]
+    );
+}
+
+#[test]
+fn pg_rename_values_migrate() {
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(
+            r#"
+        CREATE TYPE plan AS ENUM ('free', 'premium', 'group');
+        CREATE TABLE test_rename_values (
+            id SERIAL PRIMARY KEY,
+            plan plan NOT NULL
+        );
+        INSERT INTO test_rename_values (id, plan) VALUES (1, 'premium'), (2, 'group');
+    "#,
+        )
+        .unwrap();
+    for statement in Plan::PG_RENAME_VALUES_SQL {
+        connection.batch_execute(statement).unwrap();
+    }
+    insert_into(test_rename_values::table)
+        .values(&Account {
+            id: 3,
+            plan: Plan::Free,
+        })
+        .execute(connection)
+        .unwrap();
+    let accounts = test_rename_values::table
+        .order(test_rename_values::id)
+        .load::<Account>(connection)
+        .unwrap();
+    assert_eq!(
+        accounts,
+        vec![
+            Account {
+                id: 1,
+                plan: Plan::Pro,
+            },
+            Account {
+                id: 2,
+                plan: Plan::Team,
+            },
+            Account {
+                id: 3,
+                plan: Plan::Free,
+            },
+        ]
+    );
+}
@@ -0,0 +1,32 @@
+use diesel::connection::SimpleConnection;
+use diesel::prelude::*;
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, Copy, diesel_derive_enum::DbEnum)]
+#[PgType = "sorted_size"]
+pub enum Size {
+    Small,
+    Medium,
+    Large,
+}
+
+#[test]
+fn pg_sort_order() {
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(
+            "CREATE TYPE sorted_size AS ENUM ('small', 'large');
+             ALTER TYPE sorted_size ADD VALUE 'medium' BEFORE 'large';",
+        )
+        .unwrap();
+    assert_eq!(
+        Size::pg_sort_order(connection).unwrap(),
+        vec![(Size::Small, 1.0), (Size::Medium, 1.5), (Size::Large, 2.0)]
+    );
+
+    connection
+        .batch_execute("ALTER TYPE sorted_size ADD VALUE 'huge';")
+        .unwrap();
+    assert!(Size::pg_sort_order(connection).is_err());
+}
@@ -0,0 +1,92 @@
+use diesel::connection::SimpleConnection;
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::{Array, Text};
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+pub enum Region {
+    Africa,
+    Americas,
+    Asia,
+    Europe,
+    Oceania,
+}
+
+#[test]
+fn missing_variants_sql() {
+    let db_values = vec!["americas".to_string(), "europe".to_string()];
+    assert_eq!(
+        Region::missing_variants_sql(&db_values),
+        vec![
+            "ALTER TYPE region ADD VALUE 'africa' BEFORE 'americas';",
+            "ALTER TYPE region ADD VALUE 'asia' AFTER 'americas';",
+            "ALTER TYPE region ADD VALUE 'oceania' AFTER 'europe';",
+        ]
+    );
+    let all: Vec<String> = Region::all()
+        .iter()
+        .map(|region| format!("{:?}", region).to_lowercase())
+        .collect();
+    assert!(Region::missing_variants_sql(&all).is_empty());
+}
+
+fn db_values(conn: &mut PgConnection) -> Vec<String> {
+    diesel::select(sql::<Array<Text>>("enum_range(NULL::region)::text[]"))
+        .get_result(conn)
+        .unwrap()
+}
+
+#[test]
+fn sync_variants() {
+    let connection = &mut get_connection();
+    connection
+        .batch_execute("CREATE TYPE region AS ENUM ('americas', 'europe');")
+        .unwrap();
+    let planned = Region::sync_variants(connection, true).unwrap();
+    assert_eq!(planned.len(), 3);
+    assert_eq!(db_values(connection), vec!["americas", "europe"]);
+    let applied = Region::sync_variants(connection, false).unwrap();
+    assert_eq!(applied, planned);
+    assert_eq!(
+        db_values(connection),
+        vec!["africa", "americas", "asia", "europe", "oceania"]
+    );
+    assert!(Region::sync_variants(connection, false).unwrap().is_empty());
+}
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[DbEnumCockroach]
+pub enum Continent {
+    Africa,
+    Europe,
+}
+
+#[test]
+fn cockroach_missing_variants_sql() {
+    let db_values = vec!["europe".to_string()];
+    assert_eq!(
+        Continent::missing_variants_sql(&db_values),
+        vec!["ALTER TYPE continent ADD VALUE IF NOT EXISTS 'africa' BEFORE 'europe';"]
+    );
+    let migration = Continent::create_type_migration("2024-01-01-000000");
+    assert!(!migration.metadata().run_in_transaction());
+}
+
+#[test]
+fn cockroach_sync_variants_outside_transaction() {
+    let connection = &mut get_connection();
+    connection
+        .batch_execute("CREATE TYPE continent AS ENUM ('europe');")
+        .unwrap();
+    connection
+        .transaction::<_, diesel::result::Error, _>(|conn| {
+            assert!(Continent::sync_variants(conn, false).is_err());
+            assert_eq!(Continent::sync_variants(conn, true)?.len(), 1);
+            Ok(())
+        })
+        .unwrap();
+    assert_eq!(Continent::sync_variants(connection, false).unwrap().len(), 1);
+    assert!(Continent::sync_variants(connection, false).unwrap().is_empty());
+}
@@ -0,0 +1,96 @@
+use diesel::connection::SimpleConnection;
+use diesel::prelude::*;
+use diesel::sql_types::{Array, Text};
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+pub enum Weather {
+    Sunny,
+    Rainy,
+}
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[PgType = "Forecast"]
+pub enum Forecast {
+    Clear,
+    Cloudy,
+}
+
+#[derive(QueryableByName, Debug, PartialEq)]
+struct Week {
+    #[diesel(sql_type = Array<WeatherMapping>)]
+    days: Vec<Weather>,
+}
+
+#[test]
+fn pg_type_names() {
+    assert_eq!(Weather::PG_TYPE_NAME, "weather");
+    assert_eq!(Weather::PG_ARRAY_TYPE_NAME, "weather[]");
+    assert_eq!(Forecast::PG_TYPE_NAME, "\"Forecast\"");
+    assert_eq!(Forecast::PG_ARRAY_TYPE_NAME, "\"Forecast\"[]");
+}
+
+#[test]
+fn cast_with_array_type_name() {
+    let connection = &mut get_connection();
+    connection
+        .batch_execute("CREATE TYPE weather AS ENUM ('sunny', 'rainy');")
+        .unwrap();
+    let query = format!("SELECT CAST($1 AS {}) AS days", Weather::PG_ARRAY_TYPE_NAME);
+    let week = diesel::sql_query(query)
+        .bind::<Array<Text>, _>(vec!["rainy", "sunny"])
+        .get_result::<Week>(connection)
+        .unwrap();
+    assert_eq!(week.days, vec![Weather::Rainy, Weather::Sunny]);
+}
+
+const PRINTED_SCHEMA: &str = r#"// @generated automatically by Diesel CLI.
+
+pub mod sql_types {
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "Forecast"))]
+    pub struct Forecast;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "weather"))]
+    pub struct Weather;
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::Weather;
+
+    days (id) {
+        id -> Int4,
+        weather -> Weather,
+    }
+}
+"#;
+
+#[test]
+fn print_schema_patch() {
+    let patch = Weather::print_schema_patch(PRINTED_SCHEMA, "crate::weather::WeatherMapping");
+    assert_eq!(
+        patch.as_deref(),
+        Some(
+            r#"--- original
++++ modified
+@@ -5,9 +5,7 @@
+     #[diesel(postgres_type(name = "Forecast"))]
+     pub struct Forecast;
+ 
+-    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+-    #[diesel(postgres_type(name = "weather"))]
+-    pub struct Weather;
++    pub use crate::weather::WeatherMapping as Weather;
+ }
+ 
+ diesel::table! {
+"#
+        )
+    );
+    let patch = Forecast::print_schema_patch(PRINTED_SCHEMA, "crate::ForecastMapping").unwrap();
+    assert!(patch.contains("\n+    pub use crate::ForecastMapping as Forecast;\n"));
+    assert_eq!(Weather::print_schema_patch("pub mod sql_types {}", "Weather"), None);
+}
@@ -0,0 +1,103 @@
+use diesel::insert_into;
+use diesel::prelude::*;
+
+use crate::common::get_connection;
+
+// only ever loaded, e.g. for a reporting read model
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[DbReadOnly]
+pub enum Region {
+    North,
+    South,
+}
+
+// only ever appended, e.g. to an audit log
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[db_enum(write_only)]
+pub enum AuditAction {
+    Created,
+    Deleted,
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::{AuditActionMapping, RegionMapping};
+    test_read_write_only {
+        id -> Integer,
+        region -> RegionMapping,
+        action -> AuditActionMapping,
+    }
+}
+
+#[cfg(feature = "postgres")]
+pub fn create_table(conn: &mut PgConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TYPE region AS ENUM ('north', 'south');
+        CREATE TYPE audit_action AS ENUM ('created', 'deleted');
+        CREATE TABLE test_read_write_only (
+            id SERIAL PRIMARY KEY,
+            region region NOT NULL DEFAULT 'south',
+            action audit_action NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "mysql")]
+pub fn create_table(conn: &mut MysqlConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TEMPORARY TABLE IF NOT EXISTS test_read_write_only (
+            id SERIAL PRIMARY KEY,
+            region enum('north', 'south') NOT NULL DEFAULT 'south',
+            action enum('created', 'deleted') NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+pub fn create_table(conn: &mut SqliteConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TABLE test_read_write_only (
+            id SERIAL PRIMARY KEY,
+            region TEXT CHECK(region IN ('north', 'south')) NOT NULL DEFAULT 'south',
+            action TEXT CHECK(action IN ('created', 'deleted')) NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn read_only_and_write_only() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    let ct = insert_into(test_read_write_only::table)
+        .values((
+            test_read_write_only::id.eq(1),
+            test_read_write_only::action.eq(AuditAction::Deleted),
+        ))
+        .execute(connection)
+        .unwrap();
+    assert_eq!(ct, 1);
+    let deleted = test_read_write_only::table
+        .filter(test_read_write_only::action.eq(AuditAction::Deleted))
+        .count()
+        .get_result::<i64>(connection)
+        .unwrap();
+    assert_eq!(deleted, 1);
+    let region = test_read_write_only::table
+        .select(test_read_write_only::region)
+        .first::<Region>(connection)
+        .unwrap();
+    assert_eq!(region, Region::South);
+}
@@ -0,0 +1,52 @@
+use rocket::form::{Form, FromForm};
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rocket::{get, post, routes};
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[DbEnumRocket]
+#[DbValueStyle = "kebab-case"]
+pub enum Topping {
+    Cheese,
+    GreenPepper,
+}
+
+#[derive(FromForm)]
+struct Order {
+    topping: Topping,
+}
+
+#[get("/toppings/<topping>")]
+fn topping(topping: Topping) -> String {
+    format!("{:?}", topping)
+}
+
+#[post("/orders", data = "<order>")]
+fn order(order: Form<Order>) -> String {
+    format!("{:?}", order.topping)
+}
+
+#[test]
+fn route_params_and_forms_use_db_values() {
+    let rocket = rocket::build().mount("/", routes![topping, order]);
+    let client = Client::tracked(rocket).unwrap();
+
+    let response = client.get("/toppings/green-pepper").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap(), "GreenPepper");
+    let response = client.get("/toppings/GreenPepper").dispatch();
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+
+    let response = client
+        .post("/orders")
+        .header(ContentType::Form)
+        .body("topping=cheese")
+        .dispatch();
+    assert_eq!(response.into_string().unwrap(), "Cheese");
+    let response = client
+        .post("/orders")
+        .header(ContentType::Form)
+        .body("topping=pineapple")
+        .dispatch();
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common::{my_enum_as_db_string, MyEnum};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Event {
+    id: i32,
+    #[serde(with = "my_enum_as_db_string")]
+    kind: MyEnum,
+}
+
+#[test]
+fn serialize_as_db_string() {
+    let event = Event {
+        id: 1,
+        kind: MyEnum::BazQuxx,
+    };
+    let json = serde_json::to_string(&event).unwrap();
+    assert_eq!(json, r#"{"id":1,"kind":"baz_quxx"}"#);
+    assert_eq!(serde_json::from_str::<Event>(&json).unwrap(), event);
+}
+
+#[test]
+fn deserialize_unknown_db_string() {
+    let err = serde_json::from_str::<Event>(r#"{"id":1,"kind":"BazQuxx"}"#).unwrap_err();
+    assert!(err.to_string().contains("Unrecognized enum variant"));
+}
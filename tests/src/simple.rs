@@ -50,6 +50,77 @@ fn filter_by_enum() {
     );
 }
 
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn filter_by_all_variants() {
+    use crate::common::test_simple::dsl::*;
+    let connection = &mut get_connection();
+    create_table(connection);
+    let data = sample_data();
+    insert_into(test_simple)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    assert_eq!(MyEnum::all(), &[MyEnum::Foo, MyEnum::Bar, MyEnum::BazQuxx]);
+    let known: i64 = test_simple
+        .filter(my_enum.eq_any(MyEnum::all()))
+        .count()
+        .get_result(connection)
+        .unwrap();
+    assert_eq!(known, data.len() as i64);
+    let unknown: i64 = test_simple
+        .filter(my_enum.ne_all(MyEnum::all()))
+        .count()
+        .get_result(connection)
+        .unwrap();
+    assert_eq!(unknown, 0);
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn filter_by_dsl_helper() {
+    use crate::common::test_simple::dsl::*;
+    let connection = &mut get_connection();
+    create_table(connection);
+    let data = sample_data();
+    insert_into(test_simple)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let results = test_simple
+        .filter(my_enum_dsl::is_baz_quxx(my_enum))
+        .load::<Simple>(connection)
+        .unwrap();
+    assert_eq!(
+        results,
+        vec![Simple {
+            id: 2,
+            my_enum: MyEnum::BazQuxx,
+        }]
+    );
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn filter_by_dsl_method() {
+    use crate::common::test_simple::dsl::*;
+    use my_enum_dsl::MyEnumExpressionMethods;
+    let connection = &mut get_connection();
+    create_table(connection);
+    let data = sample_data();
+    insert_into(test_simple)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let results = test_simple
+        .filter(my_enum.is_baz_quxx().or(my_enum.is_bar()))
+        .select(id)
+        .order(id)
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(results, vec![2, 33]);
+}
+
 #[test]
 #[cfg(feature = "sqlite")]
 fn sqlite_invalid_enum() {
@@ -102,3 +173,56 @@ struct test_snake {
     id: i32,
     my_enum: my_enum,
 }
+
+#[derive(Insertable, Debug, PartialEq)]
+#[diesel(table_name = test_simple)]
+struct BorrowedSimple<'a> {
+    id: i32,
+    my_enum: &'a MyEnum,
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn borrowed_batch_insert() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    let data = sample_data();
+    let borrowed: Vec<BorrowedSimple> = data
+        .iter()
+        .map(|row| BorrowedSimple {
+            id: row.id,
+            my_enum: &row.my_enum,
+        })
+        .collect();
+    let ct = insert_into(test_simple::table)
+        .values(borrowed.as_slice())
+        .execute(connection)
+        .unwrap();
+    assert_eq!(data.len(), ct);
+    let items = test_simple::table.load::<Simple>(connection).unwrap();
+    assert_eq!(data, items);
+}
+
+#[test]
+#[cfg(feature = "mysql")]
+fn mysql_column_type_ddl() {
+    use diesel::connection::SimpleConnection;
+    assert_eq!(MyEnum::MYSQL_COLUMN_TYPE, "ENUM('foo', 'bar', 'baz_quxx')");
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(&format!(
+            "CREATE TEMPORARY TABLE IF NOT EXISTS test_simple (
+                id SERIAL PRIMARY KEY,
+                my_enum {} NOT NULL
+            );",
+            MyEnum::MYSQL_COLUMN_TYPE
+        ))
+        .unwrap();
+    let data = sample_data();
+    insert_into(test_simple::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let items = test_simple::table.load::<Simple>(connection).unwrap();
+    assert_eq!(data, items);
+}
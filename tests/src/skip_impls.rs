@@ -0,0 +1,113 @@
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql, Queryable};
+use diesel::insert_into;
+use diesel::prelude::*;
+
+use crate::common::get_connection;
+
+// `AsExpression` comes from diesel's own derive, and `Queryable` is written by hand
+#[derive(Debug, PartialEq, Clone, diesel::AsExpression, diesel_derive_enum::DbEnum)]
+#[diesel(sql_type = ShapeMapping)]
+#[DbEnumSkip(as_expression, queryable)]
+pub enum Shape {
+    Circle,
+    Square,
+}
+
+impl<DB> Queryable<ShapeMapping, DB> for Shape
+where
+    DB: Backend,
+    Self: FromSql<ShapeMapping, DB>,
+{
+    type Row = Self;
+
+    fn build(row: Self::Row) -> deserialize::Result<Self> {
+        Ok(row)
+    }
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::ShapeMapping;
+    test_skip_impls {
+        id -> Integer,
+        shape -> ShapeMapping,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, Clone, PartialEq)]
+#[diesel(table_name = test_skip_impls)]
+struct Drawing {
+    id: i32,
+    shape: Shape,
+}
+
+#[cfg(feature = "postgres")]
+pub fn create_table(conn: &mut PgConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TYPE shape AS ENUM ('circle', 'square');
+        CREATE TABLE test_skip_impls (
+            id SERIAL PRIMARY KEY,
+            shape shape NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "mysql")]
+pub fn create_table(conn: &mut MysqlConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TEMPORARY TABLE IF NOT EXISTS test_skip_impls (
+            id SERIAL PRIMARY KEY,
+            shape enum('circle', 'square') NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+pub fn create_table(conn: &mut SqliteConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TABLE test_skip_impls (
+            id SERIAL PRIMARY KEY,
+            shape TEXT CHECK(shape IN ('circle', 'square')) NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn skip_impls_round_trip() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    let data = vec![
+        Drawing {
+            id: 1,
+            shape: Shape::Circle,
+        },
+        Drawing {
+            id: 2,
+            shape: Shape::Square,
+        },
+    ];
+    let ct = insert_into(test_skip_impls::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    assert_eq!(data.len(), ct);
+    let items = test_skip_impls::table
+        .filter(test_skip_impls::shape.eq(Shape::Square))
+        .load::<Drawing>(connection)
+        .unwrap();
+    assert_eq!(items, vec![data[1].clone()]);
+}
@@ -0,0 +1,79 @@
+use diesel::connection::SimpleConnection;
+use diesel::dsl::sql;
+use diesel::insert_into;
+use diesel::prelude::*;
+use diesel::sql_types::Text;
+
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+use crate::common::get_connection;
+
+// stands in for encryption: the stored text is the code reversed
+pub fn obfuscate(clearance: &Clearance) -> String {
+    let code = match clearance {
+        Clearance::Public => "public",
+        Clearance::Secret => "secret",
+    };
+    code.chars().rev().collect()
+}
+
+pub fn reveal(bytes: &[u8]) -> Result<Clearance, String> {
+    let code: Vec<u8> = bytes.iter().rev().copied().collect();
+    match code.as_slice() {
+        b"public" => Ok(Clearance::Public),
+        b"secret" => Ok(Clearance::Secret),
+        _ => Err(format!("unknown clearance: {}", String::from_utf8_lossy(bytes))),
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[DbStorageByBackend(postgres = "text", mysql = "text")]
+#[DbSerializeWith = "crate::sql_hooks::obfuscate"]
+#[DbDeserializeWith = "crate::sql_hooks::reveal"]
+pub enum Clearance {
+    Public,
+    Secret,
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::ClearanceMapping;
+    test_sql_hooks {
+        id -> Integer,
+        clearance -> ClearanceMapping,
+    }
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn hooks_convert_values() {
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(
+            r#"
+        CREATE TEMPORARY TABLE test_sql_hooks (
+            id INTEGER PRIMARY KEY,
+            clearance TEXT NOT NULL
+        );
+    "#,
+        )
+        .unwrap();
+    insert_into(test_sql_hooks::table)
+        .values((
+            test_sql_hooks::id.eq(1),
+            test_sql_hooks::clearance.eq(Clearance::Secret),
+        ))
+        .execute(connection)
+        .unwrap();
+    let raw = test_sql_hooks::table
+        .select(sql::<Text>("clearance"))
+        .get_result::<String>(connection)
+        .unwrap();
+    assert_eq!(raw, "terces");
+    let clearance = test_sql_hooks::table
+        .select(test_sql_hooks::clearance)
+        .get_result::<Clearance>(connection)
+        .unwrap();
+    assert_eq!(clearance, Clearance::Secret);
+    // other conversions keep using the db value
+    assert_eq!(Clearance::Secret.to_sql_literal(), "'secret'");
+}
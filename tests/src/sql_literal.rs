@@ -0,0 +1,54 @@
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::Text;
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+pub enum Punctuation {
+    #[db_rename = "it's"]
+    Apostrophe,
+    #[db_rename = "back\\slash"]
+    Backslash,
+    Plain,
+}
+
+#[test]
+fn sql_literal_quoting() {
+    assert_eq!(Punctuation::Plain.to_sql_literal(), "'plain'");
+    assert_eq!(Punctuation::Apostrophe.to_sql_literal(), "'it''s'");
+    assert_eq!(Punctuation::Backslash.to_sql_literal(), "'back\\slash'");
+}
+
+#[cfg(feature = "postgres")]
+type Db = diesel::pg::Pg;
+#[cfg(feature = "mysql")]
+type Db = diesel::mysql::Mysql;
+#[cfg(feature = "sqlite")]
+type Db = diesel::sqlite::Sqlite;
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn sql_literal_round_trip() {
+    let connection = &mut get_connection();
+    for variant in Punctuation::all() {
+        let literal = variant.to_sql_literal_for::<Db>();
+        let value: String = diesel::select(sql::<Text>(&literal))
+            .get_result(connection)
+            .unwrap();
+        assert!(variant.matches_db_str(&value), "{} -> {}", literal, value);
+    }
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn db_rank_matches_quoted_values() {
+    let connection = &mut get_connection();
+    for (rank, variant) in Punctuation::all().iter().enumerate() {
+        let literal = variant.to_sql_literal_for::<Db>();
+        let value: i32 = diesel::select(Punctuation::db_rank(sql::<Text>(&literal)))
+            .get_result(connection)
+            .unwrap();
+        assert_eq!(value, rank as i32, "{}", literal);
+    }
+}
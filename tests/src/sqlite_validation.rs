@@ -0,0 +1,99 @@
+use diesel::connection::SimpleConnection;
+use diesel::prelude::*;
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+pub enum Flavour {
+    Vanilla,
+    #[db_rename = "mint's chip"]
+    MintChip,
+}
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[DbStorageByBackend(sqlite = "integer")]
+pub enum Scoops {
+    One,
+    Two,
+}
+
+#[test]
+fn validation_rejects_unknown_values() {
+    let connection = &mut get_connection();
+    connection
+        .batch_execute("CREATE TABLE cones (id INTEGER PRIMARY KEY, flavour TEXT)")
+        .unwrap();
+    Flavour::install_validation(connection, "cones", "flavour").unwrap();
+    // installing again replaces the triggers
+    Flavour::install_validation(connection, "cones", "flavour").unwrap();
+    connection
+        .batch_execute("INSERT INTO cones VALUES (1, 'vanilla'), (2, 'mint''s chip'), (3, NULL)")
+        .unwrap();
+    let err = connection
+        .batch_execute("INSERT INTO cones VALUES (4, 'chocolate')")
+        .unwrap_err();
+    assert_eq!(err.to_string(), "invalid value for cones.flavour");
+    assert!(connection
+        .batch_execute("UPDATE cones SET flavour = 'chocolate' WHERE id = 1")
+        .is_err());
+}
+
+#[test]
+fn validation_of_integer_storage() {
+    let connection = &mut get_connection();
+    connection
+        .batch_execute("CREATE TABLE \"ice cream\" (id INTEGER PRIMARY KEY, scoops INTEGER)")
+        .unwrap();
+    Scoops::install_validation(connection, "ice cream", "scoops").unwrap();
+    connection
+        .batch_execute("INSERT INTO \"ice cream\" VALUES (1, 0), (2, 1)")
+        .unwrap();
+    assert!(connection
+        .batch_execute("INSERT INTO \"ice cream\" VALUES (3, 2)")
+        .is_err());
+}
+
+#[test]
+fn check_constraint_drift() {
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(
+            "CREATE TABLE orders (
+                id INTEGER PRIMARY KEY,
+                -- not the constraint on flavour
+                size TEXT CHECK (size IN ('small', 'large')),
+                flavour TEXT NOT NULL CHECK (\"Flavour\" IN ('mint''s chip', 'vanilla')),
+                scoops INTEGER,
+                CHECK (scoops IN (0, 1, 2))
+            )",
+        )
+        .unwrap();
+    Flavour::assert_check_matches_db(connection, "orders", "flavour").unwrap();
+    assert_eq!(
+        Scoops::assert_check_matches_db(connection, "orders", "scoops").unwrap_err(),
+        "CHECK constraint mismatch for scoops: database allows [\"0\", \"1\", \"2\"], \
+         rust has [\"0\", \"1\"]"
+    );
+    assert_eq!(
+        Flavour::assert_check_matches_db(connection, "orders", "id").unwrap_err(),
+        "no `CHECK (id IN (...))` constraint in the table"
+    );
+    assert_eq!(
+        Flavour::assert_check_matches_db(connection, "missing", "flavour").unwrap_err(),
+        "no table named `missing`"
+    );
+}
+
+#[test]
+fn check_constraint_from_sql() {
+    assert!(Flavour::check_constraint_matches(
+        "CREATE TABLE t (f TEXT, /* f IN ('x') */ CONSTRAINT valid CHECK ([f] IN ('vanilla', 'mint''s chip')))",
+        "F",
+    )
+    .is_ok());
+    assert!(Flavour::check_constraint_matches(
+        "CREATE TABLE t (f TEXT CHECK (f NOT IN ('vanilla')))",
+        "f",
+    )
+    .is_err());
+}
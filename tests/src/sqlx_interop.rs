@@ -0,0 +1,120 @@
+use sqlx::{Connection, Executor};
+
+#[derive(Debug, PartialEq, Clone, Copy, diesel_derive_enum::DbEnum)]
+#[DbEnumSqlx]
+pub enum Fruit {
+    Apple,
+    BloodOrange,
+    #[db_null_variant]
+    Unknown,
+}
+
+const FRUITS: [Fruit; 3] = [Fruit::Apple, Fruit::BloodOrange, Fruit::Unknown];
+
+#[cfg(feature = "postgres")]
+#[tokio::test]
+async fn sqlx_postgres_round_trip() {
+    let database_url =
+        ::std::env::var("PG_TEST_DATABASE_URL").expect("Env var PG_TEST_DATABASE_URL not set");
+    let mut conn = sqlx::PgConnection::connect(&database_url).await.unwrap();
+    conn.execute(
+        r#"
+        SET search_path TO pg_temp;
+        CREATE TYPE fruit AS ENUM ('apple', 'blood_orange');
+        CREATE TABLE test_sqlx (
+            id SERIAL PRIMARY KEY,
+            fruit fruit,
+            fruit_text TEXT
+        );
+    "#,
+    )
+    .await
+    .unwrap();
+    for fruit in FRUITS {
+        sqlx::query("INSERT INTO test_sqlx (fruit, fruit_text) VALUES ($1, $2)")
+            .bind(fruit)
+            .bind(fruit)
+            .execute(&mut conn)
+            .await
+            .unwrap();
+    }
+    let fruits: Vec<(Fruit, Fruit)> =
+        sqlx::query_as("SELECT fruit, fruit_text FROM test_sqlx ORDER BY id")
+            .fetch_all(&mut conn)
+            .await
+            .unwrap();
+    assert_eq!(fruits, FRUITS.map(|fruit| (fruit, fruit)));
+    // the same labels as diesel writes
+    let labels: Vec<Option<String>> =
+        sqlx::query_scalar("SELECT fruit::text FROM test_sqlx ORDER BY id")
+            .fetch_all(&mut conn)
+            .await
+            .unwrap();
+    assert_eq!(
+        labels,
+        vec![Some("apple".to_string()), Some("blood_orange".to_string()), None]
+    );
+}
+
+#[cfg(feature = "mysql")]
+#[tokio::test]
+async fn sqlx_mysql_round_trip() {
+    let database_url = ::std::env::var("MYSQL_TEST_DATABASE_URL")
+        .expect("Env var MYSQL_TEST_DATABASE_URL not set");
+    let mut conn = sqlx::MySqlConnection::connect(&database_url).await.unwrap();
+    conn.execute(
+        r#"
+        CREATE TEMPORARY TABLE IF NOT EXISTS test_sqlx (
+            id SERIAL PRIMARY KEY,
+            fruit enum('apple', 'blood_orange'),
+            fruit_text TEXT
+        );
+    "#,
+    )
+    .await
+    .unwrap();
+    for fruit in FRUITS {
+        sqlx::query("INSERT INTO test_sqlx (fruit, fruit_text) VALUES (?, ?)")
+            .bind(fruit)
+            .bind(fruit)
+            .execute(&mut conn)
+            .await
+            .unwrap();
+    }
+    let fruits: Vec<(Fruit, Fruit)> =
+        sqlx::query_as("SELECT fruit, fruit_text FROM test_sqlx ORDER BY id")
+            .fetch_all(&mut conn)
+            .await
+            .unwrap();
+    assert_eq!(fruits, FRUITS.map(|fruit| (fruit, fruit)));
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn sqlx_sqlite_round_trip() {
+    let mut conn = sqlx::SqliteConnection::connect("sqlite::memory:")
+        .await
+        .unwrap();
+    conn.execute(
+        r#"
+        CREATE TABLE test_sqlx (
+            id INTEGER PRIMARY KEY,
+            fruit TEXT CHECK(fruit IN ('apple', 'blood_orange'))
+        );
+    "#,
+    )
+    .await
+    .unwrap();
+    for fruit in FRUITS {
+        sqlx::query("INSERT INTO test_sqlx (fruit) VALUES (?)")
+            .bind(fruit)
+            .execute(&mut conn)
+            .await
+            .unwrap();
+    }
+    let fruits: Vec<Fruit> = sqlx::query_scalar("SELECT fruit FROM test_sqlx ORDER BY id")
+        .fetch_all(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(fruits, FRUITS);
+}
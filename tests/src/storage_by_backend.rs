@@ -0,0 +1,258 @@
+use diesel::insert_into;
+use diesel::prelude::*;
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[DbStorageByBackend(postgres = "enum", mysql = "text", sqlite = "integer")]
+pub enum Priority {
+    Low,
+    Medium,
+    High,
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::PriorityMapping;
+    test_storage_by_backend {
+        id -> Integer,
+        priority -> PriorityMapping,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, Clone, PartialEq)]
+#[diesel(table_name = test_storage_by_backend)]
+struct Task {
+    id: i32,
+    priority: Priority,
+}
+
+#[cfg(feature = "postgres")]
+pub fn create_table(conn: &mut PgConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TYPE priority AS ENUM ('low', 'medium', 'high');
+        CREATE TABLE test_storage_by_backend (
+            id SERIAL PRIMARY KEY,
+            priority priority NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "mysql")]
+pub fn create_table(conn: &mut MysqlConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(&format!(
+        "CREATE TEMPORARY TABLE IF NOT EXISTS test_storage_by_backend (
+            id SERIAL PRIMARY KEY,
+            priority {} NOT NULL
+        );",
+        Priority::MYSQL_COLUMN_TYPE
+    ))
+    .unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+pub fn create_table(conn: &mut SqliteConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TABLE test_storage_by_backend (
+            id SERIAL PRIMARY KEY,
+            priority INTEGER NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn storage_by_backend_round_trip() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    let data = vec![
+        Task {
+            id: 1,
+            priority: Priority::High,
+        },
+        Task {
+            id: 2,
+            priority: Priority::Low,
+        },
+    ];
+    let ct = insert_into(test_storage_by_backend::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    assert_eq!(data.len(), ct);
+    let items = test_storage_by_backend::table
+        .load::<Task>(connection)
+        .unwrap();
+    assert_eq!(data, items);
+    let high = test_storage_by_backend::table
+        .filter(test_storage_by_backend::priority.eq(Priority::High))
+        .select(test_storage_by_backend::id)
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(high, vec![1]);
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn order_by_db_rank() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    let data = vec![
+        Task {
+            id: 1,
+            priority: Priority::High,
+        },
+        Task {
+            id: 2,
+            priority: Priority::Low,
+        },
+        Task {
+            id: 3,
+            priority: Priority::Medium,
+        },
+    ];
+    insert_into(test_storage_by_backend::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let ids = test_storage_by_backend::table
+        .select(test_storage_by_backend::id)
+        .order_by(Priority::db_rank(test_storage_by_backend::priority))
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(ids, vec![2, 3, 1]);
+}
+
+#[test]
+#[cfg(feature = "sqlite")]
+fn sqlite_stores_declaration_index() {
+    use diesel::dsl::sql;
+    use diesel::sql_types::Integer;
+    let connection = &mut get_connection();
+    create_table(connection);
+    insert_into(test_storage_by_backend::table)
+        .values(&Task {
+            id: 1,
+            priority: Priority::Medium,
+        })
+        .execute(connection)
+        .unwrap();
+    let raw = test_storage_by_backend::table
+        .select(sql::<Integer>("priority"))
+        .get_result::<i32>(connection)
+        .unwrap();
+    assert_eq!(raw, 1);
+}
+
+// as `diesel print-schema` would name the column types on each database
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[DieselTypeByBackend(mysql = "TasksVisibilityEnum", sqlite = "VisibilityInteger")]
+#[DbStorageByBackend(sqlite = "integer")]
+pub enum Visibility {
+    Public,
+    Private,
+}
+
+#[cfg(feature = "postgres")]
+use VisibilityMapping as VisibilitySql;
+#[cfg(feature = "mysql")]
+use TasksVisibilityEnum as VisibilitySql;
+#[cfg(feature = "sqlite")]
+use VisibilityInteger as VisibilitySql;
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::VisibilitySql;
+    test_diesel_type_by_backend {
+        id -> Integer,
+        visibility -> VisibilitySql,
+    }
+}
+
+#[cfg(feature = "postgres")]
+pub fn create_visibility_table(conn: &mut PgConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TYPE visibility AS ENUM ('public', 'private');
+        CREATE TABLE test_diesel_type_by_backend (
+            id SERIAL PRIMARY KEY,
+            visibility visibility NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "mysql")]
+pub fn create_visibility_table(conn: &mut MysqlConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TEMPORARY TABLE IF NOT EXISTS test_diesel_type_by_backend (
+            id SERIAL PRIMARY KEY,
+            visibility enum('public', 'private') NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+pub fn create_visibility_table(conn: &mut SqliteConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TABLE test_diesel_type_by_backend (
+            id SERIAL PRIMARY KEY,
+            visibility INTEGER NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn diesel_type_by_backend_round_trip() {
+    let connection = &mut get_connection();
+    create_visibility_table(connection);
+    insert_into(test_diesel_type_by_backend::table)
+        .values((
+            test_diesel_type_by_backend::id.eq(1),
+            test_diesel_type_by_backend::visibility.eq(Visibility::Private),
+        ))
+        .execute(connection)
+        .unwrap();
+    let visibility = test_diesel_type_by_backend::table
+        .select(test_diesel_type_by_backend::visibility)
+        .get_result::<Visibility>(connection)
+        .unwrap();
+    assert_eq!(visibility, Visibility::Private);
+}
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[DbStorageByBackend(mysql = "varchar")]
+pub enum Review {
+    Pending,
+    Approved,
+    #[db_rename = "zurückgewiesen"]
+    Rejected,
+}
+
+#[test]
+#[cfg(feature = "mysql")]
+fn mysql_varchar_column_type() {
+    assert_eq!(Priority::MYSQL_COLUMN_TYPE, "VARCHAR(6)");
+    // sized in characters, not bytes
+    assert_eq!(Review::MYSQL_COLUMN_TYPE, "VARCHAR(14)");
+}
@@ -0,0 +1,110 @@
+use diesel::insert_into;
+use diesel::prelude::*;
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[DbTextMapping]
+pub enum Stage {
+    Draft,
+    Published,
+}
+
+// mid-migration: `stage` is the new enum column, `legacy_stage` the old text column
+table! {
+    use diesel::sql_types::Integer;
+    use super::{StageMapping, StageTextMapping};
+    test_text_mapping {
+        id -> Integer,
+        stage -> StageMapping,
+        legacy_stage -> StageTextMapping,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, Clone, PartialEq)]
+#[diesel(table_name = test_text_mapping)]
+struct Article {
+    id: i32,
+    stage: Stage,
+    legacy_stage: Stage,
+}
+
+#[cfg(feature = "postgres")]
+pub fn create_table(conn: &mut PgConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TYPE stage AS ENUM ('draft', 'published');
+        CREATE TABLE test_text_mapping (
+            id SERIAL PRIMARY KEY,
+            stage stage NOT NULL,
+            legacy_stage TEXT NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "mysql")]
+pub fn create_table(conn: &mut MysqlConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TEMPORARY TABLE IF NOT EXISTS test_text_mapping (
+            id SERIAL PRIMARY KEY,
+            stage enum('draft', 'published') NOT NULL,
+            legacy_stage TEXT NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+pub fn create_table(conn: &mut SqliteConnection) {
+    use diesel::connection::SimpleConnection;
+    conn.batch_execute(
+        r#"
+        CREATE TABLE test_text_mapping (
+            id SERIAL PRIMARY KEY,
+            stage TEXT CHECK(stage IN ('draft', 'published')) NOT NULL,
+            legacy_stage TEXT NOT NULL
+        );
+    "#,
+    )
+    .unwrap();
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn text_mapping_round_trip() {
+    let connection = &mut get_connection();
+    create_table(connection);
+    let data = vec![
+        Article {
+            id: 1,
+            stage: Stage::Published,
+            legacy_stage: Stage::Published,
+        },
+        Article {
+            id: 2,
+            stage: Stage::Draft,
+            legacy_stage: Stage::Draft,
+        },
+    ];
+    let ct = insert_into(test_text_mapping::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    assert_eq!(data.len(), ct);
+    let items = test_text_mapping::table
+        .load::<Article>(connection)
+        .unwrap();
+    assert_eq!(data, items);
+    let drafts = test_text_mapping::table
+        .filter(test_text_mapping::legacy_stage.eq(Stage::Draft))
+        .select(test_text_mapping::id)
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(drafts, vec![2]);
+}
@@ -0,0 +1,40 @@
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::Text;
+
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[DbEnumTrustedRead]
+#[DbEnumAsText]
+pub enum Size {
+    Small,
+    Medium,
+    Large,
+    ExtraLarge,
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn trusted_read_round_trip() {
+    let connection = &mut get_connection();
+    for size in Size::all() {
+        let read = diesel::select(sql::<Text>(&size.to_sql_literal()))
+            .get_result::<Size>(connection)
+            .unwrap();
+        assert_eq!(&read, size);
+    }
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn trusted_read_compares_prefix() {
+    let connection = &mut get_connection();
+    // `s`, `m`, `l` and `e` are enough to tell the variants apart
+    let read = diesel::select(sql::<Text>("'moderate'"))
+        .get_result::<Size>(connection)
+        .unwrap();
+    assert_eq!(read, Size::Medium);
+    let unknown = diesel::select(sql::<Text>("'tiny'")).get_result::<Size>(connection);
+    assert!(unknown.is_err());
+}
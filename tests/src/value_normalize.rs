@@ -0,0 +1,55 @@
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::Text;
+
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+use crate::common::get_connection;
+
+pub fn trim_lowercase(value: &str) -> String {
+    value.trim().to_lowercase()
+}
+
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[DbValueNormalize = "crate::value_normalize::trim_lowercase"]
+#[DbEnumAsText]
+pub enum Account {
+    Active,
+    // a value from before the data was normalized
+    #[db_rename = "On-Hold"]
+    OnHold,
+}
+
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[db_enum(value_normalize = "str::to_uppercase", value_style(verbatim))]
+pub enum Grade {
+    Pass,
+    Fail,
+}
+
+#[test]
+fn normalized_value_is_written() {
+    assert_eq!(Account::Active.to_sql_literal(), "'active'");
+    assert_eq!(Account::OnHold.to_sql_literal(), "'on-hold'");
+    assert_eq!(Grade::Pass.to_sql_literal(), "'PASS'");
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn mixed_values_are_read() {
+    let connection = &mut get_connection();
+    for value in ["'active'", "'ACTIVE'", "' Active '"] {
+        let read = diesel::select(sql::<Text>(value))
+            .get_result::<Account>(connection)
+            .unwrap();
+        assert_eq!(read, Account::Active);
+    }
+    for value in ["'on-hold'", "'On-Hold'"] {
+        let read = diesel::select(sql::<Text>(value))
+            .get_result::<Account>(connection)
+            .unwrap();
+        assert_eq!(read, Account::OnHold);
+    }
+    assert!(diesel::select(sql::<Text>("'closed'"))
+        .get_result::<Account>(connection)
+        .is_err());
+}
@@ -7,6 +7,7 @@ use crate::common::get_connection;
 #[DieselType = "Stylized_Internal_Type"]
 #[PgType = "Stylized_External_Type"]
 #[DbValueStyle = "PascalCase"]
+#[DbEnumStrEq]
 pub enum StylizedEnum {
     FirstVariant,
     secondThing,
@@ -139,3 +140,92 @@ fn stylized_round_trip() {
         .unwrap();
     assert_eq!(data, inserted);
 }
+
+// the style may also be given unquoted - should compile
+// (but we won't actually bother round-tripping)
+
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[DbValueStyle(kebab-case)]
+pub enum IdentStylizedEnum {
+    FirstVariant,
+    SecondThing,
+}
+
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[DbValueStyle("SCREAMING_SNAKE_CASE")]
+pub enum QuotedStylizedEnum {
+    FirstVariant,
+    SecondThing,
+}
+
+// serde's name for the same option
+
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[DbRenameAll = "camelCase"]
+pub enum RenameAllEnum {
+    FirstVariant,
+    SecondThing,
+}
+
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[db_enum(rename_all(SCREAMING_SNAKE_CASE))]
+pub enum NestedRenameAllEnum {
+    FirstVariant,
+    SecondThing,
+}
+
+#[test]
+fn rename_all_alias() {
+    assert!(RenameAllEnum::FirstVariant.matches_db_str("firstVariant"));
+    assert!(RenameAllEnum::SecondThing.matches_db_str("secondThing"));
+    assert!(NestedRenameAllEnum::FirstVariant.matches_db_str("FIRST_VARIANT"));
+    assert!(NestedRenameAllEnum::SecondThing.matches_db_str("SECOND_THING"));
+}
+
+#[test]
+fn compare_with_db_str() {
+    assert!(StylizedEnum::secondThing.matches_db_str("SecondThing"));
+    assert!(!StylizedEnum::secondThing.matches_db_str("secondThing"));
+    assert_eq!(StylizedEnum::cRaZy_FiFtH, "crazy fifth");
+    assert_eq!("ThirdItem", StylizedEnum::third_item);
+    assert_ne!(StylizedEnum::FOURTH_VALUE, *"FOURTH_VALUE");
+}
+
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[PgTypeStyle = "PascalCase"]
+pub enum TicketState {
+    Open,
+    InReview,
+}
+
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[db_enum(pg_type_style(kebab-case))]
+pub enum TicketKind {
+    Bug,
+    FeatureRequest,
+}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn styled_pg_type_names() {
+    use diesel::connection::SimpleConnection;
+    use diesel::dsl::sql;
+
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(
+            r#"
+        CREATE TYPE "TicketState" AS ENUM ('open', 'in_review');
+        CREATE TYPE "ticket-kind" AS ENUM ('bug', 'feature_request');
+    "#,
+        )
+        .unwrap();
+    let state = diesel::select(sql::<TicketStateMapping>(r#"'in_review'::"TicketState""#))
+        .get_result::<TicketState>(connection)
+        .unwrap();
+    assert_eq!(state, TicketState::InReview);
+    let kind = diesel::select(TicketKind::FeatureRequest.into_sql::<TicketKindMapping>())
+        .get_result::<TicketKind>(connection)
+        .unwrap();
+    assert_eq!(kind, TicketKind::FeatureRequest);
+}
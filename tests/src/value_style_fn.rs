@@ -0,0 +1,142 @@
+use diesel::prelude::*;
+
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+use crate::common::get_connection;
+
+pub fn prefixed_style(variant: &str) -> String {
+    format!("st_{}", variant.to_lowercase())
+}
+
+#[derive(Debug, PartialEq, diesel_derive_enum::DbEnum)]
+#[DbValueStyleFn = "crate::value_style_fn::prefixed_style"]
+#[db_enum(generate_tests)]
+pub enum StyleFnEnum {
+    FirstVariant,
+    SecondThing,
+    #[db_rename = "third"]
+    ThirdItem,
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::StyleFnEnumMapping;
+    test_value_style_fn {
+        id -> Integer,
+        value -> StyleFnEnumMapping,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_value_style_fn)]
+struct TestStyleFn {
+    id: i32,
+    value: StyleFnEnum,
+}
+
+fn sample_data() -> Vec<TestStyleFn> {
+    vec![
+        TestStyleFn {
+            id: 1,
+            value: StyleFnEnum::FirstVariant,
+        },
+        TestStyleFn {
+            id: 2,
+            value: StyleFnEnum::SecondThing,
+        },
+        TestStyleFn {
+            id: 3,
+            value: StyleFnEnum::ThirdItem,
+        },
+    ]
+}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn style_fn_round_trip() {
+    use diesel::connection::SimpleConnection;
+    use diesel::insert_into;
+    let data = sample_data();
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(
+            r#"
+        CREATE TYPE style_fn_enum AS ENUM ('st_firstvariant', 'st_secondthing', 'third');
+        CREATE TABLE test_value_style_fn (
+            id SERIAL PRIMARY KEY,
+            value style_fn_enum NOT NULL
+        );
+    "#,
+        )
+        .unwrap();
+    let inserted = insert_into(test_value_style_fn::table)
+        .values(&data)
+        .get_results(connection)
+        .unwrap();
+    assert_eq!(data, inserted);
+}
+
+#[test]
+#[cfg(feature = "mysql")]
+fn style_fn_round_trip() {
+    use diesel::connection::SimpleConnection;
+    use diesel::insert_into;
+    let data = sample_data();
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(
+            r#"
+        CREATE TEMPORARY TABLE IF NOT EXISTS test_value_style_fn (
+            id SERIAL PRIMARY KEY,
+            value enum('st_firstvariant', 'st_secondthing', 'third') NOT NULL
+        );
+    "#,
+        )
+        .unwrap();
+    insert_into(test_value_style_fn::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let inserted = test_value_style_fn::table
+        .load::<TestStyleFn>(connection)
+        .unwrap();
+    assert_eq!(data, inserted);
+}
+
+#[test]
+#[cfg(feature = "sqlite")]
+fn style_fn_round_trip() {
+    use diesel::connection::SimpleConnection;
+    use diesel::insert_into;
+    let data = sample_data();
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(
+            r#"
+        CREATE TABLE test_value_style_fn (
+            id SERIAL PRIMARY KEY,
+            value TEXT CHECK(value IN ('st_firstvariant', 'st_secondthing', 'third')) NOT NULL
+        );
+    "#,
+        )
+        .unwrap();
+    insert_into(test_value_style_fn::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let inserted = test_value_style_fn::table
+        .load::<TestStyleFn>(connection)
+        .unwrap();
+    assert_eq!(data, inserted);
+}
+
+#[test]
+fn style_fn_choices() {
+    assert_eq!(
+        StyleFnEnum::choices(),
+        &[
+            (StyleFnEnum::FirstVariant, "st_firstvariant", "FirstVariant"),
+            (StyleFnEnum::SecondThing, "st_secondthing", "SecondThing"),
+            (StyleFnEnum::ThirdItem, "third", "ThirdItem"),
+        ]
+    );
+}
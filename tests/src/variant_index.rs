@@ -0,0 +1,35 @@
+#[derive(Debug, PartialEq, Clone, Copy, diesel_derive_enum::DbEnum)]
+#[DbEnumIndex]
+#[DbStorageByBackend(postgres = "integer", mysql = "integer", sqlite = "integer")]
+pub enum Channel {
+    #[db_value(10)]
+    Email,
+    #[db_value(5)]
+    Sms,
+    #[db_value(20)]
+    Push,
+}
+
+#[test]
+fn index_follows_declaration_order() {
+    // independent of the db values
+    let indexes: Vec<usize> = Channel::all().iter().map(Channel::index).collect();
+    assert_eq!(indexes, vec![0, 1, 2]);
+    for channel in Channel::all() {
+        assert_eq!(Channel::try_from(channel.index()), Ok(*channel));
+    }
+    assert_eq!(Channel::try_from(3), Err(3));
+}
+
+#[test]
+fn index_keys_a_bit_set() {
+    let enabled = [Channel::Sms, Channel::Push]
+        .iter()
+        .fold(0u8, |bits, channel| bits | 1 << channel.index());
+    assert_eq!(enabled, 0b110);
+    let decoded: Vec<Channel> = (0..8)
+        .filter(|i| enabled & 1 << i != 0)
+        .map(|i| Channel::try_from(i).unwrap())
+        .collect();
+    assert_eq!(decoded, vec![Channel::Sms, Channel::Push]);
+}
@@ -0,0 +1,46 @@
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+    if !cfg!(any(
+        feature = "postgres",
+        feature = "mysql",
+        feature = "sqlite"
+    )) {
+        t.compile_fail("tests/ui/no_backend/*.rs");
+    }
+    if cfg!(feature = "postgres") {
+        t.compile_fail("tests/ui/postgres/*.rs");
+    }
+    if cfg!(feature = "mysql") {
+        t.compile_fail("tests/ui/mysql/*.rs");
+    }
+    if cfg!(all(feature = "postgres", feature = "live-check")) {
+        if let Ok(database_url) = std::env::var("DATABASE_URL") {
+            let status = std::process::Command::new("psql")
+                .args([&database_url, "-X", "-q", "-v", "ON_ERROR_STOP=1", "-c"])
+                .arg(
+                    "DROP TYPE IF EXISTS live_check_mood; \
+                     CREATE TYPE live_check_mood AS ENUM ('happy', 'sad');",
+                )
+                .status()
+                .expect("failed to run psql");
+            assert!(status.success());
+            t.compile_fail("tests/ui/live_check/*.rs");
+        } else {
+            let snapshot = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/ui/snapshot/db_enums.json");
+            std::env::set_var("DIESEL_DERIVE_ENUM_SNAPSHOT", snapshot);
+            t.compile_fail("tests/ui/snapshot/*.rs");
+        }
+    }
+    drop(t);
+
+    // a config applies to every enum of the crate, so each one is tested on its own
+    for config in ["config", "invalid_config"] {
+        let dir = format!("{}/tests/ui/{}", env!("CARGO_MANIFEST_DIR"), config);
+        std::env::set_var("DIESEL_DERIVE_ENUM_CONFIG", format!("{}/db-enum.toml", dir));
+        let t = trybuild::TestCases::new();
+        t.compile_fail(format!("tests/ui/{}/*.rs", config));
+    }
+    std::env::remove_var("DIESEL_DERIVE_ENUM_CONFIG");
+}
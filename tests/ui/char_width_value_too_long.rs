@@ -0,0 +1,11 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+#[DbCharWidth = 4]
+pub enum Region {
+    Us,
+    Emea,
+    Latam,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+pub enum Duplicated {
+    FooBar,
+    #[db_rename = "FOO_BAR"]
+    Other,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+pub enum Numbered {
+    #[db_value = 10]
+    Ten,
+    Eleven,
+}
+
+fn main() {}
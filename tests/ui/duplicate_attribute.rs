@@ -0,0 +1,10 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+#[PgType = "first_type"]
+#[db_enum(pg_type = "second_type")]
+pub enum TwoTypes {
+    Foo,
+}
+
+fn main() {}
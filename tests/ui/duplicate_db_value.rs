@@ -0,0 +1,13 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+#[DbStorageByBackend(sqlite = "integer")]
+pub enum Duplicated {
+    #[db_value = 1]
+    One,
+    Two,
+    #[db_value = 2]
+    AlsoTwo,
+}
+
+fn main() {}
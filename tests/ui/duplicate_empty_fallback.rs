@@ -0,0 +1,12 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+pub enum Reply {
+    Yes,
+    #[db_empty_fallback]
+    No,
+    #[db_empty_fallback]
+    Unknown,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+pub enum Duplicated {
+    FooBar,
+    #[db_rename = "foo_bar"]
+    Other,
+}
+
+fn main() {}
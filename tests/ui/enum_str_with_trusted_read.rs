@@ -0,0 +1,11 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+#[DbEnumStr = "OpenStatus"]
+#[DbEnumTrustedRead]
+pub enum Status {
+    Open,
+    Closed,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+#[ExistingTypeAlias = "StatusMapping"]
+pub enum Status {
+    Open,
+}
+
+fn main() {}
@@ -0,0 +1,5 @@
+use diesel_derive_enum::db_enum_from_sql;
+
+db_enum_from_sql!("missing.sql", "status");
+
+fn main() {}
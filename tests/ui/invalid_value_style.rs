@@ -0,0 +1,9 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+#[DbValueStyle = "shouting_case"]
+pub enum BadStyle {
+    Foo,
+}
+
+fn main() {}
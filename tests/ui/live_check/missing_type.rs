@@ -0,0 +1,9 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+#[PgType = "live_check_missing"]
+pub enum Missing {
+    Foo,
+}
+
+fn main() {}
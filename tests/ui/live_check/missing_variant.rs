@@ -0,0 +1,10 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+pub enum LiveCheckMood {
+    #[db_null_variant]
+    Unknown,
+    Happy,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+pub enum LiveCheckMood {
+    Happy,
+    Sad,
+    Angry,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use diesel_derive_enum::DbEnum;
+
+fn main() {
+    #[derive(Debug, DbEnum)]
+    #[DbEnumLocal]
+    #[DbEnumDsl]
+    enum Local {
+        Foo,
+    }
+}
@@ -0,0 +1,22 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+#[MaxDbLen = 8]
+pub enum Tier {
+    Basic,
+    Standard,
+    EnterpriseGold,
+}
+
+#[derive(Debug, DbEnum)]
+#[db_enum(max_db_len = 4)]
+pub enum Size {
+    #[db_rename = "s"]
+    Small,
+    #[db_rename = "xl"]
+    ExtraLarge,
+    #[db_rename = "xxxxl"]
+    Huge,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+#[DbValueStyle = "shouting"]
+#[db_enum(unknown_key)]
+pub enum Broken {
+    #[db_rename = 42]
+    First,
+    Second(u8),
+    #[db_rename = "first"]
+    Third,
+    #[db_rename = "second"]
+    Fourth,
+}
+
+fn main() {}
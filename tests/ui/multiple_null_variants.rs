@@ -0,0 +1,11 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+pub enum TwoNulls {
+    #[db_null_variant]
+    Unknown,
+    #[db_null_variant]
+    Missing,
+}
+
+fn main() {}
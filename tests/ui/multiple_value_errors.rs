@@ -0,0 +1,13 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+pub enum Duplicates {
+    First,
+    #[db_rename = "first"]
+    Again,
+    Second,
+    #[db_rename = "second"]
+    OnceMore,
+}
+
+fn main() {}
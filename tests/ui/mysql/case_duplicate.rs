@@ -0,0 +1,11 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+pub enum Grade {
+    #[db_rename = "A"]
+    Upper,
+    #[db_rename = "a"]
+    Lower,
+}
+
+fn main() {}
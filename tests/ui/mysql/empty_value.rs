@@ -0,0 +1,11 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+pub enum Reply {
+    Yes,
+    No,
+    #[db_rename = ""]
+    NoAnswer,
+}
+
+fn main() {}
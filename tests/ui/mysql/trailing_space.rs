@@ -0,0 +1,9 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+pub enum TrailingSpace {
+    #[db_rename = "padded "]
+    Padded,
+}
+
+fn main() {}
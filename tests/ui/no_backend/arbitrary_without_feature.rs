@@ -0,0 +1,9 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, Clone, DbEnum)]
+#[DbEnumArbitrary]
+pub enum NoArbitrary {
+    Foo,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+#[DbArrayStorage = "json"]
+pub enum NoTextBackend {
+    Foo,
+}
+
+fn main() {}
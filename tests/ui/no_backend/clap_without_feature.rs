@@ -0,0 +1,9 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, Clone, DbEnum)]
+#[DbEnumClap]
+pub enum NoClap {
+    Foo,
+}
+
+fn main() {}
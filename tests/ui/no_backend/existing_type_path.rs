@@ -0,0 +1,9 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+#[ExistingTypePath = "crate::schema::sql_types::MyEnum"]
+pub enum NoPostgres {
+    Foo,
+}
+
+fn main() {}
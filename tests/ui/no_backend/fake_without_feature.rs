@@ -0,0 +1,9 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, Clone, DbEnum)]
+#[DbEnumFake]
+pub enum NoFake {
+    Foo,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, Clone, DbEnum)]
+#[DbEnumRocket]
+pub enum NoRocket {
+    Foo,
+}
+
+fn main() {}
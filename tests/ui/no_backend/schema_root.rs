@@ -0,0 +1,9 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+#[DbSchemaRoot = "crate::schema::sql_types"]
+pub enum NoPostgres {
+    Foo,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, Clone, DbEnum)]
+#[DbEnumSqlx]
+pub enum NoSqlx {
+    Foo,
+}
+
+fn main() {}
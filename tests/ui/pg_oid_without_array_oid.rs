@@ -0,0 +1,10 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+#[PgOid = 16385]
+pub enum Status {
+    Active,
+    Inactive,
+}
+
+fn main() {}
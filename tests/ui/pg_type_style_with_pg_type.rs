@@ -0,0 +1,10 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+#[PgType = "ticket_state"]
+#[PgTypeStyle = "PascalCase"]
+pub enum TicketState {
+    Open,
+}
+
+fn main() {}
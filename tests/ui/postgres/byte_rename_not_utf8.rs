@@ -0,0 +1,10 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+pub enum Season {
+    #[db_rename = b"\xE9t\xE9"]
+    Summer,
+    Winter,
+}
+
+fn main() {}
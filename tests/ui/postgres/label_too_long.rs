@@ -0,0 +1,10 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+pub enum LongLabel {
+    Short,
+    #[db_rename = "très très très très très très très très très très très long"]
+    Long,
+}
+
+fn main() {}
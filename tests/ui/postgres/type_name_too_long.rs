@@ -0,0 +1,10 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+#[PgType = "a_postgres_type_name_which_is_longer_than_the_limit_of_sixty_three_bytes"]
+pub enum Status {
+    Active,
+    Inactive,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+#[DbReadOnly]
+#[DbWriteOnly]
+enum Foo {
+    Bar,
+    Baz,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+#[DbValueStyle = "camelCase"]
+#[DbRenameAll = "kebab-case"]
+pub enum BothStyles {
+    Foo,
+}
+
+fn main() {}
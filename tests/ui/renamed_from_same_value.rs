@@ -0,0 +1,10 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+pub enum Plan {
+    Free,
+    #[db_renamed_from = "pro"]
+    Pro,
+}
+
+fn main() {}
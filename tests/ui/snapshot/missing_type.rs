@@ -0,0 +1,8 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+pub enum Missing {
+    Foo,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+pub enum SnapshotMood {
+    Happy,
+    Angry,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+#[DbStorageByBackend(postgres = "text", sqlite = "enum")]
+pub enum SqliteEnum {
+    Foo,
+}
+
+fn main() {}
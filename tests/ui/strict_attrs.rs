@@ -0,0 +1,14 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+#[DbEnumStrictAttrs]
+#[db_rename = "state"]
+pub enum State {
+    #[ExistingTypePath = "crate::schema::sql_types::State"]
+    Active,
+    #[db_rename = "off"]
+    #[DbValueStyle = "kebab-case"]
+    Inactive,
+}
+
+fn main() {}
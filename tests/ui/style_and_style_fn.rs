@@ -0,0 +1,14 @@
+use diesel_derive_enum::DbEnum;
+
+fn my_style(name: &str) -> String {
+    name.to_string()
+}
+
+#[derive(Debug, DbEnum)]
+#[DbValueStyle = "camelCase"]
+#[DbValueStyleFn = "my_style"]
+pub enum BothStyles {
+    Foo,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use diesel_derive_enum::DbEnum;
+
+fn shout(name: &str) -> String {
+    name.to_uppercase()
+}
+
+#[derive(Debug, DbEnum)]
+#[DbValueStyleFn = "shout"]
+#[DbEnumTrustedRead]
+pub enum Styled {
+    Loud,
+    Quiet,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+#[db_enum(codegen(inline, small))]
+pub enum UnknownCodegen {
+    Foo,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+#[db_enum(pg_type = "foo", colour = "blue")]
+pub enum UnknownKey {
+    Foo,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+#[DbEnumSkip(queryable, insertable)]
+pub enum UnknownSkip {
+    Foo,
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, DbEnum)]
+#[DbStorageByBackend(mysql = "unsigned_integer")]
+pub enum Negative {
+    #[db_value(-1)]
+    Unknown,
+    Known,
+}
+
+#[derive(Debug, DbEnum)]
+#[DbStorageByBackend(postgres = "unsigned_integer")]
+pub enum NotMysql {
+    A,
+    B,
+}
+
+fn main() {}
@@ -0,0 +1,141 @@
+#![cfg(test)]
+#![allow(dead_code)]
+// diesel 1's own derives put their impls in a function
+#![allow(unknown_lints, non_local_definitions)]
+
+#[macro_use]
+extern crate diesel;
+
+use diesel::connection::SimpleConnection;
+use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, PartialEq, Clone, DbEnum)]
+#[PgType = "diesel_1_status"]
+pub enum Status {
+    Active,
+    #[db_rename = "on hold"]
+    OnHold,
+    ArchivedForever,
+}
+
+table! {
+    use diesel::sql_types::{Integer, Nullable};
+    use super::StatusMapping;
+    tickets {
+        id -> Integer,
+        status -> StatusMapping,
+        previous -> Nullable<StatusMapping>,
+    }
+}
+
+#[derive(Insertable, Queryable, Debug, Clone, PartialEq)]
+#[table_name = "tickets"]
+pub struct Ticket {
+    pub id: i32,
+    pub status: Status,
+    pub previous: Option<Status>,
+}
+
+#[cfg(feature = "postgres")]
+fn get_connection() -> PgConnection {
+    let database_url =
+        std::env::var("PG_TEST_DATABASE_URL").expect("Env var PG_TEST_DATABASE_URL not set");
+    let conn = PgConnection::establish(&database_url)
+        .unwrap_or_else(|_| panic!("Failed to connect to {}", database_url));
+    conn.batch_execute(
+        "SET search_path TO pg_temp;
+         CREATE TYPE diesel_1_status AS ENUM ('active', 'on hold', 'archived_forever');
+         CREATE TABLE tickets (
+             id INTEGER PRIMARY KEY,
+             status diesel_1_status NOT NULL,
+             previous diesel_1_status
+         );",
+    )
+    .unwrap();
+    conn
+}
+
+#[cfg(feature = "mysql")]
+fn get_connection() -> MysqlConnection {
+    let database_url = std::env::var("MYSQL_TEST_DATABASE_URL")
+        .expect("Env var MYSQL_TEST_DATABASE_URL not set");
+    let conn = MysqlConnection::establish(&database_url)
+        .unwrap_or_else(|_| panic!("Failed to connect to {}", database_url));
+    conn.batch_execute(
+        "CREATE TEMPORARY TABLE tickets (
+             id INTEGER PRIMARY KEY,
+             status ENUM('active', 'on hold', 'archived_forever') NOT NULL,
+             previous ENUM('active', 'on hold', 'archived_forever')
+         );",
+    )
+    .unwrap();
+    conn
+}
+
+#[cfg(feature = "sqlite")]
+fn get_connection() -> SqliteConnection {
+    let conn = SqliteConnection::establish(":memory:").unwrap();
+    conn.batch_execute(
+        "CREATE TABLE tickets (
+             id INTEGER PRIMARY KEY,
+             status TEXT NOT NULL,
+             previous TEXT
+         );",
+    )
+    .unwrap();
+    conn
+}
+
+#[test]
+fn round_trip() {
+    let conn = get_connection();
+    let data = vec![
+        Ticket {
+            id: 1,
+            status: Status::Active,
+            previous: None,
+        },
+        Ticket {
+            id: 2,
+            status: Status::ArchivedForever,
+            previous: Some(Status::OnHold),
+        },
+    ];
+    diesel::insert_into(tickets::table)
+        .values(&data)
+        .execute(&conn)
+        .unwrap();
+    let loaded: Vec<Ticket> = tickets::table
+        .order(tickets::id)
+        .load(&conn)
+        .unwrap();
+    assert_eq!(loaded, data);
+
+    let archived: Vec<i32> = tickets::table
+        .filter(tickets::status.eq(Status::ArchivedForever))
+        .select(tickets::id)
+        .load(&conn)
+        .unwrap();
+    assert_eq!(archived, vec![2]);
+    let on_hold: Option<Status> = tickets::table
+        .filter(tickets::previous.eq(Status::OnHold))
+        .select(tickets::previous)
+        .first(&conn)
+        .unwrap();
+    assert_eq!(on_hold, Some(Status::OnHold));
+}
+
+#[test]
+fn update() {
+    let conn = get_connection();
+    conn.batch_execute("INSERT INTO tickets (id, status) VALUES (1, 'active')")
+        .unwrap();
+    diesel::update(tickets::table)
+        .set(tickets::status.eq(Status::OnHold))
+        .execute(&conn)
+        .unwrap();
+    let status: Status = tickets::table.select(tickets::status).first(&conn).unwrap();
+    assert_eq!(status, Status::OnHold);
+    assert_eq!(Status::all().len(), 3);
+}
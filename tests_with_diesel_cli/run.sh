@@ -15,6 +15,7 @@ rm -f src/schema.rs diesel.toml
 diesel setup
 diesel migration run
 cargo test
+cargo test --features sqlite
 diesel migration revert
 rm src/schema.rs diesel.toml
 
@@ -51,4 +51,36 @@ mod tests {
             .load(&mut conn)
             .unwrap();
     }
+
+    #[cfg(all(feature = "sqlite", not(feature = "custom")))]
+    #[test]
+    fn sqlite_alongside_existing_type() {
+        use diesel::connection::SimpleConnection;
+        use diesel::sqlite::SqliteConnection;
+
+        table! {
+            use diesel::sql_types::Integer;
+            use crate::with_default_schema::MyEnumMapping;
+            simple {
+                id -> Integer,
+                some_value -> MyEnumMapping,
+            }
+        }
+
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        conn.batch_execute(
+            "CREATE TABLE simple (id INTEGER PRIMARY KEY, some_value TEXT NOT NULL)",
+        )
+        .unwrap();
+        diesel::insert_into(simple::table)
+            .values((simple::id.eq(1), simple::some_value.eq(MyEnum::BazQuxx)))
+            .execute(&mut conn)
+            .unwrap();
+        let value: MyEnum = simple::table
+            .select(simple::some_value)
+            .filter(simple::some_value.eq(MyEnum::BazQuxx))
+            .get_result(&mut conn)
+            .unwrap();
+        assert_eq!(value, MyEnum::BazQuxx);
+    }
 }